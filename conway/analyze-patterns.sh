@@ -137,6 +137,14 @@ fn main() {
                 } else {
                     eprintln!("Error creating file: {}", comparison_file);
                 }
+                
+                // And the same comparison as CSV for spreadsheets
+                let csv_file = format!("{}-comparison.csv", file_prefix);
+                if let Err(e) = std::fs::write(&csv_file, analyzer.comparison_to_csv(&stats)) {
+                    eprintln!("Error writing to file {}: {}", csv_file, e);
+                } else {
+                    println!("Comparison CSV saved to: {}", csv_file);
+                }
             }
         },
         pattern_name => {
@@ -147,8 +155,14 @@ fn main() {
                 let x = grid_size.0 / 2 - pattern.width / 2;
                 let y = grid_size.1 / 2 - pattern.height / 2;
                 
-                // Analyze the pattern
-                let stats = analyzer.analyze_pattern(pattern, x, y);
+                // Analyze the pattern, printing a dot every 100 generations
+                let stats = analyzer.analyze_pattern_with_progress(&pattern, x, y, |generation, _| {
+                    if generation % 100 == 0 {
+                        print!(".");
+                        std::io::stdout().flush().ok();
+                    }
+                });
+                println!();
                 let report = stats.generate_report();
                 
                 // Print the report
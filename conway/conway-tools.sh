@@ -271,7 +271,7 @@ play_game() {
     read -p "Enter theme (classic, block, dot) [default: block]: " theme
     theme=${theme:-block}
     
-    read -p "Enter color theme (green, blue, rainbow) [default: green]: " color
+    read -p "Enter color theme (green, blue, rainbow, spectrum, viridis) [default: green]: " color
     color=${color:-green}
     
     read -p "Enter initial pattern (leave empty for random): " pattern
@@ -1,7 +1,8 @@
 // Conway's Game of Life Benchmark Example
 
 use std::env;
-use conway::benchmark::{run_size_benchmarks, run_pattern_benchmarks};
+use conway::benchmark::{run_benchmark_stats, run_size_benchmarks, run_pattern_benchmarks};
+use conway::config::BoundaryType;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -34,7 +35,7 @@ fn main() {
             println!("Results:");
             println!("---------");
             for result in results {
-                println!("{}", result.to_string());
+                println!("{}", result);
             }
         },
         "pattern" => {
@@ -47,7 +48,22 @@ fn main() {
             println!("Results:");
             println!("---------");
             for result in results {
-                println!("{}", result.to_string());
+                println!("{}", result);
+            }
+        },
+        "stats" => {
+            let runs = if args.len() > 4 {
+                args[4].parse().unwrap_or(10)
+            } else {
+                10
+            };
+            
+            println!("Running {} timed runs after a warmup on a {}x{} grid for {} generations",
+                    runs, max_size, max_size, generations);
+            println!();
+            
+            for boundary in [BoundaryType::Wrap, BoundaryType::Fixed] {
+                println!("{}", run_benchmark_stats(max_size, max_size, generations, boundary, None, 0.3, runs));
             }
         },
         _ => {
@@ -60,7 +76,7 @@ fn main() {
             println!("---------------");
             let size_results = run_size_benchmarks(max_size, generations);
             for result in size_results {
-                println!("{}", result.to_string());
+                println!("{}", result);
             }
             
             println!();
@@ -69,7 +85,7 @@ fn main() {
             let pattern_size = std::cmp::min(250, max_size);
             let pattern_results = run_pattern_benchmarks(pattern_size, pattern_size, generations);
             for result in pattern_results {
-                println!("{}", result.to_string());
+                println!("{}", result);
             }
         }
     }
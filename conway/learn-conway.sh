@@ -23,165 +23,10 @@ print_header() {
 
 # Check if the executable exists and build if needed
 check_executable() {
-    if [[ ! -f "$SCRIPT_DIR/target/release/examples/tutorial" ]]; then
-        echo -e "${YELLOW}Building Conway's Game of Life Tutorial...${NC}"
+    if [[ ! -f "$SCRIPT_DIR/target/release/conway" ]]; then
+        echo -e "${YELLOW}Building Conway's Game of Life...${NC}"
         
-        # Create the tutorial example if it doesn't exist
-        if [[ ! -f "$SCRIPT_DIR/examples/tutorial.rs" ]]; then
-            echo -e "${YELLOW}Creating tutorial runner...${NC}"
-            mkdir -p "$SCRIPT_DIR/examples"
-            
-            cat > "$SCRIPT_DIR/examples/tutorial.rs" << 'EOF'
-// Conway's Game of Life Tutorial Runner
-
-use std::io::{self, Write};
-use std::time::Duration;
-use std::thread;
-use crossterm::{
-    cursor::{Hide, MoveTo, Show},
-    event::{self, Event, KeyCode},
-    execute,
-    style::{Color, Print, SetBackgroundColor, SetForegroundColor, ResetColor},
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
-};
-
-use conway::tutorial::{Tutorial, Action, UserInputType};
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Setup terminal
-    terminal::enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(
-        stdout,
-        EnterAlternateScreen,
-        Hide
-    )?;
-    
-    // Create tutorial
-    let mut tutorial = Tutorial::new();
-    let mut running = true;
-    
-    while running {
-        // Clear screen
-        execute!(
-            stdout,
-            Clear(ClearType::All),
-            MoveTo(0, 0)
-        )?;
-        
-        // Get current step
-        let step = tutorial.current_step();
-        
-        // Print step title and description
-        execute!(
-            stdout,
-            SetForegroundColor(Color::Yellow),
-            Print(format!("Step {}: {}\n\n", tutorial.current_step() + 1, step.title)),
-            ResetColor,
-            Print(format!("{}\n\n", step.description))
-        )?;
-        
-        // Render grid
-        render_grid(tutorial.grid(), &mut stdout)?;
-        
-        // Print instructions
-        execute!(
-            stdout,
-            MoveTo(0, 25),
-            SetForegroundColor(Color::Cyan),
-            Print("\nInstructions: Press SPACE to advance to the next action, ENTER to skip to the next step, 'q' to quit\n"),
-            ResetColor
-        )?;
-        
-        // Wait for user input
-        match wait_for_key()? {
-            KeyCode::Char('q') => {
-                running = false;
-                break;
-            },
-            KeyCode::Enter => {
-                if !tutorial.next_step(0) {
-                    // No more steps, exit
-                    running = false;
-                    break;
-                }
-                continue;
-            },
-            _ => {
-                // Execute next action
-                if !tutorial.execute_actions(0) {
-                    // No more actions, move to next step
-                    if !tutorial.next_step(0) {
-                        // No more steps, exit
-                        running = false;
-                        break;
-                    }
-                }
-            }
-        }
-    }
-    
-    // Clean up
-    execute!(
-        stdout,
-        Show,
-        LeaveAlternateScreen
-    )?;
-    terminal::disable_raw_mode()?;
-    
-    println!("Thank you for exploring Conway's Game of Life!");
-    
-    Ok(())
-}
-
-// Render the grid
-fn render_grid(grid: &conway::grid::Grid, stdout: &mut io::Stdout) -> crossterm::Result<()> {
-    execute!(
-        stdout,
-        MoveTo(0, 5)
-    )?;
-    
-    let (width, height) = grid.dimensions();
-    let display_height = std::cmp::min(height, 15);
-    
-    for y in 0..display_height {
-        for x in 0..width {
-            let cell_char = if grid.get(x, y) { "█" } else { " " };
-            
-            if grid.get(x, y) {
-                execute!(
-                    stdout,
-                    SetForegroundColor(Color::Green),
-                    Print(cell_char),
-                    ResetColor
-                )?;
-            } else {
-                execute!(
-                    stdout,
-                    Print(cell_char)
-                )?;
-            }
-        }
-        execute!(stdout, Print("\n"))?;
-    }
-    
-    Ok(())
-}
-
-// Wait for a key press
-fn wait_for_key() -> crossterm::Result<KeyCode> {
-    loop {
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key_event) = event::read()? {
-                return Ok(key_event.code);
-            }
-        }
-    }
-}
-EOF
-        }
-        
-        cd "$SCRIPT_DIR" && cargo build --release --example tutorial
+        cd "$SCRIPT_DIR" && cargo build --release
         if [ $? -ne 0 ]; then
             echo -e "${RED}Failed to build the tutorial!${NC}"
             exit 1
@@ -213,7 +58,7 @@ main() {
     check_executable
     
     # Run the tutorial
-    "$SCRIPT_DIR/target/release/examples/tutorial"
+    "$SCRIPT_DIR/target/release/conway" --tutorial
     
     # Return to the script after tutorial finishes
     echo
@@ -1,12 +1,16 @@
 // Conway's Game of Life Pattern Analyzer
 // Analyzes patterns and their behavior over time
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use crate::grid::Grid;
-use crate::patterns::Pattern;
+use crate::patterns::{Pattern, PatternLibrary};
 use crate::config::BoundaryType;
+use crate::rle;
 
 /// Represents the life cycle classification of a pattern
 #[derive(Debug, Clone, PartialEq)]
@@ -40,6 +44,235 @@ pub enum PatternType {
     Unknown,
 }
 
+/// The broad class of a `PatternType`, without its measurements, for grouping patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatternKind {
+    Extinct,
+    Stable,
+    Exploding,
+    Spaceship,
+    Emitter,
+    Unknown,
+}
+
+impl PatternType {
+    /// The broad class of this pattern type
+    pub fn kind(&self) -> PatternKind {
+        match self {
+            PatternType::ExtinctPattern { .. } => PatternKind::Extinct,
+            PatternType::StablePattern { .. } => PatternKind::Stable,
+            PatternType::ExplodingPattern { .. } => PatternKind::Exploding,
+            PatternType::SpaceshipPattern { .. } => PatternKind::Spaceship,
+            PatternType::PatternEmitter { .. } => PatternKind::Emitter,
+            PatternType::Unknown => PatternKind::Unknown,
+        }
+    }
+}
+
+/// Express a spaceship's displacement per period in the standard c/n notation,
+/// e.g. a glider moving (1, 1) every 4 generations is "c/4 diagonal"
+pub fn velocity_notation(displacement: (isize, isize), period: usize) -> String {
+    let (ax, ay) = (displacement.0.unsigned_abs(), displacement.1.unsigned_abs());
+    let (major, minor) = (ax.max(ay), ax.min(ay));
+    
+    // Nothing moves faster than one cell per generation (the speed of light, c)
+    if period == 0 || major == 0 || major > period {
+        return format!("({}, {}) per {} generations", displacement.0, displacement.1, period);
+    }
+    
+    let speed = |cells: usize| {
+        let divisor = gcd(cells, period);
+        match (cells / divisor, period / divisor) {
+            (1, 1) => "c".to_string(),
+            (1, n) => format!("c/{}", n),
+            (m, 1) => format!("{}c", m),
+            (m, n) => format!("{}c/{}", m, n),
+        }
+    };
+    
+    if minor == 0 {
+        format!("{} orthogonal", speed(major))
+    } else if minor == major {
+        format!("{} diagonal", speed(major))
+    } else {
+        format!("({},{})c/{} oblique", major, minor, period)
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Start of the occupied span along a wrapped axis: the first position after the longest
+/// run of empty positions, so coordinates measured from it never cross the seam
+fn seam_origin(length: usize, positions: impl Iterator<Item = usize>) -> usize {
+    let mut occupied = vec![false; length];
+    for position in positions {
+        occupied[position] = true;
+    }
+    
+    let (mut best_end, mut best_len, mut run) = (0, 0, 0);
+    for i in 0..2 * length {
+        if occupied[i % length] {
+            run = 0;
+        } else {
+            run += 1;
+            if run > best_len && run <= length {
+                best_len = run;
+                best_end = i % length;
+            }
+        }
+    }
+    
+    if best_len == 0 { 0 } else { (best_end + 1) % length }
+}
+
+/// Side length of the blocks the grid is partitioned into when measuring entropy
+const ENTROPY_BLOCK_SIZE: usize = 4;
+
+/// Shannon entropy, in bits, of the live-cell counts of the grid's `block_size` square blocks.
+/// An empty or uniform grid scores 0; a grid whose blocks take many different densities
+/// scores higher, up to log2(block_size² + 1). Partial blocks at the edges are ignored.
+pub fn spatial_entropy(grid: &Grid, block_size: usize) -> f64 {
+    let (width, height) = grid.dimensions();
+    if block_size == 0 || width < block_size || height < block_size {
+        return 0.0;
+    }
+    
+    // Histogram of how many blocks hold each possible number of live cells
+    let mut counts = vec![0usize; block_size * block_size + 1];
+    for block_y in (0..=height - block_size).step_by(block_size) {
+        for block_x in (0..=width - block_size).step_by(block_size) {
+            let alive: usize = (block_y..block_y + block_size)
+                .map(|y| grid.count_alive_in_row(y, block_x, block_x + block_size))
+                .sum();
+            counts[alive] += 1;
+        }
+    }
+    
+    let blocks = (width / block_size * (height / block_size)) as f64;
+    counts.iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / blocks;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Longest emitter period looked for, enough for the common guns
+const MAX_EMITTER_PERIOD: usize = 60;
+
+/// Number of consecutive periods the core of an emitter must repeat before it is trusted
+const EMITTER_CONFIRMING_PERIODS: usize = 2;
+
+/// Generations an emitted object is run for on its own to classify it
+const EMITTED_ANALYSIS_GENERATIONS: usize = 100;
+
+/// Dead cells left around an object when it is analyzed on its own
+const ISOLATION_MARGIN: usize = 8;
+
+/// Largest gap between live cells of one component, so the separate quadrants of
+/// oscillators like the pulsar are treated as a single object
+const COMPONENT_REACH: usize = 2;
+
+/// Flood fill from `start` through the live cells, treating cells within `reach` of each
+/// other in both directions as connected. Every cell gathered is added to `visited`.
+fn gather_object(
+    live: &HashSet<(usize, usize)>,
+    start: (usize, usize),
+    reach: usize,
+    visited: &mut HashSet<(usize, usize)>,
+) -> Vec<(usize, usize)> {
+    visited.insert(start);
+    let mut object = vec![start];
+    let mut index = 0;
+    while index < object.len() {
+        let (x, y) = object[index];
+        index += 1;
+        for ny in y.saturating_sub(reach)..=y + reach {
+            for nx in x.saturating_sub(reach)..=x + reach {
+                if live.contains(&(nx, ny)) && visited.insert((nx, ny)) {
+                    object.push((nx, ny));
+                }
+            }
+        }
+    }
+    object
+}
+
+/// Copy an object onto its own torus with `ISOLATION_MARGIN` dead cells on every side
+fn isolate_object(object: &[(usize, usize)]) -> Grid {
+    let left = object.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let top = object.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let width = object.iter().map(|&(x, _)| x - left + 1).max().unwrap_or(0) + 2 * ISOLATION_MARGIN;
+    let height = object.iter().map(|&(_, y)| y - top + 1).max().unwrap_or(0) + 2 * ISOLATION_MARGIN;
+    
+    let mut isolated = Grid::new(width, height, BoundaryType::Wrap);
+    for &(x, y) in object {
+        isolated.set(x - left + ISOLATION_MARGIN, y - top + ISOLATION_MARGIN, true);
+    }
+    isolated
+}
+
+/// Well-known oscillators, by library name and period, that a settled pattern is matched against
+const KNOWN_OSCILLATORS: [(&str, usize); 5] = [
+    ("Blinker", 2),
+    ("Toad", 2),
+    ("Beacon", 2),
+    ("Pulsar", 3),
+    ("Pentadecathlon", 15),
+];
+
+/// Live cells moved to start at (0, 0) and sorted, so equal shapes compare equal wherever they are
+fn normalized_shape(cells: impl Iterator<Item = (isize, isize)>) -> Vec<(isize, isize)> {
+    let mut cells: Vec<(isize, isize)> = cells.collect();
+    let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    for cell in &mut cells {
+        *cell = (cell.0 - left, cell.1 - top);
+    }
+    cells.sort_unstable();
+    cells
+}
+
+/// Name the oscillator making up the whole of a grid that cycles with the given period, if it
+/// is one of `KNOWN_OSCILLATORS` in any phase, rotation or reflection. Grids holding anything
+/// else besides, such as leftover debris, go unnamed.
+fn name_oscillator(grid: &Grid, period: usize) -> Option<String> {
+    let shape = normalized_shape(grid.live_cells().map(|(x, y)| (x as isize, y as isize)));
+    
+    KNOWN_OSCILLATORS.iter()
+        .filter(|&&(_, known_period)| known_period == period)
+        .filter_map(|&(name, _)| PatternLibrary::get_by_name(name))
+        .find(|pattern| {
+            let mut phase = isolate_object(&pattern.cells);
+            (0..period).any(|_| {
+                let cells: Vec<(isize, isize)> = phase.live_cells().map(|(x, y)| (x as isize, y as isize)).collect();
+                phase.update();
+                
+                // The eight rotations and reflections of the phase
+                (0..8).any(|symmetry| {
+                    let transformed = cells.iter().map(|&(x, y)| {
+                        let (x, y) = if symmetry & 4 != 0 { (y, x) } else { (x, y) };
+                        (if symmetry & 1 != 0 { -x } else { x }, if symmetry & 2 != 0 { -y } else { y })
+                    });
+                    normalized_shape(transformed) == shape
+                })
+            })
+        })
+        .map(|pattern| pattern.name.to_string())
+}
+
 /// Detailed statistics about a pattern's evolution
 #[derive(Debug, Clone)]
 pub struct PatternStats {
@@ -52,6 +285,10 @@ pub struct PatternStats {
     pub pattern_type: PatternType,
     pub stable_formations: HashMap<String, usize>, // Formation name -> count
     pub population_history: Vec<usize>,
+    pub oscillator_phase_populations: Option<Vec<usize>>, // Population in each phase of one period
+    pub entropy_history: Vec<f64>, // Spatial entropy of each generation, in bits
+    pub density_profile: Option<(Vec<usize>, Vec<usize>)>, // Live cells per row and per column at the end, for exploding patterns and emitters
+    pub detected_name: Option<String>, // Name of the well-known oscillator the pattern settled into
     pub analysis_duration: Duration,
 }
 
@@ -67,10 +304,22 @@ impl PatternStats {
             pattern_type: PatternType::Unknown,
             stable_formations: HashMap::new(),
             population_history: vec![initial_population],
+            oscillator_phase_populations: None,
+            entropy_history: Vec::new(),
+            density_profile: None,
+            detected_name: None,
             analysis_duration: Duration::from_secs(0),
         }
     }
     
+    /// The highest spatial entropy reached and the generation it occurred in
+    pub fn peak_entropy(&self) -> Option<(usize, f64)> {
+        self.entropy_history.iter()
+            .copied()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+    
     /// Generate a report of the pattern statistics
     pub fn generate_report(&self) -> String {
         let mut report = String::new();
@@ -93,11 +342,24 @@ impl PatternStats {
                 if let Some(period) = oscillator_period {
                     report.push_str(&format!("Oscillator with period {} (stabilized after {} generations)\n", 
                         period, generations_to_stabilize));
+                    if let Some(name) = &self.detected_name {
+                        report.push_str(&format!("Recognized as: {}\n", name));
+                    }
                 } else {
                     report.push_str(&format!("Still life (stabilized after {} generations)\n", 
                         generations_to_stabilize));
                 }
                 report.push_str(&format!("Final stable population: {}\n", final_population));
+                
+                if let Some(phases) = &self.oscillator_phase_populations {
+                    let min = phases.iter().min().unwrap_or(&0);
+                    let max = phases.iter().max().unwrap_or(&0);
+                    let average = phases.iter().sum::<usize>() as f64 / phases.len().max(1) as f64;
+                    
+                    let phase_list: Vec<String> = phases.iter().map(|pop| pop.to_string()).collect();
+                    report.push_str(&format!("Phase populations: {}\n", phase_list.join(", ")));
+                    report.push_str(&format!("Phase population min/max/avg: {}/{}/{:.1}\n", min, max, average));
+                }
             },
             PatternType::ExplodingPattern { average_growth_rate } => {
                 report.push_str(&format!("Exploding pattern (average growth rate: {:.2} cells/generation)\n", 
@@ -107,6 +369,7 @@ impl PatternStats {
                 report.push_str(&format!("Spaceship with period {} and displacement ({}, {})\n", 
                     period, displacement.0, displacement.1));
                 report.push_str(&format!("Speed: {:.2} cells/generation\n", speed));
+                report.push_str(&format!("Velocity: {}\n", velocity_notation(*displacement, *period)));
             },
             PatternType::PatternEmitter { period, emitted_pattern_type } => {
                 report.push_str(&format!("Pattern emitter with period {}\n", period));
@@ -117,6 +380,18 @@ impl PatternStats {
             },
         }
         
+        if let Some((generation, peak)) = self.peak_entropy() {
+            let average = self.entropy_history.iter().sum::<f64>() / self.entropy_history.len() as f64;
+            report.push_str(&format!("\nPeak spatial entropy: {:.3} bits (generation {})\n", peak, generation));
+            report.push_str(&format!("Average spatial entropy: {:.3} bits\n", average));
+        }
+        
+        if let Some((rows, columns)) = &self.density_profile {
+            report.push_str("\nDensity profile at the final generation:\n");
+            push_density_bars(&mut report, "Rows", rows);
+            push_density_bars(&mut report, "Columns", columns);
+        }
+        
         if !self.stable_formations.is_empty() {
             report.push_str("\nStable formations detected:\n");
             for (formation, count) in &self.stable_formations {
@@ -167,11 +442,68 @@ impl PatternStats {
     }
 }
 
+/// Most bars drawn for one axis of a density profile; longer axes are grouped into bands
+const DENSITY_PROFILE_BARS: usize = 40;
+
+/// Append a bar chart of live cells along one axis, covering only the occupied span.
+/// Axes longer than DENSITY_PROFILE_BARS are summed into bands of equal width.
+fn push_density_bars(report: &mut String, label: &str, densities: &[usize]) {
+    let (Some(first), Some(last)) = (
+        densities.iter().position(|&count| count > 0),
+        densities.iter().rposition(|&count| count > 0),
+    ) else {
+        report.push_str(&format!("{}: empty\n", label));
+        return;
+    };
+    
+    let span = &densities[first..=last];
+    let band = span.len().div_ceil(DENSITY_PROFILE_BARS);
+    let bands: Vec<usize> = span.chunks(band).map(|chunk| chunk.iter().sum()).collect();
+    let max = bands.iter().copied().max().unwrap_or(1).max(1);
+    
+    report.push_str(&format!("{} {}-{} ({} per bar):\n", label, first, last, band));
+    for (i, &count) in bands.iter().enumerate() {
+        let bar_length = (count as f64 * 40.0 / max as f64).round() as usize;
+        report.push_str(&format!("{:5}: {:5} {}\n", first + i * band, count, "#".repeat(bar_length)));
+    }
+}
+
+/// Results of analyzing every pattern file in a directory
+#[derive(Debug)]
+pub struct DirectoryAnalysis {
+    /// Stats for each pattern that loaded, in file name order
+    pub stats: Vec<PatternStats>,
+    /// Files that could not be read or parsed, with the reason
+    pub errors: Vec<(PathBuf, io::Error)>,
+}
+
+/// One connected group of live cells and how it behaves on its own
+#[derive(Debug, Clone)]
+pub struct ComponentStats {
+    /// Bounding box of the component in the analyzed grid, as (min_x, min_y, max_x, max_y)
+    pub bounds: (usize, usize, usize, usize),
+    pub population: usize,
+    pub pattern_type: PatternType,
+}
+
+impl ComponentStats {
+    /// Generations the component takes to repeat: 1 for a still life, the period of an
+    /// oscillator, spaceship or emitter, and `None` if it never settles
+    pub fn period(&self) -> Option<usize> {
+        match &self.pattern_type {
+            PatternType::StablePattern { oscillator_period, .. } => Some(oscillator_period.unwrap_or(1)),
+            PatternType::SpaceshipPattern { period, .. } | PatternType::PatternEmitter { period, .. } => Some(*period),
+            _ => None,
+        }
+    }
+}
+
 /// A pattern analyzer for Conway's Game of Life
 pub struct PatternAnalyzer {
     max_generations: usize,
     grid_size: (usize, usize),
     boundary: BoundaryType,
+    cycle_window: Option<usize>,
 }
 
 impl PatternAnalyzer {
@@ -180,37 +512,98 @@ impl PatternAnalyzer {
             max_generations,
             grid_size,
             boundary,
+            cycle_window: None,
         }
     }
     
+    /// Only remember the grid hashes of the last `window` generations when looking for cycles,
+    /// or every generation with None (the default). A window bounds the memory of very long
+    /// analyses, but cycles with a period longer than the window are no longer detected.
+    pub fn set_cycle_window(&mut self, window: Option<usize>) {
+        self.cycle_window = window.map(|window| window.max(1));
+    }
+    
+    /// Generations of grid hashes kept for cycle detection, None when all are kept
+    pub fn cycle_window(&self) -> Option<usize> {
+        self.cycle_window
+    }
+    
+    /// Size of the grid patterns are analyzed on
+    pub fn grid_size(&self) -> (usize, usize) {
+        self.grid_size
+    }
+    
     /// Analyze a pattern and return detailed statistics
     pub fn analyze_pattern(&self, pattern: &Pattern, x: usize, y: usize) -> PatternStats {
-        let start_time = Instant::now();
-        
+        self.analyze_pattern_with_progress(pattern, x, y, |_, _| {})
+    }
+    
+    /// Analyze a pattern, calling `on_generation` with the generation and population after
+    /// every step so long analyses can report progress
+    pub fn analyze_pattern_with_progress(
+        &self,
+        pattern: &Pattern,
+        x: usize,
+        y: usize,
+        mut on_generation: impl FnMut(usize, usize),
+    ) -> PatternStats {
         // Create a grid and place the pattern
         let mut grid = Grid::new(self.grid_size.0, self.grid_size.1, self.boundary.clone());
         pattern.place(&mut grid, x, y);
         
+        self.analyze_grid(pattern.name, grid, &mut on_generation)
+    }
+    
+    /// Analyze several patterns placed together on one grid, to study how they interact.
+    /// Cells are added on top of each other, so overlapping placements never erase earlier patterns.
+    pub fn analyze_composite(&self, placements: &[(&Pattern, usize, usize)]) -> PatternStats {
+        let mut grid = Grid::new(self.grid_size.0, self.grid_size.1, self.boundary.clone());
+        
+        for (pattern, x, y) in placements {
+            for &(px, py) in &pattern.cells {
+                grid.set(x + px, y + py, true);
+            }
+        }
+        
+        let names: Vec<&str> = placements.iter().map(|(pattern, _, _)| pattern.name).collect();
+        self.analyze_grid(&names.join(" + "), grid, &mut |_, _| {})
+    }
+    
+    /// Run the evolution loop on a prepared grid and collect statistics
+    fn analyze_grid(&self, name: &str, mut grid: Grid, on_generation: &mut dyn FnMut(usize, usize)) -> PatternStats {
+        let start_time = Instant::now();
+        
         // Initialize stats
-        let initial_population = grid.count_alive();
-        let mut stats = PatternStats::new(&pattern.name, initial_population);
+        let initial = grid.stats();
+        let mut stats = PatternStats::new(name, initial.population);
+        stats.entropy_history.push(spatial_entropy(&grid, ENTROPY_BLOCK_SIZE));
         
-        // Track grid hashes to detect cycles
+        // Track grid hashes to detect cycles, oldest first in the window order
         let mut grid_history: HashMap<u64, usize> = HashMap::new();
+        let mut window_order: VecDeque<u64> = VecDeque::new();
         let mut hash = self.hash_grid(&grid);
         grid_history.insert(hash, 0);
+        window_order.push_back(hash);
         
         // Track pattern center and detect movement
         let mut center_history: Vec<(usize, usize)> = Vec::new();
         center_history.push(self.find_pattern_center(&grid));
         
+        // Track the region the pattern started in, where an emitter's core keeps cycling
+        // while its output leaves
+        let core = initial.bounding_box;
+        let mut core_history: Vec<Option<u64>> = Vec::new();
+        core_history.push(core.and_then(|core| self.hash_region(&grid, core)));
+        
         for generation in 1..=self.max_generations {
             // Update the grid
             grid.update();
             
             // Update population stats
             let population = grid.count_alive();
+            on_generation(generation, population);
             stats.population_history.push(population);
+            stats.entropy_history.push(spatial_entropy(&grid, ENTROPY_BLOCK_SIZE));
             
             if population > stats.max_population {
                 stats.max_population = population;
@@ -219,6 +612,7 @@ impl PatternAnalyzer {
             
             // Find pattern center
             center_history.push(self.find_pattern_center(&grid));
+            core_history.push(core.and_then(|core| self.hash_region(&grid, core)));
             
             // Check for extinction
             if population == 0 {
@@ -228,26 +622,32 @@ impl PatternAnalyzer {
                 break;
             }
             
-            // Check for cycles (stable patterns)
+            // Check for still lifes, the most common way for a pattern to settle, by
+            // comparing with the hash of the generation before, whatever the cycle window
+            let previous_hash = hash;
             hash = self.hash_grid(&grid);
+            if hash == previous_hash {
+                stats.pattern_type = PatternType::StablePattern {
+                    generations_to_stabilize: generation - 1,
+                    oscillator_period: None,
+                    final_population: population,
+                };
+                break;
+            }
+            
+            // Check for longer cycles (oscillators)
             if let Some(previous_gen) = grid_history.get(&hash) {
                 let period = generation - previous_gen;
+                stats.pattern_type = PatternType::StablePattern {
+                    generations_to_stabilize: *previous_gen,
+                    oscillator_period: Some(period),
+                    final_population: population,
+                };
                 
-                // Determine if it's a still life or oscillator
-                if period == 1 {
-                    stats.pattern_type = PatternType::StablePattern {
-                        generations_to_stabilize: generation - 1,
-                        oscillator_period: None,
-                        final_population: population,
-                    };
-                } else {
-                    stats.pattern_type = PatternType::StablePattern {
-                        generations_to_stabilize: *previous_gen,
-                        oscillator_period: Some(period),
-                        final_population: population,
-                    };
-                }
-                
+                // The last P populations cover one full cycle of the oscillator
+                let history = &stats.population_history;
+                stats.oscillator_phase_populations = Some(history[history.len() - period..].to_vec());
+                stats.detected_name = name_oscillator(&grid, period);
                 break;
             }
             
@@ -259,9 +659,19 @@ impl PatternAnalyzer {
                 }
             }
             
+            // Check for emitters (a cycling core with a growing population), which
+            // would otherwise be taken for exploding patterns
+            if let (Some(core), Some(period)) = (core, self.detect_emitter(&core_history, &stats.population_history)) {
+                stats.pattern_type = PatternType::PatternEmitter {
+                    period,
+                    emitted_pattern_type: Box::new(self.classify_emitted(&grid, core)),
+                };
+                break;
+            }
+            
             // Detect if it's an exploding pattern (significant growth over time)
-            if generation > 50 && population > initial_population * 2 {
-                let growth_rate = (population - initial_population) as f64 / generation as f64;
+            if generation > 50 && population > initial.population * 2 {
+                let growth_rate = (population - initial.population) as f64 / generation as f64;
                 
                 if growth_rate > 0.1 {
                     stats.pattern_type = PatternType::ExplodingPattern {
@@ -271,8 +681,16 @@ impl PatternAnalyzer {
                 }
             }
             
-            // Store grid hash for cycle detection
+            // Store grid hash for cycle detection, forgetting the oldest beyond the window
             grid_history.insert(hash, generation);
+            if let Some(window) = self.cycle_window {
+                window_order.push_back(hash);
+                if window_order.len() > window {
+                    if let Some(oldest) = window_order.pop_front() {
+                        grid_history.remove(&oldest);
+                    }
+                }
+            }
         }
         
         // Update final stats
@@ -285,6 +703,11 @@ impl PatternAnalyzer {
             stats.stable_formations = self.identify_stable_formations(&grid);
         }
         
+        // Keep the marginal densities of growing patterns to show where debris is piling up
+        if let PatternType::ExplodingPattern { .. } | PatternType::PatternEmitter { .. } = stats.pattern_type {
+            stats.density_profile = Some((grid.row_densities(), grid.column_densities()));
+        }
+        
         stats
     }
     
@@ -295,42 +718,208 @@ impl PatternAnalyzer {
             .collect()
     }
     
+    /// Analyze every pattern in the `.rle` and `.cells` files of a directory, each centered on
+    /// its own grid. Files holding several patterns contribute one entry per pattern. Files that
+    /// fail to load are skipped and listed in the errors; only an unreadable directory is an error.
+    pub fn analyze_directory<P: AsRef<Path>>(&self, dir: P) -> io::Result<DirectoryAnalysis> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("");
+                matches!(extension.to_lowercase().as_str(), "rle" | "cells")
+            })
+            .collect();
+        paths.sort();
+        
+        let mut analysis = DirectoryAnalysis { stats: Vec::new(), errors: Vec::new() };
+        for path in paths {
+            let patterns = match rle::load_pattern_file(&path) {
+                Ok(patterns) => patterns,
+                Err(e) => {
+                    analysis.errors.push((path, e));
+                    continue;
+                },
+            };
+            
+            for pattern in patterns {
+                let mut grid = Grid::new(self.grid_size.0, self.grid_size.1, self.boundary.clone());
+                let x = self.grid_size.0.saturating_sub(pattern.width) / 2;
+                let y = self.grid_size.1.saturating_sub(pattern.height) / 2;
+                for &(px, py) in &pattern.cells {
+                    grid.set(x + px, y + py, true);
+                }
+                
+                // Unnamed patterns go by their file name
+                let name = if pattern.name.starts_with("Unnamed") {
+                    path.file_stem().map_or(pattern.name.clone(), |stem| stem.to_string_lossy().into_owned())
+                } else {
+                    pattern.name
+                };
+                analysis.stats.push(self.analyze_grid(&name, grid, &mut |_, _| {}));
+            }
+        }
+        
+        Ok(analysis)
+    }
+    
     /// Calculate a hash of the grid state for cycle detection
     fn hash_grid(&self, grid: &Grid) -> u64 {
+        grid.hash_state()
+    }
+    
+    /// Hash the live cells inside the (min_x, min_y, max_x, max_y) region, or None when it is empty
+    fn hash_region(&self, grid: &Grid, region: (usize, usize, usize, usize)) -> Option<u64> {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
         
+        let (min_x, min_y, max_x, max_y) = region;
         let mut hasher = DefaultHasher::new();
+        let mut alive = 0;
+        for (x, y) in grid.live_cells().filter(|&(x, y)| (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y)) {
+            (x, y).hash(&mut hasher);
+            alive += 1;
+        }
         
-        for y in 0..self.grid_size.1 {
-            for x in 0..self.grid_size.0 {
-                grid.get(x, y).hash(&mut hasher);
+        (alive > 0).then(|| hasher.finish())
+    }
+    
+    /// Detect an emitter from the history of its core region: the shortest period over which
+    /// the core has repeated for the last few periods while the population grew by the same
+    /// amount each period
+    fn detect_emitter(&self, core_history: &[Option<u64>], population_history: &[usize]) -> Option<usize> {
+        let last = core_history.len() - 1;
+        
+        (1..=MAX_EMITTER_PERIOD)
+            .take_while(|period| (EMITTER_CONFIRMING_PERIODS + 1) * period <= last)
+            .find(|&period| {
+                let core_repeats = (0..EMITTER_CONFIRMING_PERIODS * period).all(|back| {
+                    let state = core_history[last - back];
+                    state.is_some() && state == core_history[last - back - period]
+                });
+                
+                let growth: Vec<isize> = (0..EMITTER_CONFIRMING_PERIODS)
+                    .map(|i| {
+                        let end = last - i * period;
+                        population_history[end] as isize - population_history[end - period] as isize
+                    })
+                    .collect();
+                
+                core_repeats && growth[0] > 0 && growth.windows(2).all(|w| w[0] == w[1])
+            })
+    }
+    
+    /// Classify what an emitter sends out by cutting the nearest complete object that has
+    /// cleared the core out of the grid and analyzing it on its own
+    fn classify_emitted(&self, grid: &Grid, core: (usize, usize, usize, usize)) -> PatternType {
+        let (min_x, min_y, max_x, max_y) = core;
+        let live: HashSet<(usize, usize)> = grid.live_cells().collect();
+        
+        // Cells within two of the core may still be attached to it
+        let near_core = |&(x, y): &(usize, usize)| {
+            x + 2 >= min_x && x <= max_x + 2 && y + 2 >= min_y && y <= max_y + 2
+        };
+        let core_distance = |&(x, y): &(usize, usize)| {
+            let dx = min_x.saturating_sub(x).max(x.saturating_sub(max_x));
+            let dy = min_y.saturating_sub(y).max(y.saturating_sub(max_y));
+            dx.max(dy)
+        };
+        
+        let mut outside: Vec<(usize, usize)> = live.iter().copied().filter(|cell| !near_core(cell)).collect();
+        outside.sort_by_key(|cell| (core_distance(cell), *cell));
+        
+        let mut visited = HashSet::new();
+        for start in outside {
+            if !visited.insert(start) {
+                continue;
+            }
+            
+            // Gather the object this cell belongs to, counting diagonal neighbors as connected
+            let object = gather_object(&live, start, 1, &mut visited);
+            if object.iter().any(near_core) {
+                continue;
+            }
+            
+            let isolated = isolate_object(&object);
+            let analyzer = PatternAnalyzer::new(EMITTED_ANALYSIS_GENERATIONS, isolated.dimensions(), BoundaryType::Wrap);
+            return analyzer.analyze_grid("Emitted", isolated, &mut |_, _| {}).pattern_type;
+        }
+        
+        PatternType::Unknown
+    }
+    
+    /// Split a grid into its connected components and analyze each one on its own, so a
+    /// still life next to a pulsar reports periods 1 and 3 instead of the combined period.
+    /// Cells up to two apart belong to the same component. Components are returned from the
+    /// top left, and a component crossing a wrapped edge is split into its two halves.
+    pub fn analyze_components(&self, grid: &Grid) -> Vec<ComponentStats> {
+        let live: HashSet<(usize, usize)> = grid.live_cells().collect();
+        let mut starts: Vec<(usize, usize)> = live.iter().copied().collect();
+        starts.sort_by_key(|&(x, y)| (y, x));
+        
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        for start in starts {
+            if visited.contains(&start) {
+                continue;
             }
+            
+            let object = gather_object(&live, start, COMPONENT_REACH, &mut visited);
+            let bounds = (
+                object.iter().map(|&(x, _)| x).min().unwrap_or(0),
+                object.iter().map(|&(_, y)| y).min().unwrap_or(0),
+                object.iter().map(|&(x, _)| x).max().unwrap_or(0),
+                object.iter().map(|&(_, y)| y).max().unwrap_or(0),
+            );
+            
+            let isolated = isolate_object(&object);
+            let mut analyzer = PatternAnalyzer::new(self.max_generations, isolated.dimensions(), BoundaryType::Wrap);
+            analyzer.set_cycle_window(self.cycle_window);
+            let stats = analyzer.analyze_grid("Component", isolated, &mut |_, _| {});
+            components.push(ComponentStats {
+                bounds,
+                population: object.len(),
+                pattern_type: stats.pattern_type,
+            });
         }
         
-        hasher.finish()
+        components
     }
     
     /// Find the center point of a pattern
     fn find_pattern_center(&self, grid: &Grid) -> (usize, usize) {
-        let mut sum_x = 0;
-        let mut sum_y = 0;
-        let mut count = 0;
-        
-        for y in 0..self.grid_size.1 {
-            for x in 0..self.grid_size.0 {
-                if grid.get(x, y) {
-                    sum_x += x;
-                    sum_y += y;
-                    count += 1;
-                }
-            }
+        let cells: Vec<(usize, usize)> = grid.live_cells().collect();
+        if cells.is_empty() {
+            return (self.grid_size.0 / 2, self.grid_size.1 / 2);
         }
         
-        if count == 0 {
-            (self.grid_size.0 / 2, self.grid_size.1 / 2)
-        } else {
-            (sum_x / count, sum_y / count)
+        // On a torus a pattern straddling the seam would average to the middle of the grid,
+        // so measure positions from just past the widest empty band instead of from zero
+        let (width, height) = grid.dimensions();
+        let (origin_x, origin_y) = match self.boundary {
+            BoundaryType::Wrap => (
+                seam_origin(width, cells.iter().map(|&(x, _)| x)),
+                seam_origin(height, cells.iter().map(|&(_, y)| y)),
+            ),
+            BoundaryType::Fixed | BoundaryType::Absorbing => (0, 0),
+        };
+        
+        let sum_x: usize = cells.iter().map(|&(x, _)| (x + width - origin_x) % width).sum();
+        let sum_y: usize = cells.iter().map(|&(_, y)| (y + height - origin_y) % height).sum();
+        let count = cells.len();
+        
+        ((sum_x / count + origin_x) % width, (sum_y / count + origin_y) % height)
+    }
+    
+    /// Signed movement from one coordinate to another along an axis of the given length.
+    /// On a torus this is the shorter way around, so crossing the seam is a small step.
+    fn axis_displacement(&self, from: usize, to: usize, length: usize) -> isize {
+        let delta = to as isize - from as isize;
+        match self.boundary {
+            BoundaryType::Wrap => {
+                let wrapped = delta.rem_euclid(length as isize);
+                if wrapped > length as isize / 2 { wrapped - length as isize } else { wrapped }
+            },
+            BoundaryType::Fixed | BoundaryType::Absorbing => delta,
         }
     }
     
@@ -360,7 +949,7 @@ impl PatternAnalyzer {
                 continue;
             }
             
-            let samples = center_history.len() / period;
+            let samples = (center_history.len() - 1) / period;
             if samples < 2 {
                 continue;
             }
@@ -371,8 +960,8 @@ impl PatternAnalyzer {
                 let pos1 = center_history[i * period];
                 let pos2 = center_history[(i + 1) * period];
                 
-                let dx = pos2.0 as isize - pos1.0 as isize;
-                let dy = pos2.1 as isize - pos1.1 as isize;
+                let dx = self.axis_displacement(pos1.0, pos2.0, self.grid_size.0);
+                let dy = self.axis_displacement(pos1.1, pos2.1, self.grid_size.1);
                 
                 displacements.push((dx, dy));
             }
@@ -439,6 +1028,46 @@ impl PatternAnalyzer {
         formations
     }
     
+    /// Export the comparison as CSV, one row per pattern, for spreadsheets and analysis tools.
+    /// Period, speed and velocity are left empty when they don't apply.
+    pub fn comparison_to_csv(&self, stats: &[PatternStats]) -> String {
+        let mut csv = String::from(
+            "name,initial_population,max_population,final_population,generation_of_max,classification,period,speed,velocity\n"
+        );
+        
+        for stat in stats {
+            let (classification, period, speed, velocity) = match &stat.pattern_type {
+                PatternType::ExtinctPattern { .. } => ("Extinct", None, None, None),
+                PatternType::StablePattern { oscillator_period: None, .. } => ("Still Life", None, None, None),
+                PatternType::StablePattern { oscillator_period: Some(p), .. } => ("Oscillator", Some(*p), None, None),
+                PatternType::ExplodingPattern { .. } => ("Exploding", None, None, None),
+                PatternType::SpaceshipPattern { period, displacement, speed } => (
+                    "Spaceship",
+                    Some(*period),
+                    Some(format!("{:.4}", speed)),
+                    Some(velocity_notation(*displacement, *period)),
+                ),
+                PatternType::PatternEmitter { period, .. } => ("Emitter", Some(*period), None, None),
+                PatternType::Unknown => ("Unknown", None, None, None),
+            };
+            
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                csv_field(&stat.name),
+                stat.initial_population,
+                stat.max_population,
+                stat.final_population,
+                stat.generation_of_max,
+                classification,
+                period.map(|p| p.to_string()).unwrap_or_default(),
+                speed.unwrap_or_default(),
+                csv_field(&velocity.unwrap_or_default()),
+            ));
+        }
+        
+        csv
+    }
+    
     /// Generate a comparison report for multiple patterns
     pub fn generate_comparison_report(&self, stats: &[PatternStats]) -> String {
         if stats.is_empty() {
@@ -461,10 +1090,13 @@ impl PatternAnalyzer {
             let classification = match &stat.pattern_type {
                 PatternType::ExtinctPattern { .. } => "Extinct",
                 PatternType::StablePattern { oscillator_period: None, .. } => "Still Life",
-                PatternType::StablePattern { oscillator_period: Some(p), .. } => 
-                    &format!("Oscillator (p={})", p),
+                PatternType::StablePattern { oscillator_period: Some(p), .. } => match &stat.detected_name {
+                    Some(name) => &format!("{} (p{})", name, p),
+                    None => &format!("Oscillator (p={})", p),
+                },
                 PatternType::ExplodingPattern { .. } => "Exploding",
-                PatternType::SpaceshipPattern { .. } => "Spaceship",
+                PatternType::SpaceshipPattern { period, displacement, .. } => 
+                    &format!("Spaceship ({})", velocity_notation(*displacement, *period)),
                 PatternType::PatternEmitter { .. } => "Emitter",
                 PatternType::Unknown => "Unknown",
             };
@@ -494,6 +1126,25 @@ impl PatternAnalyzer {
             }
         }
         
+        // Rank patterns by average entropy; chaotic patterns stay high for longer
+        let mut entropy_ranking: Vec<_> = stats.iter()
+            .filter_map(|stat| {
+                let (_, peak) = stat.peak_entropy()?;
+                let average = stat.entropy_history.iter().sum::<f64>() / stat.entropy_history.len() as f64;
+                Some((stat.name.as_str(), average, peak))
+            })
+            .collect();
+        entropy_ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+        
+        if !entropy_ranking.is_empty() {
+            report.push_str("\nEntropy Ranking (average / peak bits):\n");
+            report.push_str("-------------------------------------\n");
+            
+            for (name, average, peak) in entropy_ranking {
+                report.push_str(&format!("{:<20}: {:.3} / {:.3}\n", name, average, peak));
+            }
+        }
+        
         report
     }
 }
\ No newline at end of file
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod tests {
+    use crate::analyzer::{spatial_entropy, velocity_notation, PatternAnalyzer, PatternType};
+    use crate::config::BoundaryType;
+    use crate::grid::Grid;
+    use crate::patterns::{Pattern, PatternCategory, PatternLibrary};
+
+    #[test]
+    fn test_oscillator_phase_populations() {
+        let analyzer = PatternAnalyzer::new(100, (30, 30), BoundaryType::Fixed);
+        let stats = analyzer.analyze_pattern(&PatternLibrary::pulsar(), 8, 8);
+        
+        assert!(matches!(stats.pattern_type, PatternType::StablePattern { oscillator_period: Some(3), .. }));
+        
+        let mut phases = stats.oscillator_phase_populations.clone().unwrap();
+        phases.sort();
+        assert_eq!(phases, vec![48, 56, 72]);
+        assert!(stats.generate_report().contains("Phase population min/max/avg: 48/72/58.7"));
+    }
+
+    #[test]
+    fn test_velocity_notation() {
+        assert_eq!(velocity_notation((1, 1), 4), "c/4 diagonal");
+        assert_eq!(velocity_notation((-2, 0), 4), "c/2 orthogonal");
+        assert_eq!(velocity_notation((0, 2), 5), "2c/5 orthogonal");
+        assert_eq!(velocity_notation((2, -1), 6), "(2,1)c/6 oblique");
+        assert_eq!(velocity_notation((3, 3), 3), "c diagonal");
+        assert_eq!(velocity_notation((5, 0), 2), "(5, 0) per 2 generations");
+    }
+
+    #[test]
+    fn test_analyze_composite_combines_patterns() {
+        let analyzer = PatternAnalyzer::new(50, (20, 20), BoundaryType::Fixed);
+        let blinker = PatternLibrary::blinker();
+        
+        // Two blinkers far apart still behave as one period 2 system
+        let stats = analyzer.analyze_composite(&[(&blinker, 2, 2), (&blinker, 12, 12)]);
+        
+        assert_eq!(stats.name, "Blinker + Blinker");
+        assert_eq!(stats.initial_population, 6);
+        assert!(matches!(stats.pattern_type, PatternType::StablePattern { oscillator_period: Some(2), .. }));
+    }
+
+    #[test]
+    fn test_spatial_entropy() {
+        let mut grid = Grid::new(8, 8, BoundaryType::Wrap);
+        assert_eq!(spatial_entropy(&grid, 4), 0.0);
+        
+        // One full block out of four: p = 3/4 and 1/4
+        for y in 0..4 {
+            for x in 0..4 {
+                grid.set(x, y, true);
+            }
+        }
+        assert!((spatial_entropy(&grid, 4) - 0.8113).abs() < 1e-4);
+        
+        // Entropy is tracked for every analyzed generation
+        let analyzer = PatternAnalyzer::new(100, (40, 40), BoundaryType::Wrap);
+        let stats = analyzer.analyze_pattern(&PatternLibrary::r_pentomino(), 18, 18);
+        assert_eq!(stats.entropy_history.len(), stats.population_history.len());
+        
+        let (generation, peak) = stats.peak_entropy().unwrap();
+        assert!(peak > stats.entropy_history[0]);
+        assert!(stats.generate_report().contains(&format!("(generation {})", generation)));
+    }
+
+    #[test]
+    fn test_spaceship_detected_across_wrapped_edge() {
+        let analyzer = PatternAnalyzer::new(200, (20, 20), BoundaryType::Wrap);
+        
+        // The glider moves down and right, crossing both seams within a few generations
+        for offset in 14..=17 {
+            let stats = analyzer.analyze_pattern(&PatternLibrary::glider(), offset, offset);
+            
+            match stats.pattern_type {
+                PatternType::SpaceshipPattern { period, displacement, .. } => {
+                    assert_eq!(velocity_notation(displacement, period), "c/4 diagonal");
+                },
+                other => panic!("Glider at {} classified as {:?}", offset, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_comparison_to_csv() {
+        let analyzer = PatternAnalyzer::new(100, (30, 30), BoundaryType::Wrap);
+        let glider = PatternLibrary::glider();
+        let blinker = PatternLibrary::blinker();
+        let mut stats = analyzer.compare_patterns(&[(&glider, 10, 10), (&blinker, 10, 10)]);
+        stats[1].name = "Blinker, \"odd\" name".to_string();
+        
+        let csv = analyzer.comparison_to_csv(&stats);
+        let lines: Vec<&str> = csv.lines().collect();
+        
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("name,initial_population,"));
+        assert!(lines[1].starts_with("Glider,5,5,5,"));
+        assert!(lines[1].ends_with(",Spaceship,4,0.3536,c/4 diagonal"));
+        assert_eq!(lines[2], "\"Blinker, \"\"odd\"\" name\",3,3,3,0,Oscillator,2,,");
+    }
+    
+    #[test]
+    fn test_density_profile_for_growing_pattern() {
+        let analyzer = PatternAnalyzer::new(200, (80, 60), BoundaryType::Fixed);
+        let stats = analyzer.analyze_pattern(&PatternLibrary::glider_gun(), 2, 2);
+        
+        assert!(matches!(stats.pattern_type, PatternType::PatternEmitter { .. }));
+        let (rows, columns) = stats.density_profile.clone().unwrap();
+        assert_eq!((rows.len(), columns.len()), (60, 80));
+        assert_eq!(rows.iter().sum::<usize>(), stats.final_population);
+        assert_eq!(columns.iter().sum::<usize>(), stats.final_population);
+        assert!(stats.generate_report().contains("Density profile at the final generation"));
+        
+        // Settled patterns carry no profile
+        let pulsar = analyzer.analyze_pattern(&PatternLibrary::pulsar(), 30, 20);
+        assert!(pulsar.density_profile.is_none());
+    }
+    
+    #[test]
+    fn test_glider_gun_detected_as_emitter() {
+        let analyzer = PatternAnalyzer::new(300, (120, 120), BoundaryType::Fixed);
+        let stats = analyzer.analyze_pattern(&PatternLibrary::glider_gun(), 2, 2);
+        
+        let PatternType::PatternEmitter { period, emitted_pattern_type } = stats.pattern_type else {
+            panic!("expected an emitter, got {:?}", stats.pattern_type);
+        };
+        assert_eq!(period, 30);
+        assert!(matches!(
+            *emitted_pattern_type,
+            PatternType::SpaceshipPattern { period: 4, displacement: (1, 1), .. }
+        ), "{:?}", emitted_pattern_type);
+        
+        // A plain glider leaves its starting region empty, so it stays a spaceship
+        let glider = analyzer.analyze_pattern(&PatternLibrary::glider(), 10, 10);
+        assert!(matches!(glider.pattern_type, PatternType::SpaceshipPattern { .. }));
+    }
+    
+    #[test]
+    fn test_progress_callback_sees_every_generation() {
+        let analyzer = PatternAnalyzer::new(500, (60, 60), BoundaryType::Fixed);
+        let mut progress = Vec::new();
+        let stats = analyzer.analyze_pattern_with_progress(&PatternLibrary::r_pentomino(), 28, 28, |generation, population| {
+            progress.push((generation, population));
+        });
+        
+        // One call per generation stepped, with the same populations the stats record
+        assert_eq!(progress.len(), stats.population_history.len() - 1);
+        for (index, &(generation, population)) in progress.iter().enumerate() {
+            assert_eq!(generation, index + 1);
+            assert_eq!(population, stats.population_history[generation]);
+        }
+    }
+    
+    #[test]
+    fn test_analyze_directory_skips_bad_files() {
+        let dir = std::env::temp_dir().join(format!("conway_analyze_dir_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a_glider.rle"), "#N Glider\nx = 3, y = 3\nbo$2bo$3o!\n").unwrap();
+        std::fs::write(dir.join("b_blinker.cells"), "!A bare blinker\nOOO\n").unwrap();
+        std::fs::write(dir.join("c_broken.rle"), "x = 3, y = 3\n3o\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a pattern").unwrap();
+        
+        let analyzer = PatternAnalyzer::new(50, (20, 20), BoundaryType::Wrap);
+        let analysis = analyzer.analyze_directory(&dir).unwrap();
+        
+        let names: Vec<&str> = analysis.stats.iter().map(|stats| stats.name.as_str()).collect();
+        assert_eq!(names, vec!["Glider", "b_blinker"]);
+        assert!(matches!(analysis.stats[1].pattern_type, PatternType::StablePattern { oscillator_period: Some(2), .. }));
+        assert_eq!(analysis.errors.len(), 1);
+        assert!(analysis.errors[0].0.ends_with("c_broken.rle"));
+        
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(analyzer.analyze_directory(&dir).is_err());
+    }
+    
+    #[test]
+    fn test_components_report_their_own_periods() {
+        let analyzer = PatternAnalyzer::new(100, (40, 30), BoundaryType::Fixed);
+        let mut grid = Grid::new(40, 30, BoundaryType::Fixed);
+        for (x, y) in [(2, 2), (3, 2), (2, 3), (3, 3)] {
+            grid.set(x, y, true);
+        }
+        PatternLibrary::pulsar().place(&mut grid, 20, 10);
+        
+        let components = analyzer.analyze_components(&grid);
+        
+        // The pulsar's four quadrants form one component rather than four
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].bounds, (2, 2, 3, 3));
+        assert_eq!(components[0].population, 4);
+        assert_eq!(components[0].period(), Some(1));
+        assert_eq!(components[1].population, 48);
+        assert_eq!(components[1].period(), Some(3));
+    }
+    
+    #[test]
+    fn test_known_oscillators_are_named() {
+        let analyzer = PatternAnalyzer::new(100, (40, 40), BoundaryType::Fixed);
+        for pattern in [
+            PatternLibrary::blinker(),
+            PatternLibrary::toad(),
+            PatternLibrary::beacon(),
+            PatternLibrary::pulsar(),
+            PatternLibrary::pentadecathlon(),
+        ] {
+            let (x, y) = pattern.centered_position(40, 40);
+            let stats = analyzer.analyze_pattern(&pattern, x, y);
+            assert_eq!(stats.detected_name.as_deref(), Some(pattern.name));
+        }
+        
+        let pulsar = analyzer.analyze_pattern(&PatternLibrary::pulsar(), 12, 12);
+        assert!(analyzer.generate_comparison_report(&[pulsar]).contains("Pulsar (p3)"));
+        
+        // Two blinkers together are no single known oscillator
+        let blinker = PatternLibrary::blinker();
+        let stats = analyzer.analyze_composite(&[(&blinker, 5, 5), (&blinker, 25, 25)]);
+        assert_eq!(stats.detected_name, None);
+    }
+    
+    #[test]
+    fn test_cycle_window_limits_detected_periods() {
+        let pentadecathlon = PatternLibrary::pentadecathlon();
+        let mut analyzer = PatternAnalyzer::new(60, (30, 30), BoundaryType::Fixed);
+        assert_eq!(analyzer.cycle_window(), None);
+        
+        // A window as long as the period still catches the cycle
+        analyzer.set_cycle_window(Some(15));
+        let (x, y) = pentadecathlon.centered_position(30, 30);
+        let stats = analyzer.analyze_pattern(&pentadecathlon, x, y);
+        assert!(matches!(stats.pattern_type, PatternType::StablePattern { oscillator_period: Some(15), .. }));
+        
+        // A shorter window forgets the earlier phase before it comes back around
+        analyzer.set_cycle_window(Some(14));
+        let stats = analyzer.analyze_pattern(&pentadecathlon, x, y);
+        assert!(!matches!(stats.pattern_type, PatternType::StablePattern { .. }));
+        
+        let blinker = PatternLibrary::blinker();
+        let stats = analyzer.analyze_pattern(&blinker, 10, 10);
+        assert!(matches!(stats.pattern_type, PatternType::StablePattern { oscillator_period: Some(2), .. }));
+    }
+    
+    #[test]
+    fn test_still_life_found_by_comparing_generations() {
+        // An L tromino fills in to a block after one generation
+        let tromino = Pattern {
+            name: "Tromino",
+            description: "Three cells of a block",
+            category: PatternCategory::StillLife,
+            width: 2,
+            height: 2,
+            cells: vec![(0, 0), (1, 0), (0, 1)],
+            author: None,
+            comments: Vec::new(),
+        };
+        
+        // Still lifes only need the previous generation's hash, so even the smallest window finds them
+        let mut analyzer = PatternAnalyzer::new(20, (10, 10), BoundaryType::Fixed);
+        analyzer.set_cycle_window(Some(1));
+        let stats = analyzer.analyze_pattern(&tromino, 4, 4);
+        assert_eq!(stats.pattern_type, PatternType::StablePattern {
+            generations_to_stabilize: 1,
+            oscillator_period: None,
+            final_population: 4,
+        });
+        assert_eq!(stats.generations_analyzed, 2);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+// Conway's Game of Life Automata
+// Pluggable update functions for cellular automata beyond two-state life-like rules
+
+use crate::rule::Rule;
+
+/// A cellular automaton on the Moore neighborhood. A grid holding one is updated by asking it
+/// for the next state of every cell instead of applying the grid's birth/survival rule.
+///
+/// State 0 is dead; every other state counts as alive for population, rendering and the
+/// other two-state views of the grid.
+pub trait Automaton: Send + Sync {
+    /// Number of states a cell can be in, including the dead state 0
+    fn states(&self) -> u8 {
+        2
+    }
+
+    /// The next state of a cell, given its current state and its neighbors' states in the
+    /// order northwest, north, northeast, west, east, southwest, south, southeast.
+    /// Neighbors past a fixed edge are in state 0.
+    fn next_state(&self, current: u8, neighbors: &[u8; 8]) -> u8;
+}
+
+/// A life-like rule run through the generic automaton path: any live neighbor counts,
+/// whatever its state
+impl Automaton for Rule {
+    fn next_state(&self, current: u8, neighbors: &[u8; 8]) -> u8 {
+        let count = neighbors.iter().filter(|&&state| state != 0).count() as u8;
+        Rule::next_state(self, current != 0, count) as u8
+    }
+}
+
+/// Brian's Brain: a firing cell always starts dying, a dying cell always turns off,
+/// and an off cell fires when exactly two of its neighbors are firing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BriansBrain;
+
+impl BriansBrain {
+    pub const OFF: u8 = 0;
+    pub const FIRING: u8 = 1;
+    pub const DYING: u8 = 2;
+}
+
+impl Automaton for BriansBrain {
+    fn states(&self) -> u8 {
+        3
+    }
+
+    fn next_state(&self, current: u8, neighbors: &[u8; 8]) -> u8 {
+        match current {
+            Self::FIRING => Self::DYING,
+            Self::DYING => Self::OFF,
+            _ => {
+                let firing = neighbors.iter().filter(|&&state| state == Self::FIRING).count();
+                if firing == 2 { Self::FIRING } else { Self::OFF }
+            },
+        }
+    }
+}
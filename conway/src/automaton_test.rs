@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    
+    use crate::automaton::{Automaton, BriansBrain};
+    use crate::config::BoundaryType;
+    use crate::grid::Grid;
+    use crate::rule::Rule;
+
+    #[test]
+    fn test_rule_as_automaton_matches_rule_update() {
+        for boundary in [BoundaryType::Wrap, BoundaryType::Fixed] {
+            let highlife = Rule::from_counts(&[3, 6], &[2, 3]);
+            let mut expected = Grid::new(70, 30, boundary);
+            expected.set_rule(highlife);
+            expected.randomize_seeded(0.35, 7);
+            
+            let mut generic = expected.clone();
+            generic.set_automaton(Some(Arc::new(highlife)));
+            
+            for generation in 0..40 {
+                expected.update();
+                generic.update();
+                assert_eq!(generic.to_coordinate_list(), expected.to_coordinate_list(), "generation {}", generation);
+            }
+        }
+    }
+
+    #[test]
+    fn test_brians_brain_uses_three_states() {
+        let mut grid = Grid::new(10, 10, BoundaryType::Fixed);
+        grid.set_automaton(Some(Arc::new(BriansBrain)));
+        grid.set_state(4, 4, BriansBrain::FIRING);
+        grid.set_state(5, 4, BriansBrain::FIRING);
+        let before = grid.hash_state();
+        
+        grid.update();
+        
+        // The pair starts dying and the four cells touching both of them fire
+        assert_eq!(grid.get_state(4, 4), BriansBrain::DYING);
+        assert_eq!(grid.get_state(5, 4), BriansBrain::DYING);
+        for (x, y) in [(4, 3), (5, 3), (4, 5), (5, 5)] {
+            assert_eq!(grid.get_state(x, y), BriansBrain::FIRING);
+        }
+        assert_eq!(grid.count_alive(), 6);
+        assert_ne!(grid.hash_state(), before);
+        
+        // Dying cells turn off rather than firing again
+        grid.update();
+        assert_eq!(grid.get_state(4, 4), BriansBrain::OFF);
+        assert_eq!(grid.get_state(4, 3), BriansBrain::DYING);
+    }
+
+    #[test]
+    fn test_state_planes_hold_every_state() {
+        struct Counter;
+        impl Automaton for Counter {
+            fn states(&self) -> u8 {
+                5
+            }
+            
+            fn next_state(&self, current: u8, _neighbors: &[u8; 8]) -> u8 {
+                if current == 0 { 0 } else { current % 4 + 1 }
+            }
+        }
+        
+        let mut grid = Grid::new(70, 3, BoundaryType::Wrap);
+        grid.set_automaton(Some(Arc::new(Counter)));
+        for state in 0..5 {
+            grid.set_state(65 + state as usize, 1, state);
+        }
+        
+        grid.update();
+        let states: Vec<u8> = (65..70).map(|x| grid.get_state(x, 1)).collect();
+        assert_eq!(states, vec![0, 2, 3, 4, 1]);
+        
+        // Setting a cell alive puts it back in state 1
+        grid.set(67, 1, true);
+        assert_eq!(grid.get_state(67, 1), 1);
+    }
+}
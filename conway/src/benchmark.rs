@@ -1,6 +1,7 @@
 // Conway's Game of Life Benchmark Tool
 // A utility for testing the performance of Conway's Game of Life
 
+use std::fmt;
 use std::time::{Duration, Instant};
 use rand::Rng;
 
@@ -16,9 +17,10 @@ pub struct BenchmarkResult {
     pub cell_updates_per_second: f64,
 }
 
-impl BenchmarkResult {
-    pub fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for BenchmarkResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
             "Grid Size: {}x{}, Boundary: {}, Generations: {}, Time: {:.2?}, Cell Updates/s: {:.2} billion",
             self.grid_size.0,
             self.grid_size.1,
@@ -30,6 +32,75 @@ impl BenchmarkResult {
     }
 }
 
+/// Summary of repeated runs of the same benchmark, in cell updates per second
+pub struct BenchmarkStats {
+    pub grid_size: (usize, usize),
+    pub generations: usize,
+    pub boundary_type: &'static str,
+    pub runs: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl BenchmarkStats {
+    /// Summarize the runs of one benchmark. Returns None when there are no runs.
+    pub fn from_results(results: &[BenchmarkResult]) -> Option<Self> {
+        let first = results.first()?;
+        
+        let mut rates: Vec<f64> = results.iter().map(|result| result.cell_updates_per_second).collect();
+        rates.sort_by(f64::total_cmp);
+        
+        let count = rates.len();
+        let median = if count.is_multiple_of(2) {
+            (rates[count / 2 - 1] + rates[count / 2]) / 2.0
+        } else {
+            rates[count / 2]
+        };
+        let mean = rates.iter().sum::<f64>() / count as f64;
+        
+        // Sample standard deviation, since the runs are a sample of possible timings
+        let variance = if count > 1 {
+            rates.iter().map(|rate| (rate - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+        
+        Some(Self {
+            grid_size: first.grid_size,
+            generations: first.generations,
+            boundary_type: first.boundary_type,
+            runs: count,
+            min: rates[0],
+            median,
+            mean,
+            std_dev: variance.sqrt(),
+        })
+    }
+}
+
+impl fmt::Display for BenchmarkStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let billions = |rate: f64| rate / 1_000_000_000.0;
+        write!(
+            f,
+            "Grid Size: {}x{}, Boundary: {}, Generations: {}, Runs: {}, Cell Updates/s (billion): \
+             min {:.2}, median {:.2}, mean {:.2}, std dev {:.2} ({:.1}%)",
+            self.grid_size.0,
+            self.grid_size.1,
+            self.boundary_type,
+            self.generations,
+            self.runs,
+            billions(self.min),
+            billions(self.median),
+            billions(self.mean),
+            billions(self.std_dev),
+            100.0 * self.std_dev / self.mean,
+        )
+    }
+}
+
 /// Run a benchmark for a given grid size, generations, and boundary type
 pub fn run_benchmark(
     width: usize,
@@ -47,8 +118,7 @@ pub fn run_benchmark(
         Some(name) => {
             if let Some(pattern) = PatternLibrary::get_by_name(name) {
                 // Place pattern in the center
-                let x = width / 2 - pattern.width / 2;
-                let y = height / 2 - pattern.height / 2;
+                let (x, y) = pattern.centered_position(width, height);
                 pattern.place(&mut grid, x, y);
             } else {
                 // Invalid pattern, use random
@@ -74,20 +144,35 @@ pub fn run_benchmark(
     let total_cells = width * height * generations;
     let cell_updates_per_second = total_cells as f64 / elapsed.as_secs_f64();
     
-    let boundary_str = match boundary {
-        BoundaryType::Wrap => "Wrapped",
-        BoundaryType::Fixed => "Fixed",
-    };
-    
     BenchmarkResult {
         grid_size: (width, height),
         generations,
-        boundary_type: boundary_str,
+        boundary_type: boundary.as_str(),
         elapsed_time: elapsed,
         cell_updates_per_second,
     }
 }
 
+/// Run a benchmark repeatedly for stable numbers. One warmup run is discarded first,
+/// then `runs` timed runs (at least one) are summarized.
+pub fn run_benchmark_stats(
+    width: usize,
+    height: usize,
+    generations: usize,
+    boundary: BoundaryType,
+    pattern_name: Option<&str>,
+    density: f64,
+    runs: usize,
+) -> BenchmarkStats {
+    run_benchmark(width, height, generations, boundary.clone(), pattern_name, density);
+    
+    let results: Vec<BenchmarkResult> = (0..runs.max(1))
+        .map(|_| run_benchmark(width, height, generations, boundary.clone(), pattern_name, density))
+        .collect();
+    
+    BenchmarkStats::from_results(&results).expect("at least one benchmark run")
+}
+
 /// Run benchmarks for various grid sizes
 pub fn run_size_benchmarks(max_size: usize, generations: usize) -> Vec<BenchmarkResult> {
     let sizes = [
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    
+    use crate::benchmark::{run_benchmark_stats, BenchmarkResult, BenchmarkStats};
+    use crate::config::BoundaryType;
+
+    fn result(cell_updates_per_second: f64) -> BenchmarkResult {
+        BenchmarkResult {
+            grid_size: (100, 100),
+            generations: 10,
+            boundary_type: "wrap",
+            elapsed_time: Duration::from_millis(1),
+            cell_updates_per_second,
+        }
+    }
+
+    #[test]
+    fn test_stats_from_results() {
+        let results: Vec<BenchmarkResult> = [4.0, 1.0, 3.0, 2.0].iter().map(|&rate| result(rate * 1e9)).collect();
+        let stats = BenchmarkStats::from_results(&results).unwrap();
+        
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, 1e9);
+        assert_eq!(stats.median, 2.5e9);
+        assert_eq!(stats.mean, 2.5e9);
+        assert!((stats.std_dev - 1.2909944e9).abs() < 1e3);
+        assert!(stats.to_string().contains("min 1.00, median 2.50, mean 2.50, std dev 1.29 (51.6%)"));
+        
+        assert!(BenchmarkStats::from_results(&[]).is_none());
+    }
+
+    #[test]
+    fn test_run_benchmark_stats_counts_runs() {
+        let stats = run_benchmark_stats(64, 16, 2, BoundaryType::Fixed, Some("glider"), 0.3, 3);
+        assert_eq!((stats.runs, stats.grid_size, stats.boundary_type), (3, (64, 16), "fixed"));
+        assert!(stats.min <= stats.median && stats.min <= stats.mean);
+    }
+}
@@ -0,0 +1,111 @@
+// Conway's Game of Life Terminal Recording
+// Tees the renderer's output into an asciinema v2 cast file
+
+use std::io::{self, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Writer that passes everything through to the terminal and also records it, with the
+/// time it was flushed, as output events of an asciinema v2 cast.
+///
+/// Writes are gathered until the next flush and saved as a single event, so each frame
+/// the renderer draws becomes one line of the cast. A failure to write the cast stops the
+/// recording without disturbing the terminal; it is reported by `finish`.
+pub struct CastWriter<W: Write, C: Write> {
+    inner: W,
+    cast: Option<C>,
+    pending: Vec<u8>,
+    start: Instant,
+    error: Option<io::Error>,
+}
+
+impl<W: Write, C: Write> CastWriter<W, C> {
+    /// Start a recording of a terminal with the given size in columns and rows,
+    /// writing the cast header straight away
+    pub fn new(inner: W, mut cast: C, width: u16, height: u16) -> io::Result<Self> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        writeln!(cast, "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}}}", width, height, timestamp)?;
+
+        Ok(Self {
+            inner,
+            cast: Some(cast),
+            pending: Vec::new(),
+            start: Instant::now(),
+            error: None,
+        })
+    }
+
+    /// Save any output not yet flushed and close the cast, returning the first error
+    /// the recording ran into
+    pub fn finish(mut self) -> io::Result<()> {
+        self.record_pending();
+        if let Some(mut cast) = self.cast.take() {
+            if let Err(e) = cast.flush() {
+                self.error.get_or_insert(e);
+            }
+        }
+        self.error.map_or(Ok(()), Err)
+    }
+
+    // Write the pending bytes as one output event, holding back a character cut off at the
+    // end so it is never split across two events
+    fn record_pending(&mut self) {
+        let Some(cast) = &mut self.cast else {
+            self.pending.clear();
+            return;
+        };
+
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if complete == 0 {
+            return;
+        }
+
+        let data = String::from_utf8_lossy(&self.pending[..complete]).into_owned();
+        let time = self.start.elapsed().as_secs_f64();
+        let result = writeln!(cast, "[{:.6}, \"o\", \"{}\"]", time, json_escape(&data));
+        self.pending.drain(..complete);
+
+        if let Err(e) = result {
+            self.error = Some(e);
+            self.cast = None;
+        }
+    }
+}
+
+impl<W: Write, C: Write> Write for CastWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if self.cast.is_some() {
+            self.pending.extend_from_slice(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.record_pending();
+        self.inner.flush()
+    }
+}
+
+/// Escape text for use inside a JSON string
+pub fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::cast::{json_escape, CastWriter};
+
+    #[test]
+    fn test_json_escape() {
+        assert_eq!(json_escape("plain █"), "plain █");
+        assert_eq!(json_escape("\"quoted\"\\"), "\\\"quoted\\\"\\\\");
+        assert_eq!(json_escape("\x1b[2J\r\n"), "\\u001b[2J\\r\\n");
+    }
+
+    #[test]
+    fn test_cast_records_one_event_per_flush() {
+        let mut terminal = Vec::new();
+        let mut cast = Vec::new();
+        {
+            let mut writer = CastWriter::new(&mut terminal, &mut cast, 80, 24).unwrap();
+            write!(writer, "\x1b[H").unwrap();
+            write!(writer, "Gen: 1").unwrap();
+            writer.flush().unwrap();
+
+            // A character split across writes waits for its remaining bytes
+            let block = "█".as_bytes();
+            writer.write_all(&block[..1]).unwrap();
+            writer.flush().unwrap();
+            writer.write_all(&block[1..]).unwrap();
+            writer.flush().unwrap();
+
+            // Nothing written since the last flush makes no event
+            writer.flush().unwrap();
+            writer.finish().unwrap();
+        }
+
+        assert_eq!(String::from_utf8(terminal).unwrap(), "\x1b[HGen: 1█");
+
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 80, \"height\": 24, \"timestamp\": "));
+        assert!(lines[1].starts_with('[') && lines[1].ends_with(", \"o\", \"\\u001b[HGen: 1\"]"));
+        assert!(lines[2].ends_with(", \"o\", \"█\"]"));
+    }
+}
@@ -1,30 +1,39 @@
 use clap::Parser;
+use std::fmt;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
 pub struct Config {
     /// Width of the grid
-    #[arg(short = 'w', long, default_value_t = 100)]
+    #[arg(short = 'w', long, default_value_t = 100, value_parser = grid_dimension)]
     pub width: usize,
 
     /// Height of the grid
-    #[arg(short = 'H', long, default_value_t = 50)]
+    #[arg(short = 'H', long, default_value_t = 50, value_parser = grid_dimension)]
     pub height: usize,
+    
+    /// Size the grid to fill the terminal, overriding --width and --height
+    #[arg(long)]
+    pub auto_size: bool,
 
     /// Maximum frames per second
     #[arg(long, default_value_t = 60)]
     pub max_fps: u64,
 
-    /// Initial density for random initialization (0.0-1.0)
+    /// Share of cells brought to life when randomizing with r and R (0.0-1.0)
     #[arg(short, long, default_value_t = 0.3)]
     pub density: f64,
+    
+    /// Fill the grid at random at --density when the game starts
+    #[arg(long, conflicts_with_all = ["initial_pattern", "image", "generate_from_seed"])]
+    pub randomize_on_start: bool,
 
     /// Cell theme to use (classic, block, dot)
     #[arg(short, long, default_value = "block")]
     pub theme: String,
 
-    /// Color theme to use (green, blue, rainbow)
+    /// Color theme to use (green, blue, rainbow, spectrum, viridis)
     #[arg(short = 'c', long, default_value = "green")]
     pub color_theme: String,
 
@@ -32,9 +41,26 @@ pub struct Config {
     #[arg(short, long)]
     pub file: Option<PathBuf>,
 
-    /// Boundary condition type (wrap, fixed)
-    #[arg(short = 'b', long, default_value = "wrap")]
-    pub boundary: String,
+    /// Boundary condition type (wrap, fixed, absorbing)
+    #[arg(short = 'b', long, default_value = "wrap", value_parser = boundary_type)]
+    pub boundary: BoundaryType,
+    
+    /// Birth/survival rule in B/S notation, e.g. B36/S23 for HighLife, or a Larger than Life
+    /// range rule such as R5,C0,M0,S33..57,B34..45
+    #[arg(long, default_value = "B3/S23")]
+    pub rule: String,
+    
+    /// Print the rule's next-state table for every neighbor count and exit
+    #[arg(long)]
+    pub print_rule: bool,
+    
+    /// Seed the grid from an image; dark pixels become live cells
+    #[arg(long)]
+    pub image: Option<PathBuf>,
+    
+    /// Luminance (0-255) below which an image pixel becomes a live cell
+    #[arg(long, value_name = "N", default_value_t = 128)]
+    pub image_threshold: u8,
     
     /// Initial pattern to place on the grid (glider, blinker, toad, beacon, etc.)
     #[arg(short = 'p', long)]
@@ -43,6 +69,116 @@ pub struct Config {
     /// Generate an interesting pattern based on a complexity value in a seed file
     #[arg(long)]
     pub generate_from_seed: Option<PathBuf>,
+    
+    /// Start the interactive tutorial instead of the game
+    #[arg(long)]
+    pub tutorial: bool,
+    
+    /// Start a custom tutorial loaded from a TOML file
+    #[arg(long)]
+    pub tutorial_file: Option<PathBuf>,
+    
+    /// Terminal characters per cell; 2 compensates for tall characters so cells look square
+    #[arg(long, value_name = "CHARS", default_value_t = 1)]
+    pub cell_aspect: usize,
+    
+    /// Color cells born this generation green and surviving cells white
+    #[arg(long)]
+    pub highlight_changes: bool,
+    
+    /// Color live cells that will die in the next generation red
+    #[arg(long)]
+    pub mark_dying: bool,
+    
+    /// Draw dead cells as a faint dot instead of a blank, so the grid stays visible
+    #[arg(long)]
+    pub dead_marks: bool,
+    
+    /// Cursor style (block, outline, underline); outline and underline keep the cell visible
+    #[arg(long, default_value = "block")]
+    pub cursor_style: String,
+    
+    /// Keep a rewind history of this many snapshots (step with < and > while paused)
+    #[arg(long, value_name = "DEPTH")]
+    pub history: Option<usize>,
+    
+    /// Take a history snapshot every N generations
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub history_interval: usize,
+    
+    /// Pause after this many generations
+    #[arg(long, value_name = "N")]
+    pub max_generations: Option<usize>,
+    
+    /// Exit instead of pausing when --max-generations is reached
+    #[arg(long)]
+    pub exit_on_limit: bool,
+    
+    /// Generations to advance per drawn frame, for fast-forwarding; by default just enough
+    /// to keep up with the speed at --max-fps
+    #[arg(long, value_name = "N")]
+    pub steps_per_frame: Option<usize>,
+    
+    /// Seed for the random number generator, making random boards reproducible
+    #[arg(long)]
+    pub seed: Option<u64>,
+    
+    /// Record the session (edits, speed changes, generations) to a replay file
+    #[arg(long)]
+    pub record: Option<PathBuf>,
+    
+    /// Play back a session previously saved with --record
+    #[arg(long)]
+    pub replay: Option<PathBuf>,
+    
+    /// Record the terminal output to an asciinema v2 cast file, for embedding on a webpage
+    #[arg(long, value_name = "PATH")]
+    pub record_cast: Option<PathBuf>,
+    
+    /// Save a PNG of the whole grid to this path when `p` is pressed
+    #[arg(long, value_name = "PATH")]
+    pub screenshot: Option<PathBuf>,
+    
+    /// Resume the session saved in this file, if there is one, and save the session to it on
+    /// exit: the board, rule, generation, speed and view. A resumed session brings its own
+    /// grid, so the grid size, pattern, image and rule options only apply to a new one.
+    #[arg(long, value_name = "PATH", conflicts_with = "file")]
+    pub session: Option<PathBuf>,
+    
+    /// Render a library pattern's evolution to the GIF given by --gif and exit, without a terminal.
+    /// Uses the --width, --height and --boundary grid settings.
+    #[arg(long, value_name = "PATTERN", requires = "gif")]
+    pub visualize: Option<String>,
+    
+    /// Output path for --visualize
+    #[arg(long, value_name = "PATH", requires = "visualize")]
+    pub gif: Option<PathBuf>,
+    
+    /// Generations drawn into the GIF
+    #[arg(long, value_name = "N", default_value_t = 100)]
+    pub gif_generations: usize,
+    
+    /// Size of each cell in the GIF, in pixels
+    #[arg(long, value_name = "PIXELS", default_value_t = 10)]
+    pub cell_size: u32,
+    
+    /// Color theme of the GIF (classic, matrix, ocean, inferno, rainbow)
+    #[arg(long, default_value = "matrix")]
+    pub visual_theme: String,
+}
+
+// Parse a grid width or height, which must leave at least one cell
+fn grid_dimension(text: &str) -> Result<usize, String> {
+    match text.parse::<usize>() {
+        Ok(0) => Err("the grid needs at least one cell in each direction".to_string()),
+        Ok(size) => Ok(size),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// Parse a boundary type, rejecting names that are not one
+fn boundary_type(text: &str) -> Result<BoundaryType, String> {
+    BoundaryType::parse(text).ok_or_else(|| format!("unknown boundary '{}' (expected wrap, fixed or absorbing)", text))
 }
 
 // Different cell appearance themes
@@ -76,6 +212,43 @@ impl CellTheme {
             CellTheme::Dot => " ",
         }
     }
+
+    // Faint mark drawn for dead cells when asked for, so the grid stays visible even when
+    // sparse. None of them is one of the shades used for zoomed-out blocks.
+    pub fn dead_mark(&self) -> &str {
+        match self {
+            CellTheme::Classic => ".",
+            CellTheme::Block => "·",
+            CellTheme::Dot => "·",
+        }
+    }
+}
+
+// How the cell under the cursor is marked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorStyle {
+    Block,      // Grey background over the whole cell
+    Outline,    // Thin bars in the characters either side, leaving the cell as drawn
+    Underline,  // The cell drawn as usual, underlined
+}
+
+impl CursorStyle {
+    pub fn from_string(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "outline" => CursorStyle::Outline,
+            "underline" => CursorStyle::Underline,
+            _ => CursorStyle::Block,
+        }
+    }
+    
+    // The style after this one, for cycling through them
+    pub fn next(self) -> Self {
+        match self {
+            CursorStyle::Block => CursorStyle::Outline,
+            CursorStyle::Outline => CursorStyle::Underline,
+            CursorStyle::Underline => CursorStyle::Block,
+        }
+    }
 }
 
 // Different color themes
@@ -83,6 +256,8 @@ pub enum ColorTheme {
     Green,
     Blue,
     Rainbow,
+    Spectrum,  // Smooth truecolor hue gradient
+    Viridis,   // Perceptually uniform, colorblind-safe gradient
 }
 
 impl ColorTheme {
@@ -90,23 +265,55 @@ impl ColorTheme {
         match s.to_lowercase().as_str() {
             "blue" => ColorTheme::Blue,
             "rainbow" => ColorTheme::Rainbow,
+            "spectrum" => ColorTheme::Spectrum,
+            "viridis" => ColorTheme::Viridis,
             _ => ColorTheme::Green,
         }
     }
+    
+    // The theme after this one, for cycling through them
+    pub fn next(&self) -> Self {
+        match self {
+            ColorTheme::Green => ColorTheme::Blue,
+            ColorTheme::Blue => ColorTheme::Rainbow,
+            ColorTheme::Rainbow => ColorTheme::Spectrum,
+            ColorTheme::Spectrum => ColorTheme::Viridis,
+            ColorTheme::Viridis => ColorTheme::Green,
+        }
+    }
 }
 
 // Boundary condition types
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum BoundaryType {
     Wrap,
     Fixed,
+    Absorbing,  // Like Fixed, but cells on the outermost ring are killed every generation
 }
 
 impl BoundaryType {
-    pub fn from_string(s: &str) -> Self {
+    // The boundary type with the given name, in any case, or None if there is no such type
+    pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
-            "fixed" => BoundaryType::Fixed,
-            _ => BoundaryType::Wrap,
+            "wrap" => Some(BoundaryType::Wrap),
+            "fixed" => Some(BoundaryType::Fixed),
+            "absorbing" => Some(BoundaryType::Absorbing),
+            _ => None,
         }
     }
+
+    // Name of the boundary type, as accepted by parse
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoundaryType::Wrap => "wrap",
+            BoundaryType::Fixed => "fixed",
+            BoundaryType::Absorbing => "absorbing",
+        }
+    }
+}
+
+impl fmt::Display for BoundaryType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
\ No newline at end of file
@@ -1,6 +1,11 @@
-use std::path::PathBuf;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
-use std::io;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 use crossterm::{
     event::{self, Event, KeyCode, KeyModifiers, KeyEvent},
@@ -8,16 +13,78 @@ use crossterm::{
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 
-use crate::grid::Grid;
-use crate::renderer::Renderer;
-use crate::config::{CellTheme, ColorTheme, BoundaryType};
-use crate::patterns::Pattern;
+use crate::grid::{Grid, Symmetry};
+use crate::renderer::{self, Renderer, StatusInfo, ViewState};
+use crate::config::{CellTheme, ColorTheme, BoundaryType, CursorStyle};
+use crate::history::GridHistory;
+use crate::patterns::{Pattern, PatternLibrary};
+use crate::rle;
+use crate::rule::{self, RangeRule, Rule};
+use crate::replay::{Replay, ReplayEvent, ReplayPlayer, ReplayRecorder};
+use crate::cast::CastWriter;
+use crate::session::SessionState;
+
+// How long a paused game waits for input before checking again
+const PAUSED_POLL_TIMEOUT: Duration = Duration::from_millis(250);
+
+// Unchanged generations after which an armed auto-pause stops the simulation
+const AUTO_PAUSE_STABLE_GENERATIONS: usize = 10;
+
+// Generations of population kept for the status bar sparkline
+const POPULATION_TREND_LENGTH: usize = 60;
+
+// Most candidates listed under the pattern search prompt
+const SEARCH_RESULTS_SHOWN: usize = 8;
+
+// Largest preview, in characters, drawn for the highlighted search candidate
+const SEARCH_PREVIEW_SIZE: (usize, usize) = (24, 12);
+
+// How deep macros may play other macros, which stops a macro that plays itself
+const MACRO_NESTING_LIMIT: usize = 8;
+
+// Share of cells brought to life by randomizing, until changed with --density or { and }
+const DEFAULT_RANDOM_DENSITY: f64 = 0.3;
+
+// Change in random density for each press of { or }
+const RANDOM_DENSITY_STEP: f64 = 0.05;
+
+// Largest brush, in cells per side
+const MAX_BRUSH_SIZE: usize = 9;
+
+// Pixels per cell, and live and dead colors, of the screenshots taken with `p`
+const SCREENSHOT_CELL_SIZE: u32 = 4;
+const SCREENSHOT_ALIVE_COLOR: [u8; 3] = [255, 255, 255];
+const SCREENSHOT_DEAD_COLOR: [u8; 3] = [0, 0, 0];
 
 pub enum GameState {
     Running,
     Paused,
 }
 
+/// What the main loop should do after handling input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameAction {
+    /// Leave the game
+    Quit,
+    /// The screen may have changed and should be drawn again
+    Redraw,
+    /// Nothing changed
+    None,
+}
+
+/// What the next key names the register for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MacroCommand {
+    Record,
+    Play,
+}
+
+/// The `/` pattern search prompt: what has been typed and which candidate is highlighted
+struct PatternSearch {
+    query: String,
+    selected: usize,
+}
+
 pub struct Game {
     grid: Grid,
     state: GameState,
@@ -25,6 +92,41 @@ pub struct Game {
     generation: usize,
     max_fps: u64,
     save_path: Option<PathBuf>,
+    record_path: Option<PathBuf>,
+    recorder: Option<ReplayRecorder>,
+    cast_path: Option<PathBuf>,
+    screenshot_path: Option<PathBuf>,
+    session_path: Option<PathBuf>,
+    view: Option<ViewState>,       // View restored from a session, applied to the renderer when playing starts
+    player: Option<ReplayPlayer>,
+    rng: StdRng,
+    history: Option<GridHistory>,
+    population_delta: isize,
+    stable_generations: usize,
+    cell_aspect: usize,
+    highlight_changes: bool,
+    mark_dying: bool,
+    dead_marks: bool,
+    cursor_style: CursorStyle,
+    brush_size: usize,             // Cells per side of the square a toggle edits
+    random_density: f64,           // Share of cells r and R bring to life
+    search: Option<PatternSearch>,
+    placement: Option<Pattern>,    // Pattern previewed at the cursor until placed or cancelled
+    macros: HashMap<char, Vec<KeyEvent>>,            // Recorded key sequences by register
+    macro_recording: Option<(char, Vec<KeyEvent>)>,  // Register and keys of the macro being recorded
+    macro_command: Option<MacroCommand>,             // Q or @ pressed, waiting for the register
+    macro_depth: usize,                              // Macros currently playing inside one another
+    save_prompt: Option<String>,   // Name being typed for the pattern to save, while prompting
+    auto_pause: bool,
+    auto_pause_reason: Option<&'static str>,
+    notice: Option<String>,        // Outcome of the last screenshot or pattern save, shown in the status bar until the next key
+    population_trend: VecDeque<usize>,   // Population of the last few generations, oldest first
+    generation_limit: Option<usize>,   // Generation at which update pauses the game, until reached
+    exit_on_limit: bool,               // Leave the game instead of pausing at the limit
+    limit_reached: bool,
+    garden_of_eden: bool,              // The loaded board failed the orphan screen, until it changes
+    steps_per_frame: Option<usize>,    // Generations per update; None derives it from speed and frame rate
+    last_update: Instant,
 }
 
 impl Game {
@@ -44,9 +146,192 @@ impl Game {
             generation: 0,
             max_fps,
             save_path,
+            record_path: None,
+            recorder: None,
+            cast_path: None,
+            screenshot_path: None,
+            session_path: None,
+            view: None,
+            player: None,
+            rng: StdRng::from_entropy(),
+            history: None,
+            population_delta: 0,
+            stable_generations: 0,
+            cell_aspect: 1,
+            highlight_changes: false,
+            mark_dying: false,
+            dead_marks: false,
+            cursor_style: CursorStyle::Block,
+            brush_size: 1,
+            random_density: DEFAULT_RANDOM_DENSITY,
+            search: None,
+            placement: None,
+            macros: HashMap::new(),
+            macro_recording: None,
+            macro_command: None,
+            macro_depth: 0,
+            save_prompt: None,
+            auto_pause: false,
+            auto_pause_reason: None,
+            notice: None,
+            population_trend: VecDeque::with_capacity(POPULATION_TREND_LENGTH),
+            generation_limit: None,
+            exit_on_limit: false,
+            limit_reached: false,
+            garden_of_eden: false,
+            steps_per_frame: None,
+            last_update: Instant::now(),
+        }
+    }
+    
+    /// Keep a rewind history of up to `depth` snapshots, one every `interval` generations.
+    /// While paused, `<` and `>` step backward and forward through it.
+    pub fn enable_history(&mut self, depth: usize, interval: usize) {
+        self.history = Some(GridHistory::new(depth, interval));
+    }
+    
+    /// Draw each cell this many terminal characters wide; 2 gives roughly square cells
+    pub fn set_cell_aspect(&mut self, chars_wide: usize) {
+        self.cell_aspect = chars_wide;
+    }
+    
+    /// Color births and survivors differently, and optionally cells about to die
+    pub fn set_change_highlights(&mut self, highlight_changes: bool, mark_dying: bool) {
+        self.highlight_changes = highlight_changes;
+        self.mark_dying = mark_dying;
+    }
+    
+    /// Draw dead cells as a faint mark of the cell theme instead of leaving them blank
+    pub fn set_dead_marks(&mut self, enabled: bool) {
+        self.dead_marks = enabled;
+    }
+    
+    /// Mark the cell under the cursor in this style; `o` cycles through the styles while playing
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.cursor_style = style;
+    }
+    
+    /// Toggle a square of this many cells per side around the cursor, from 1 (a single cell)
+    /// up to 9. `[` and `]` change it while playing.
+    pub fn set_brush_size(&mut self, size: usize) {
+        self.brush_size = size.clamp(1, MAX_BRUSH_SIZE);
+    }
+    
+    /// Cells per side of the square a toggle edits
+    pub fn brush_size(&self) -> usize {
+        self.brush_size
+    }
+    
+    /// Bring this share of cells to life when randomizing with `r` and `R`, from 0.0 to 1.0.
+    /// `{` and `}` change it in steps of 5% while playing.
+    pub fn set_random_density(&mut self, density: f64) {
+        self.random_density = density.clamp(0.0, 1.0);
+    }
+    
+    /// Share of cells brought to life when randomizing
+    pub fn random_density(&self) -> f64 {
+        self.random_density
+    }
+    
+    /// Fill the grid at random at the random density, as `r` does
+    pub fn randomize(&mut self) {
+        let seed = self.next_seed();
+        self.grid.randomize_seeded(self.random_density, seed);
+    }
+    
+    /// Simulate with a different birth/survival rule
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.grid.set_rule(rule);
+    }
+    
+    /// Simulate with a range rule over a larger neighborhood, or the birth/survival rule with None
+    pub fn set_range_rule(&mut self, rule: Option<RangeRule>) {
+        self.grid.set_range_rule(rule);
+    }
+    
+    /// Seed the game's random number generator so random edits are reproducible
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+    
+    /// Record the session to a replay file when the game exits
+    pub fn record_to(&mut self, path: PathBuf) {
+        self.record_path = Some(path);
+    }
+    
+    /// Record everything drawn to the terminal as an asciinema cast file
+    pub fn record_cast_to(&mut self, path: PathBuf) {
+        self.cast_path = Some(path);
+    }
+    
+    /// Save a PNG of the whole grid to this path whenever `p` is pressed
+    pub fn screenshot_to(&mut self, path: PathBuf) {
+        self.screenshot_path = Some(path);
+    }
+    
+    /// Resume a game saved with `save_session`: the same board, rule, generation, speed and
+    /// running state, with the cursor, viewport and zoom restored once it starts playing
+    pub fn load_session<P: AsRef<Path>>(path: P, max_fps: u64) -> io::Result<Self> {
+        let session = SessionState::load_from_file(path)?;
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+        
+        let boundary = BoundaryType::parse(&session.boundary)
+            .ok_or_else(|| invalid(format!("Unknown boundary '{}'", session.boundary)))?;
+        let mut game = Game::new(session.width, session.height, max_fps, boundary, None);
+        let (rule, range_rule) = rule::parse_any(&session.rule).map_err(|e| invalid(e.to_string()))?;
+        game.set_rule(rule);
+        game.set_range_rule(range_rule);
+        for &(x, y) in &session.cells {
+            if x >= session.width || y >= session.height {
+                return Err(invalid(format!("Cell ({}, {}) is outside the {}x{} grid", x, y, session.width, session.height)));
+            }
+            game.grid.set(x, y, true);
+        }
+        
+        game.generation = session.generation;
+        game.speed = session.speed.min(9);
+        game.state = if session.running { GameState::Running } else { GameState::Paused };
+        game.view = Some(session.view);
+        Ok(game)
+    }
+    
+    /// The game and the renderer's view as a session that `load_session` can resume
+    pub fn session_state<W: Write>(&self, renderer: &Renderer<W>) -> SessionState {
+        let (width, height) = self.grid.dimensions();
+        SessionState {
+            width,
+            height,
+            boundary: self.grid.boundary().to_string(),
+            rule: self.grid.rule_notation(),
+            generation: self.generation,
+            speed: self.speed,
+            running: matches!(self.state, GameState::Running),
+            cells: self.grid.live_cells().collect(),
+            view: renderer.view_state(),
         }
     }
     
+    /// Save the game and the renderer's view to a session file
+    pub fn save_session<P: AsRef<Path>, W: Write>(&self, renderer: &Renderer<W>, path: P) -> io::Result<()> {
+        self.session_state(renderer).save_to_file(path)
+    }
+    
+    /// Save the session to this path when the game exits
+    pub fn save_session_to(&mut self, path: PathBuf) {
+        self.session_path = Some(path);
+    }
+    
+    /// Replace the grid with the replay's starting board and play its events back
+    pub fn play_replay(&mut self, replay: Replay) {
+        self.grid = replay.initial_grid();
+        self.player = Some(ReplayPlayer::new(replay));
+    }
+    
+    /// Replace the grid, e.g. with one loaded from an image. It should match the game's dimensions.
+    pub fn set_grid(&mut self, grid: Grid) {
+        self.grid = grid;
+    }
+    
     /// Initialize the grid with a predefined pattern
     pub fn initialize_with_pattern(&mut self, pattern: &Pattern, x: usize, y: usize) {
         pattern.place(&mut self.grid, x, y);
@@ -57,61 +342,257 @@ impl Game {
         self.grid.dimensions()
     }
     
+    /// Advance the simulation by one generation
+    pub fn step(&mut self) {
+        // The cells that flipped give both the population change and whether anything moved,
+        // without hashing or counting the grid before the update
+        let changes = self.grid.update_returning_changes();
+        self.generation += 1;
+        self.garden_of_eden = false;
+        
+        let population = self.grid.count_alive();
+        self.population_delta = changes.iter().map(|&(_, _, alive)| if alive { 1 } else { -1 }).sum();
+        if self.population_trend.len() == POPULATION_TREND_LENGTH {
+            self.population_trend.pop_front();
+        }
+        self.population_trend.push_back(population);
+        if changes.is_empty() {
+            self.stable_generations += 1;
+        } else {
+            self.stable_generations = 0;
+        }
+        
+        if let Some(history) = &mut self.history {
+            history.record(self.generation, &self.grid);
+        }
+        
+        self.check_auto_pause();
+    }
+    
+    /// Pause automatically once the population dies out or the grid stops changing
+    pub fn set_auto_pause(&mut self, armed: bool) {
+        self.auto_pause = armed;
+        self.auto_pause_reason = None;
+    }
+    
+    /// Why auto-pause last stopped the simulation, if it has since it was armed or resumed
+    pub fn auto_pause_reason(&self) -> Option<&'static str> {
+        self.auto_pause_reason
+    }
+    
+    /// The outcome of the last screenshot or pattern save, shown in the status bar until the next key
+    pub fn notice(&self) -> Option<&str> {
+        self.notice.as_deref()
+    }
+    
+    /// Stop running once the given generation is reached, either pausing or, with
+    /// `exit_on_limit`, leaving the game. Resuming after the limit runs on without stopping again.
+    pub fn set_generation_limit(&mut self, limit: Option<usize>, exit_on_limit: bool) {
+        self.generation_limit = limit;
+        self.exit_on_limit = exit_on_limit;
+        self.limit_reached = false;
+    }
+    
+    /// Advance this many generations each time a step is due instead of one, so fast-forwarding
+    /// is not held back by drawing every generation. `None` picks it from the speed and frame
+    /// rate: one at speeds the frames keep up with, and enough to keep up at faster speeds.
+    pub fn set_steps_per_frame(&mut self, steps: Option<usize>) {
+        self.steps_per_frame = steps.map(|steps| steps.max(1));
+    }
+    
+    /// Generations advanced each time a step is due
+    pub fn steps_per_frame(&self) -> usize {
+        self.steps_per_frame.unwrap_or_else(|| self.derived_steps_per_frame())
+    }
+    
+    // Generations a second the current speed asks for: speed + 1 up to the default of 5, as
+    // it always was, then four times faster with each speed above it, to 1536 at 9
+    fn generations_per_second(&self) -> u64 {
+        match self.speed.min(9) {
+            speed @ 0..=5 => speed as u64 + 1,
+            speed => 6 << (2 * (speed - 5)),
+        }
+    }
+    
+    // Generations each frame has to advance for the game to keep up with its speed when
+    // frames come at most max_fps times a second
+    fn derived_steps_per_frame(&self) -> usize {
+        self.generations_per_second().div_ceil(self.max_fps.max(1)) as usize
+    }
+    
+    /// Whether the simulation stopped because it reached the generation limit
+    pub fn limit_reached(&self) -> bool {
+        self.limit_reached
+    }
+    
+    /// Whether the simulation is paused
+    pub fn is_paused(&self) -> bool {
+        matches!(self.state, GameState::Paused)
+    }
+    
+    /// Number of live cells
+    pub fn population(&self) -> usize {
+        self.grid.count_alive()
+    }
+    
+    /// Number of generations simulated since the start or the last clear
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    /// Change in population caused by the last generation
+    pub fn population_delta(&self) -> isize {
+        self.population_delta
+    }
+    
+    /// Population after each of the last generations stepped, oldest first
+    pub fn population_trend(&self) -> &VecDeque<usize> {
+        &self.population_trend
+    }
+    
+    /// Number of consecutive generations that left the grid unchanged
+    pub fn stable_generations(&self) -> usize {
+        self.stable_generations
+    }
+    
+    /// Read-only access to the grid, for rendering it outside of `run`
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+    
+    /// Set a single cell alive or dead
+    pub fn set_cell(&mut self, x: usize, y: usize, state: bool) {
+        self.grid.set(x, y, state);
+    }
+    
+    /// Draw the whole grid as `run` would, in the classic theme with dead cells marked (`O` alive,
+    /// `.` dead), and return the characters drawn, one line per row. Lets tests check what the
+    /// game shows.
+    pub fn render_to_string(&self) -> String {
+        let (width, height) = self.grid.dimensions();
+        let mut renderer = Renderer::new(Vec::new(), width, height, CellTheme::Classic, ColorTheme::Green);
+        renderer.set_cell_aspect(self.cell_aspect);
+        renderer.set_dead_marks(true);
+        renderer.set_wrap(matches!(self.grid.boundary(), BoundaryType::Wrap));
+        renderer.set_screen_size(width * self.cell_aspect, height + renderer::STATUS_ROWS);
+        
+        // Writing to a Vec<u8> cannot fail
+        renderer.render_grid(&self.grid).expect("rendering into memory failed");
+        renderer::screen_text(&renderer.into_output())
+    }
+    
     pub fn run(&mut self, cell_theme: CellTheme, color_theme: ColorTheme) -> crossterm::Result<()> {
         // Setup terminal
         terminal::enable_raw_mode()?;
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen)?;
         
-        // Initialize renderer
+        // Tee the renderer's output into a cast file if recording the terminal was requested
+        let (columns, rows) = terminal::size()?;
+        let result = match &self.cast_path {
+            Some(path) => match File::create(path).and_then(|file| CastWriter::new(stdout, BufWriter::new(file), columns, rows)) {
+                Ok(output) => self.play_on(output, cell_theme, color_theme).map(|output| {
+                    if let Err(e) = output.finish() {
+                        eprintln!("Failed to save cast: {}", e);
+                    }
+                }),
+                Err(e) => {
+                    eprintln!("Failed to create cast file: {}", e);
+                    self.play_on(io::stdout(), cell_theme, color_theme).map(drop)
+                },
+            },
+            None => self.play_on(stdout, cell_theme, color_theme).map(drop),
+        };
+        
+        execute!(io::stdout(), LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        
+        result
+    }
+    
+    // Run the game on a renderer drawing to `output`, cleaning the screen up afterwards,
+    // and hand the output back
+    fn play_on<W: Write>(&mut self, output: W, cell_theme: CellTheme, color_theme: ColorTheme) -> crossterm::Result<W> {
+        let mut renderer = self.create_renderer(output, cell_theme, color_theme)?;
+        let result = self.play(&mut renderer);
+        renderer.cleanup()?;
+        result.map(|_| renderer.into_output())
+    }
+    
+    // Build a renderer set up for the game's grid and the current terminal
+    fn create_renderer<W: Write>(&self, output: W, cell_theme: CellTheme, color_theme: ColorTheme) -> crossterm::Result<Renderer<W>> {
         let (width, height) = self.grid.dimensions();
-        let mut renderer = Renderer::new(stdout, width, height, cell_theme, color_theme);
+        let mut renderer = Renderer::new(output, width, height, cell_theme, color_theme);
+        renderer.set_cell_aspect(self.cell_aspect);
+        renderer.set_highlight_changes(self.highlight_changes);
+        renderer.set_mark_dying(self.mark_dying);
+        renderer.set_dead_marks(self.dead_marks);
+        renderer.set_cursor_style(self.cursor_style);
+        renderer.set_wrap(matches!(self.grid.boundary(), BoundaryType::Wrap));
+        renderer.set_incremental(true);
+        let (columns, rows) = terminal::size()?;
+        renderer.set_screen_size(columns as usize, rows as usize);
+        if let Some(view) = self.view {
+            renderer.set_view_state(view);
+        }
         renderer.init()?;
+        Ok(renderer)
+    }
+    
+    // Main loop: load the saved board, then simulate, draw and handle input until quit
+    fn play<W: Write>(&mut self, renderer: &mut Renderer<W>) -> crossterm::Result<()> {
+        // If save path was provided, try to load grid state. A replay brings its own board,
+        // which only needs snapshotting so it can be rewound to.
+        if self.player.is_some() {
+            self.record_edit();
+        } else if let Err(e) = self.load_board() {
+            eprintln!("Failed to load grid state: {}", e);
+        }
         
-        // If save path was provided, try to load grid state
-        if let Some(path) = &self.save_path {
-            if path.exists() {
-                if let Err(e) = self.grid.load_from_file(path) {
-                    eprintln!("Failed to load grid state: {}", e);
-                }
-            }
+        // Start recording once the initial board is in place
+        if self.record_path.is_some() {
+            self.recorder = Some(ReplayRecorder::new(Replay::from_grid(&self.grid)));
         }
         
-        let mut last_update = Instant::now();
         let frame_time = Duration::from_millis(1000 / self.max_fps);
+        let mut last_frame = Instant::now();
         
-        // Main game loop
-        'game_loop: loop {
-            // Handle input
-            if event::poll(Duration::from_millis(10))? {
-                if let Event::Key(key_event) = event::read()? {
-                    if self.handle_input(key_event, &mut renderer)? {
-                        break 'game_loop;
-                    }
-                }
-            }
+        // Main game loop. Waiting for input fills the time until the next frame is due,
+        // so keys are handled as soon as they arrive rather than after a sleep.
+        let mut needs_render = true;
+        loop {
+            let running = matches!(self.state, GameState::Running);
+            let idle = !running && self.player.is_none();
+            let timeout = if idle {
+                PAUSED_POLL_TIMEOUT
+            } else {
+                frame_time.saturating_sub(last_frame.elapsed())
+            };
             
-            // Update game state
-            let now = Instant::now();
-            if matches!(self.state, GameState::Running) && 
-               now.duration_since(last_update).as_millis() >= (1000 / (self.speed + 1) as u128) {
-                self.grid.update();
-                self.generation += 1;
-                last_update = now;
+            match self.handle_events(renderer, timeout)? {
+                GameAction::Quit => break,
+                GameAction::Redraw => needs_render = true,
+                GameAction::None => {},
             }
             
-            // Render
-            let state_text = match self.state {
-                GameState::Running => "Running",
-                GameState::Paused => "Paused",
-            };
-            
-            renderer.render(&self.grid, state_text, self.generation, self.speed)?;
+            self.update();
+            if self.limit_reached && self.exit_on_limit {
+                break;
+            }
             
-            // Cap FPS
-            let elapsed = now.elapsed();
-            if elapsed < frame_time {
-                std::thread::sleep(frame_time - elapsed);
+            // Render at most once per frame while running, and only on changes while paused
+            let frame_due = matches!(self.state, GameState::Running) && last_frame.elapsed() >= frame_time;
+            if needs_render || frame_due {
+                self.draw(renderer)?;
+                needs_render = false;
+                last_frame = Instant::now();
+            }
+        }
+        
+        // Save the recorded session if recording was requested
+        if let (Some(recorder), Some(path)) = (self.recorder.take(), &self.record_path) {
+            if let Err(e) = recorder.finish(self.generation).save_to_file(path) {
+                eprintln!("Failed to save replay: {}", e);
             }
         }
         
@@ -122,18 +603,353 @@ impl Game {
             }
         }
         
-        // Clean up
-        renderer.cleanup()?;
-        execute!(io::stdout(), LeaveAlternateScreen)?;
-        terminal::disable_raw_mode()?;
+        // Save the whole session, view included, if requested
+        if let Some(path) = &self.session_path {
+            if let Err(e) = self.save_session(renderer, path) {
+                eprintln!("Failed to save session: {}", e);
+            }
+        }
         
         Ok(())
     }
     
-    fn handle_input(&mut self, key_event: KeyEvent, renderer: &mut Renderer<io::Stdout>) -> crossterm::Result<bool> {
-        match key_event.code {
-            KeyCode::Char('q') => return Ok(true),
+    /// Play any due replay events, then wait up to `timeout` for a terminal event and handle it
+    pub fn handle_events<W: Write>(&mut self, renderer: &mut Renderer<W>, timeout: Duration) -> crossterm::Result<GameAction> {
+        if self.play_due_events(renderer) {
+            return Ok(GameAction::Redraw);
+        }
+        
+        if !event::poll(timeout)? {
+            return Ok(GameAction::None);
+        }
+        
+        // Any event, including a terminal resize, may change what is on screen
+        match event::read()? {
+            Event::Key(key_event) => Ok(self.handle_key(key_event, renderer)),
+            Event::Resize(columns, rows) => {
+                renderer.set_screen_size(columns as usize, rows as usize);
+                Ok(GameAction::Redraw)
+            },
+            _ => Ok(GameAction::Redraw),
+        }
+    }
+    
+    /// Advance `steps_per_frame` generations if the game is running and the current speed says
+    /// a step is due. Returns whether a step was taken.
+    pub fn update(&mut self) -> bool {
+        self.check_generation_limit();
+        
+        let now = Instant::now();
+        // A batch of the derived size falls due often enough to run at the speed's rate
+        let interval = 1000 * self.derived_steps_per_frame() as u128 / self.generations_per_second() as u128;
+        let step_due = now.duration_since(self.last_update).as_millis() >= interval;
+        
+        if !matches!(self.state, GameState::Running) || !step_due || !self.replay_allows_step() {
+            return false;
+        }
+        
+        for _ in 0..self.steps_per_frame() {
+            self.step();
+            
+            // Checked here rather than in step so edits and replays can't stop the game,
+            // and the limit is exact however fast the game runs
+            self.check_generation_limit();
+            
+            // Stop early for the limit, an auto-pause or the next replay event
+            if !matches!(self.state, GameState::Running) || !self.replay_allows_step() {
+                break;
+            }
+        }
+        self.last_update = now;
+        true
+    }
+    
+    /// Load the board from the save path given to `new`, if that file exists, and screen it
+    /// for Garden of Eden patterns. The board is snapshotted either way so it can be rewound
+    /// to. Returns whether a board was loaded.
+    pub fn load_board(&mut self) -> io::Result<bool> {
+        let Some(path) = self.save_path.as_ref().filter(|path| path.exists()) else {
+            self.record_edit();
+            return Ok(false);
+        };
+        
+        let loaded = self.grid.load_from_file(path);
+        self.record_edit();
+        loaded?;
+        self.garden_of_eden = self.grid.has_orphan_pattern();
+        Ok(true)
+    }
+    
+    /// Whether the loaded board contains a pattern no previous generation could produce, so it
+    /// cannot have been reached by running the game. Cleared once the board steps or is edited.
+    pub fn garden_of_eden(&self) -> bool {
+        self.garden_of_eden
+    }
+    
+    /// Draw the grid, status bar and any open prompt
+    pub fn draw<W: Write>(&self, renderer: &mut Renderer<W>) -> crossterm::Result<()> {
+        let state_text = match self.state {
+            GameState::Running => "Running",
+            GameState::Paused if self.limit_reached => "Paused, limit reached",
+            GameState::Paused => "Paused",
+        };
+        let state_text = match &self.macro_recording {
+            Some((register, _)) => format!("{}, recording @{}", state_text, register),
+            None => state_text.to_string(),
+        };
+        let state_text = match self.brush_size {
+            1 => state_text,
+            size => format!("{}, brush {}x{}", state_text, size, size),
+        };
+        let state_text = if self.garden_of_eden {
+            format!("{}, Garden of Eden", state_text)
+        } else {
+            state_text
+        };
+        
+        let auto_pause = match (self.auto_pause, self.auto_pause_reason) {
+            (false, _) => None,
+            (true, None) => Some("armed".to_string()),
+            (true, Some(reason)) => Some(format!("paused, {}", reason)),
+        };
+        let rule = self.grid.rule_notation();
+        
+        // Show where the pattern being placed would land, clipped to the grid as placing it would be
+        let (width, height) = self.grid.dimensions();
+        let (cursor_x, cursor_y) = renderer.get_cursor_pos();
+        let preview = self.placement.iter()
+            .flat_map(|pattern| pattern.cells.iter())
+            .map(|&(x, y)| (cursor_x + x, cursor_y + y))
+            .filter(|&(x, y)| x < width && y < height)
+            .collect();
+        renderer.set_preview(preview);
+        
+        // Outline the brush while it edits more than the cell under the cursor
+        let brush = if self.brush_size > 1 && self.placement.is_none() {
+            self.brush_cells(cursor_x, cursor_y)
+        } else {
+            Vec::new()
+        };
+        renderer.set_brush_footprint(brush);
+        
+        renderer.render(&self.grid, &StatusInfo {
+            game_state: &state_text,
+            generation: self.generation,
+            speed: self.speed,
+            population_delta: self.population_delta,
+            stable_generations: self.stable_generations,
+            rule: &rule,
+            auto_pause: auto_pause.as_deref(),
+            notice: self.notice.as_deref(),
+            population_trend: &self.population_trend,
+            random_density: self.random_density,
+        })?;
+        
+        if let Some(search) = &self.search {
+            let matches = PatternLibrary::search_in(PatternLibrary::all_with_user_patterns(), &search.query);
+            let labels: Vec<String> = matches.iter()
+                .take(SEARCH_RESULTS_SHOWN)
+                .map(|pattern| format!("{} ({})", pattern.name, pattern.category))
+                .collect();
+            let names: Vec<&str> = labels.iter().map(String::as_str).collect();
+            let preview = matches.get(search.selected)
+                .map(|pattern| pattern.ascii_preview(SEARCH_PREVIEW_SIZE.0, SEARCH_PREVIEW_SIZE.1))
+                .unwrap_or_default();
+            renderer.render_search(&search.query, &names, search.selected, &preview)?;
+        }
+        
+        if let Some(name) = &self.save_prompt {
+            renderer.render_prompt("Save pattern as: ", name, "Enter-save | Esc-cancel")?;
+        }
+        
+        if let Some(pattern) = &self.placement {
+            let warning = if pattern.would_collide(&self.grid, cursor_x, cursor_y) {
+                " - overwrites live cells"
+            } else {
+                ""
+            };
+            renderer.print_line(0, &format!(
+                "Placing {}{}  (hjkl/Arrows-move | Enter-place | Esc-cancel)",
+                pattern.name, warning
+            ))?;
+        }
+        
+        Ok(())
+    }
+    
+    // Apply every replay event whose timestamp has been reached, returning whether any were played
+    fn play_due_events<W: Write>(&mut self, renderer: &mut Renderer<W>) -> bool {
+        let mut played = false;
+        while let Some(entry) = self.player.as_mut().and_then(ReplayPlayer::next_due) {
+            // Catch up to the generation the event was recorded at
+            while self.generation < entry.generation {
+                self.step();
+            }
             
+            match entry.event {
+                ReplayEvent::Key { code, modifiers } => {
+                    self.handle_key(KeyEvent::new(code, modifiers), renderer);
+                },
+                ReplayEvent::Seed(_) => {},
+                ReplayEvent::End => self.player = None,
+            }
+            played = true;
+        }
+        
+        if self.player.as_ref().is_some_and(ReplayPlayer::is_finished) {
+            self.player = None;
+        }
+        
+        played
+    }
+    
+    // While replaying, never step past the generation of the next recorded event
+    fn replay_allows_step(&self) -> bool {
+        match self.player.as_ref().and_then(ReplayPlayer::peek) {
+            Some(entry) => self.generation < entry.generation,
+            None => true,
+        }
+    }
+    
+    // Snapshot the grid after an edit, discarding any history it invalidates
+    fn record_edit(&mut self) {
+        self.reset_change_tracking();
+        self.garden_of_eden = false;
+        
+        if let Some(history) = &mut self.history {
+            history.record_now(self.generation, &self.grid);
+        }
+    }
+    
+    // Stop the simulation if auto-pause is armed and the grid has died out or settled.
+    // The stable counter compares grid hashes, so this catches still lifes but not oscillators.
+    fn check_auto_pause(&mut self) {
+        if !self.auto_pause {
+            return;
+        }
+        
+        let reason = if self.grid.count_alive() == 0 {
+            "population died out"
+        } else if self.stable_generations >= AUTO_PAUSE_STABLE_GENERATIONS {
+            "grid stopped changing"
+        } else {
+            return;
+        };
+        
+        self.state = GameState::Paused;
+        self.auto_pause_reason = Some(reason);
+    }
+    
+    // Pause once the generation limit is reached or passed, which a jump through the rewind
+    // history can do. The limit is then used up, so resuming runs on without stopping again.
+    fn check_generation_limit(&mut self) {
+        if self.generation_limit.is_some_and(|limit| self.generation >= limit) {
+            self.generation_limit = None;
+            self.state = GameState::Paused;
+            self.limit_reached = true;
+        }
+    }
+    
+    // Forget the population delta and stable counter after the grid was changed by hand
+    fn reset_change_tracking(&mut self) {
+        self.population_delta = 0;
+        self.stable_generations = 0;
+    }
+    
+    // Step back to the previous snapshot in the rewind history
+    fn rewind(&mut self) {
+        if let Some(snapshot) = self.history.as_ref().and_then(|history| history.before(self.generation)) {
+            snapshot.restore(&mut self.grid);
+            self.generation = snapshot.generation;
+            self.reset_change_tracking();
+        }
+    }
+    
+    // Step forward through the rewind history, simulating once past the recorded frontier
+    fn fast_forward(&mut self) {
+        match self.history.as_ref().and_then(|history| history.after(self.generation)) {
+            Some(snapshot) => {
+                snapshot.restore(&mut self.grid);
+                self.generation = snapshot.generation;
+                self.reset_change_tracking();
+            },
+            None => self.step(),
+        }
+        self.check_generation_limit();
+    }
+    
+    // Seed for the next random operation, taken from the replay or freshly drawn
+    fn next_seed(&mut self) -> u64 {
+        let seed = self.player.as_mut()
+            .and_then(ReplayPlayer::next_seed)
+            .unwrap_or_else(|| self.rng.gen());
+        
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(self.generation, ReplayEvent::Seed(seed));
+        }
+        
+        seed
+    }
+    
+    /// Apply a key press to the game and view. Every key other than quit may change the screen.
+    pub fn handle_key<W: Write>(&mut self, key_event: KeyEvent, renderer: &mut Renderer<W>) -> GameAction {
+        // While typing into a prompt or naming a macro register, q is just another letter
+        let typing = self.search.is_some() || self.save_prompt.is_some() || self.macro_command.is_some();
+        if key_event.code == KeyCode::Char('q') && !typing {
+            return GameAction::Quit;
+        }
+        
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(self.generation, ReplayEvent::Key {
+                code: key_event.code,
+                modifiers: key_event.modifiers,
+            });
+        }
+        
+        // Q ends a macro being recorded; every other key goes into it
+        if key_event.code == KeyCode::Char('Q') && !typing {
+            if let Some((register, keys)) = self.macro_recording.take() {
+                self.macros.insert(register, keys);
+                return GameAction::Redraw;
+            }
+        }
+        if let Some((_, keys)) = &mut self.macro_recording {
+            keys.push(key_event);
+        }
+        
+        self.notice = None;
+        self.apply_key(key_event, renderer);
+        GameAction::Redraw
+    }
+    
+    // Carry out a key from the user or from a macro being played
+    fn apply_key<W: Write>(&mut self, key_event: KeyEvent, renderer: &mut Renderer<W>) {
+        if let Some(command) = self.macro_command.take() {
+            if let KeyCode::Char(register) = key_event.code {
+                match command {
+                    MacroCommand::Record => self.macro_recording = Some((register, Vec::new())),
+                    MacroCommand::Play => self.play_macro(register, renderer),
+                }
+            }
+            return;
+        }
+        
+        if self.search.is_some() {
+            self.handle_search_input(key_event);
+            return;
+        }
+        
+        if self.save_prompt.is_some() {
+            self.handle_save_input(key_event);
+            return;
+        }
+        
+        if self.placement.is_some() {
+            self.handle_placement_input(key_event, renderer);
+            return;
+        }
+        
+        match key_event.code {
             // Cursor movement (Vim style)
             KeyCode::Char('h') => renderer.move_cursor(-1, 0),
             KeyCode::Char('l') => renderer.move_cursor(1, 0),
@@ -146,10 +962,18 @@ impl Game {
                 if key_event.modifiers.contains(KeyModifiers::SHIFT) {
                     self.grid.place_glider(x, y);
                 } else if key_event.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.grid.place_random_pattern(x, y);
-                } else {
+                    let seed = self.next_seed();
+                    self.grid.place_random_pattern_seeded(x, y, seed);
+                } else if self.brush_size == 1 {
                     self.grid.toggle(x, y);
+                } else {
+                    // The whole brush takes the opposite of the cell under the cursor
+                    let alive = !self.grid.get(x, y);
+                    for (bx, by) in self.brush_cells(x, y) {
+                        self.grid.set(bx, by, alive);
+                    }
                 }
+                self.record_edit();
             },
             
             // Simulation control
@@ -158,16 +982,44 @@ impl Game {
                     GameState::Running => GameState::Paused,
                     GameState::Paused => GameState::Running,
                 };
+                self.auto_pause_reason = None;
+                self.limit_reached = false;
+            },
+            KeyCode::Char('a') => self.set_auto_pause(!self.auto_pause),
+            KeyCode::Char('[') => self.set_brush_size(self.brush_size - 1),
+            KeyCode::Char(']') => self.set_brush_size(self.brush_size + 1),
+            KeyCode::Char('{') => self.nudge_random_density(-RANDOM_DENSITY_STEP),
+            KeyCode::Char('}') => self.nudge_random_density(RANDOM_DENSITY_STEP),
+            KeyCode::Char('r') => {
+                self.randomize();
+                self.record_edit();
+            },
+            KeyCode::Char('R') => {
+                let seed = self.next_seed();
+                self.grid.randomize_symmetric_seeded(self.random_density, Symmetry::Both, seed);
+                self.record_edit();
             },
-            KeyCode::Char('r') => self.grid.randomize(0.3),
             KeyCode::Char('c') => {
                 self.grid.clear();
                 self.generation = 0;
+                self.record_edit();
             },
-            KeyCode::Char(n) if n.is_digit(10) => {
+            KeyCode::Char(n) if n.is_ascii_digit() => {
                 self.speed = n.to_digit(10).unwrap() as usize;
             },
             
+            KeyCode::Char('s') if self.grid.count_alive() > 0 => self.save_prompt = Some(String::new()),
+            KeyCode::Char('p') => self.save_screenshot(),
+            KeyCode::Char('/') => {
+                self.search = Some(PatternSearch { query: String::new(), selected: 0 });
+            },
+            
+            // Rewind history
+            KeyCode::Char('<') if matches!(self.state, GameState::Paused) => self.rewind(),
+            KeyCode::Char('>') if matches!(self.state, GameState::Paused) && self.history.is_some() => {
+                self.fast_forward();
+            },
+            
             // View control
             KeyCode::Char('+') => renderer.zoom(1),
             KeyCode::Char('-') => renderer.zoom(-1),
@@ -176,10 +1028,167 @@ impl Game {
             KeyCode::Left => renderer.pan_viewport(-5, 0),
             KeyCode::Right => renderer.pan_viewport(5, 0),
             KeyCode::Char('z') => renderer.reset_view(),
+            KeyCode::Char('C') => {
+                let theme = renderer.color_theme().next();
+                renderer.set_color_theme(theme);
+            },
+            KeyCode::Char('o') => {
+                self.cursor_style = self.cursor_style.next();
+                renderer.set_cursor_style(self.cursor_style);
+            },
+            
+            // Keyboard macros (Vim style, with Q since q quits)
+            KeyCode::Char('Q') => self.macro_command = Some(MacroCommand::Record),
+            KeyCode::Char('@') => self.macro_command = Some(MacroCommand::Play),
             
             _ => {},
         }
+    }
+    
+    // Feed the keys recorded in a register back through the game, as if typed again
+    fn play_macro<W: Write>(&mut self, register: char, renderer: &mut Renderer<W>) {
+        let Some(keys) = self.macros.get(&register).cloned() else {
+            return;
+        };
+        if self.macro_depth >= MACRO_NESTING_LIMIT {
+            return;
+        }
+        
+        self.macro_depth += 1;
+        for key_event in keys {
+            self.apply_key(key_event, renderer);
+        }
+        self.macro_depth -= 1;
+    }
+    
+    // Edit the search query, move the highlight, or pick the highlighted pattern for placing
+    fn handle_search_input(&mut self, key_event: KeyEvent) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        let mut matches = PatternLibrary::search_in(PatternLibrary::all_with_user_patterns(), &search.query);
+        let shown = matches.len().min(SEARCH_RESULTS_SHOWN);
+        
+        match key_event.code {
+            KeyCode::Esc => self.search = None,
+            KeyCode::Enter if search.selected < matches.len() => {
+                self.placement = Some(matches.swap_remove(search.selected));
+                self.search = None;
+            },
+            KeyCode::Up => search.selected = search.selected.saturating_sub(1),
+            KeyCode::Down => search.selected = (search.selected + 1).min(shown.saturating_sub(1)),
+            KeyCode::Backspace => {
+                search.query.pop();
+                search.selected = 0;
+            },
+            KeyCode::Char(c) => {
+                search.query.push(c);
+                search.selected = 0;
+            },
+            _ => {},
+        }
+    }
+    
+    // Move the previewed pattern with the cursor, then stamp it on Enter or drop it on Escape
+    fn handle_placement_input<W: Write>(&mut self, key_event: KeyEvent, renderer: &mut Renderer<W>) {
+        match key_event.code {
+            KeyCode::Char('h') | KeyCode::Left => renderer.move_cursor(-1, 0),
+            KeyCode::Char('l') | KeyCode::Right => renderer.move_cursor(1, 0),
+            KeyCode::Char('k') | KeyCode::Up => renderer.move_cursor(0, -1),
+            KeyCode::Char('j') | KeyCode::Down => renderer.move_cursor(0, 1),
+            KeyCode::Esc => self.placement = None,
+            KeyCode::Enter => {
+                if let Some(pattern) = self.placement.take() {
+                    let (x, y) = renderer.get_cursor_pos();
+                    self.initialize_with_pattern(&pattern, x, y);
+                    self.record_edit();
+                }
+            },
+            _ => {},
+        }
+    }
+    
+    // Edit the name of the pattern being saved, then save the live cells under it
+    fn handle_save_input(&mut self, key_event: KeyEvent) {
+        let Some(name) = &mut self.save_prompt else {
+            return;
+        };
+        
+        match key_event.code {
+            KeyCode::Esc => self.save_prompt = None,
+            KeyCode::Enter if !name.trim().is_empty() => {
+                let name = name.trim().to_string();
+                self.save_prompt = None;
+                self.save_user_pattern(&name);
+            },
+            KeyCode::Backspace => {
+                name.pop();
+            },
+            KeyCode::Char(c) => name.push(c),
+            _ => {},
+        }
+    }
+    
+    // Change the random density by a step, rounded to whole percents so repeated steps
+    // land back on the same values
+    fn nudge_random_density(&mut self, step: f64) {
+        self.set_random_density(((self.random_density + step) * 100.0).round() / 100.0);
+    }
+    
+    // Cells of the brush square centered on (x, y); an even-sized brush reaches further right
+    // and down. Wraps around the edges of wrapping grids and is clipped at the others.
+    fn brush_cells(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let (width, height) = self.grid.dimensions();
+        let wrap = matches!(self.grid.boundary(), BoundaryType::Wrap);
+        let reach = (self.brush_size - 1) / 2;
+        
+        let mut cells = Vec::with_capacity(self.brush_size * self.brush_size);
+        for dy in 0..self.brush_size {
+            for dx in 0..self.brush_size {
+                let bx = x as isize + dx as isize - reach as isize;
+                let by = y as isize + dy as isize - reach as isize;
+                if wrap {
+                    cells.push((bx.rem_euclid(width as isize) as usize, by.rem_euclid(height as isize) as usize));
+                } else if bx >= 0 && by >= 0 && (bx as usize) < width && (by as usize) < height {
+                    cells.push((bx as usize, by as usize));
+                }
+            }
+        }
+        cells
+    }
+    
+    // Write the current generation to the screenshot path, replacing the previous screenshot.
+    // Like saved patterns, replays skip the write.
+    fn save_screenshot(&mut self) {
+        let Some(path) = &self.screenshot_path else {
+            return;
+        };
+        if self.player.is_some() {
+            return;
+        }
+        
+        // The terminal is in raw mode on the alternate screen, so report in the status bar, not on stderr
+        self.notice = Some(match self.grid.save_png(path, SCREENSHOT_CELL_SIZE, SCREENSHOT_ALIVE_COLOR, SCREENSHOT_DEAD_COLOR) {
+            Ok(()) => format!("Screenshot saved to {}", path.display()),
+            Err(e) => format!("Screenshot failed: {}", e),
+        });
+    }
+    
+    // Append the trimmed live cells to the user patterns file, so they load with the library next time.
+    // Replays skip the write, since playing a session back should not change files.
+    fn save_user_pattern(&mut self, name: &str) {
+        if self.player.is_some() {
+            return;
+        }
+        
+        let Some(path) = PatternLibrary::user_patterns_path() else {
+            self.notice = Some("Pattern not saved: no home directory or CONWAY_PATTERNS path".to_string());
+            return;
+        };
         
-        Ok(false)
+        self.notice = Some(match rle::append_to_file(&path, name, &self.grid.trim()) {
+            Ok(()) => format!("Saved pattern '{}' to {}", name, path.display()),
+            Err(e) => format!("Pattern not saved: {}", e),
+        });
     }
 }
\ No newline at end of file
@@ -0,0 +1,454 @@
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    
+    use crate::game::{Game, GameAction};
+    use crate::config::{BoundaryType, CellTheme, ColorTheme};
+    use crate::grid::Grid;
+    use crate::renderer::{Renderer, STATUS_ROWS};
+    use crate::rule::Rule;
+    use crate::session::SessionState;
+
+    #[test]
+    fn test_step_without_run_loop() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        
+        // Horizontal blinker
+        game.set_cell(4, 5, true);
+        game.set_cell(5, 5, true);
+        game.set_cell(6, 5, true);
+        assert_eq!(game.population(), 3);
+        assert_eq!(game.generation(), 0);
+        
+        game.step();
+        
+        assert_eq!(game.generation(), 1);
+        assert_eq!(game.population(), 3);
+        assert!(game.grid().get(5, 4));
+        assert!(game.grid().get(5, 6));
+        assert!(!game.grid().get(4, 5));
+    }
+    
+    #[test]
+    fn test_population_delta_and_stable_counter() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        
+        // An L-tromino becomes a block after one generation
+        game.set_cell(4, 4, true);
+        game.set_cell(5, 4, true);
+        game.set_cell(4, 5, true);
+        
+        game.step();
+        assert_eq!(game.population_delta(), 1);
+        assert_eq!(game.stable_generations(), 0);
+        
+        game.step();
+        game.step();
+        assert_eq!(game.population_delta(), 0);
+        assert_eq!(game.stable_generations(), 2);
+    }
+    
+    #[test]
+    fn test_auto_pause_on_still_life_and_extinction() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        game.set_cell(4, 4, true);
+        game.set_cell(5, 4, true);
+        game.set_cell(4, 5, true);
+        
+        // The tromino becomes a block, which then has to sit still for ten generations
+        game.set_auto_pause(true);
+        for _ in 0..10 {
+            game.step();
+        }
+        assert_eq!(game.auto_pause_reason(), None);
+        
+        game.step();
+        assert_eq!(game.auto_pause_reason(), Some("grid stopped changing"));
+        assert!(game.is_paused());
+        
+        // Disarming clears the reason and stops further checks
+        game.set_auto_pause(false);
+        game.step();
+        assert_eq!(game.auto_pause_reason(), None);
+        
+        // A lone cell dies immediately
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        game.set_auto_pause(true);
+        game.set_cell(2, 2, true);
+        game.step();
+        assert_eq!(game.auto_pause_reason(), Some("population died out"));
+    }
+    
+    #[test]
+    fn test_keys_drive_the_game_without_a_terminal() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        let mut press = |game: &mut Game, code: KeyCode| {
+            game.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &mut renderer)
+        };
+        
+        // Space toggles the cell under the cursor
+        assert_eq!(press(&mut game, KeyCode::Char(' ')), GameAction::Redraw);
+        assert_eq!(game.population(), 1);
+        
+        // A paused game never steps on update
+        assert!(!game.update());
+        
+        // q is typed into the search prompt rather than quitting
+        assert_eq!(press(&mut game, KeyCode::Char('/')), GameAction::Redraw);
+        assert_eq!(press(&mut game, KeyCode::Char('q')), GameAction::Redraw);
+        press(&mut game, KeyCode::Esc);
+        assert_eq!(press(&mut game, KeyCode::Char('q')), GameAction::Quit);
+    }
+    
+    #[test]
+    fn test_generation_limit_pauses_exactly_at_the_limit() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        game.set_generation_limit(Some(3), false);
+        
+        // Blinker, run at top speed
+        game.set_cell(4, 5, true);
+        game.set_cell(5, 5, true);
+        game.set_cell(6, 5, true);
+        game.handle_key(KeyEvent::new(KeyCode::Char('9'), KeyModifiers::NONE), &mut renderer);
+        game.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut renderer);
+        
+        while !game.is_paused() {
+            game.update();
+        }
+        assert_eq!(game.generation(), 3);
+        assert!(game.limit_reached());
+        
+        // Resuming clears the limit and runs past it
+        game.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut renderer);
+        assert!(!game.limit_reached());
+        while game.generation() == 3 {
+            game.update();
+        }
+        assert!(!game.is_paused());
+    }
+    
+    #[test]
+    fn test_generation_limit_catches_steps_past_it() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        game.enable_history(10, 1);
+        game.set_cell(4, 5, true);
+        game.set_cell(5, 5, true);
+        game.set_cell(6, 5, true);
+        
+        // Stepping with > while paused carries the game past a limit set below it
+        for _ in 0..5 {
+            game.handle_key(KeyEvent::new(KeyCode::Char('>'), KeyModifiers::NONE), &mut renderer);
+        }
+        game.set_generation_limit(Some(3), false);
+        game.handle_key(KeyEvent::new(KeyCode::Char('>'), KeyModifiers::NONE), &mut renderer);
+        assert_eq!(game.generation(), 6);
+        assert!(game.limit_reached());
+        
+        // Resuming runs on, and a limit already passed stops the game before it steps again
+        game.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut renderer);
+        while game.generation() == 6 {
+            game.update();
+        }
+        assert!(!game.is_paused());
+        game.set_generation_limit(Some(4), false);
+        let generation = game.generation();
+        game.update();
+        assert!(game.is_paused() && game.limit_reached());
+        assert_eq!(game.generation(), generation);
+    }
+    
+    #[test]
+    fn test_population_trend_keeps_recent_generations() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        
+        // Block plus a lone cell that dies on the first step
+        game.set_cell(1, 1, true);
+        game.set_cell(2, 1, true);
+        game.set_cell(1, 2, true);
+        game.set_cell(2, 2, true);
+        game.set_cell(7, 7, true);
+        
+        for _ in 0..100 {
+            game.step();
+        }
+        assert_eq!(game.population_trend().len(), 60);
+        assert!(game.population_trend().iter().all(|&population| population == 4));
+    }
+    
+    #[test]
+    fn test_color_theme_cycles_in_place() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        let key = KeyEvent::new(KeyCode::Char('C'), KeyModifiers::SHIFT);
+        
+        game.handle_key(key, &mut renderer);
+        assert!(matches!(renderer.color_theme(), ColorTheme::Blue));
+        
+        // Five themes bring it back around
+        for _ in 0..4 {
+            game.handle_key(key, &mut renderer);
+        }
+        assert!(matches!(renderer.color_theme(), ColorTheme::Green));
+    }
+    
+    #[test]
+    fn test_chosen_pattern_is_previewed_until_placed() {
+        let mut game = Game::new(20, 20, 60, BoundaryType::Fixed, None);
+        let mut renderer = Renderer::new(Vec::new(), 20, 20, CellTheme::Classic, ColorTheme::Green);
+        let mut press = |game: &mut Game, code: KeyCode| {
+            game.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &mut renderer);
+        };
+        
+        press(&mut game, KeyCode::Char('/'));
+        for c in "toad".chars() {
+            press(&mut game, KeyCode::Char(c));
+        }
+        press(&mut game, KeyCode::Enter);
+        
+        // Choosing the pattern leaves the grid alone while it is moved around
+        assert_eq!(game.population(), 0);
+        press(&mut game, KeyCode::Char('l'));
+        press(&mut game, KeyCode::Down);
+        assert_eq!(game.population(), 0);
+        
+        // Enter stamps it at the moved cursor and ends the placement
+        press(&mut game, KeyCode::Enter);
+        assert_eq!(game.population(), 6);
+        assert!(game.grid().get(12, 11) && game.grid().get(11, 12));
+        press(&mut game, KeyCode::Char(' '));
+        assert_eq!(game.population(), 7);
+        
+        // Escape drops a chosen pattern without placing it
+        press(&mut game, KeyCode::Char('/'));
+        press(&mut game, KeyCode::Char('b'));
+        press(&mut game, KeyCode::Enter);
+        press(&mut game, KeyCode::Esc);
+        press(&mut game, KeyCode::Enter);
+        assert_eq!(game.population(), 7);
+    }
+    
+    #[test]
+    fn test_macros_record_and_replay_keys() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Fixed, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        let mut type_keys = |game: &mut Game, keys: &str| {
+            for c in keys.chars() {
+                assert_eq!(game.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), &mut renderer), GameAction::Redraw);
+            }
+        };
+        
+        // Recording runs the keys as usual; q names the register instead of quitting
+        type_keys(&mut game, "Qq lQ");
+        assert_eq!(game.population(), 1);
+        
+        // Each replay toggles the next cell along and moves on
+        type_keys(&mut game, "@q@q");
+        assert_eq!(game.population(), 3);
+        assert!(game.grid().get(5, 5) && game.grid().get(6, 5) && game.grid().get(7, 5));
+        
+        // A macro that plays itself stops instead of recursing forever
+        type_keys(&mut game, "Qb@bQ@b");
+        assert_eq!(game.population(), 3);
+    }
+    
+    #[test]
+    fn test_steps_per_frame_advances_several_generations() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Wrap, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        
+        // Speed 5 runs 6 generations a second, within 60 frames, but speed 9 runs 1536 and
+        // has to advance several generations each frame, more of them the fewer frames there are
+        game.handle_key(KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE), &mut renderer);
+        assert_eq!(game.steps_per_frame(), 1);
+        game.handle_key(KeyEvent::new(KeyCode::Char('9'), KeyModifiers::NONE), &mut renderer);
+        assert_eq!(game.steps_per_frame(), 26);
+        let mut slow = Game::new(10, 10, 4, BoundaryType::Wrap, None);
+        slow.handle_key(KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE), &mut renderer);
+        assert_eq!(slow.steps_per_frame(), 2);
+        slow.handle_key(KeyEvent::new(KeyCode::Char('9'), KeyModifiers::NONE), &mut renderer);
+        assert_eq!(slow.steps_per_frame(), 384);
+        
+        // Each update runs a batch, cut short at the generation limit
+        game.set_steps_per_frame(Some(4));
+        game.set_generation_limit(Some(10), false);
+        game.set_cell(5, 5, true);
+        game.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut renderer);
+        
+        while !game.update() {}
+        assert_eq!(game.generation(), 4);
+        while !game.is_paused() {
+            game.update();
+        }
+        assert_eq!(game.generation(), 10);
+    }
+    
+    #[test]
+    fn test_brush_toggles_a_square_around_the_cursor() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Fixed, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        let mut type_keys = |game: &mut Game, keys: &str| {
+            for c in keys.chars() {
+                game.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), &mut renderer);
+            }
+        };
+        
+        // Brush size 1 is the plain single-cell toggle
+        type_keys(&mut game, "[ ");
+        assert_eq!(game.brush_size(), 1);
+        assert_eq!(game.population(), 1);
+        
+        // A 3x3 brush takes the opposite of the cell under the cursor
+        type_keys(&mut game, "]] ");
+        assert_eq!(game.brush_size(), 3);
+        assert_eq!(game.population(), 0);
+        type_keys(&mut game, " ");
+        assert_eq!(game.population(), 9);
+        assert!(game.grid().get(4, 4) && game.grid().get(6, 6));
+        
+        // Larger brushes are clipped at the edges of a fixed grid
+        game.set_brush_size(20);
+        assert_eq!(game.brush_size(), 9);
+        type_keys(&mut game, " ");
+        assert_eq!(game.population(), 0);
+        type_keys(&mut game, " ");
+        assert_eq!(game.population(), 81);
+        assert!(game.grid().get(9, 9) && !game.grid().get(0, 0));
+    }
+    
+    #[test]
+    fn test_screenshot_outcome_is_shown_in_the_status_bar() {
+        let path = std::env::temp_dir().join(format!("conway_screenshot_test_{}.png", std::process::id()));
+        let mut game = Game::new(10, 10, 60, BoundaryType::Fixed, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        let mut press = |game: &mut Game, c: char| {
+            game.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), &mut renderer);
+        };
+        
+        game.screenshot_to(path.clone());
+        press(&mut game, 'p');
+        assert!(path.exists());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(game.notice(), Some(format!("Screenshot saved to {}", path.display()).as_str()));
+        
+        // The next key clears it
+        press(&mut game, 'l');
+        assert_eq!(game.notice(), None);
+        
+        // Failures are reported the same way rather than printed over the screen
+        game.screenshot_to(path.join("missing").join("board.png"));
+        press(&mut game, 'p');
+        assert!(game.notice().unwrap().starts_with("Screenshot failed: "));
+    }
+    
+    #[test]
+    fn test_loaded_board_is_screened_for_garden_of_eden() {
+        let path = std::env::temp_dir().join(format!("conway_eden_test_{}.bin", std::process::id()));
+        
+        // Nothing outside a fixed grid can bring these two corner cells to life
+        let mut board = Grid::new(10, 10, BoundaryType::Fixed);
+        board.set(0, 0, true);
+        board.set(1, 1, true);
+        board.save_to_file(&path).unwrap();
+        
+        let mut game = Game::new(10, 10, 60, BoundaryType::Fixed, Some(path.clone()));
+        assert!(game.load_board().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert!(game.garden_of_eden());
+        
+        // Once the board has stepped it has a predecessor again
+        game.step();
+        assert!(!game.garden_of_eden());
+        
+        // Without a saved board there is nothing to load or flag
+        let mut fresh = Game::new(10, 10, 60, BoundaryType::Fixed, Some(path));
+        assert!(!fresh.load_board().unwrap());
+        assert!(!fresh.garden_of_eden());
+    }
+    
+    #[test]
+    fn test_session_restores_game_and_view() {
+        let path = std::env::temp_dir().join(format!("conway_session_test_{}.toml", std::process::id()));
+        let mut game = Game::new(30, 20, 60, BoundaryType::Fixed, None);
+        let mut renderer = Renderer::new(Vec::new(), 30, 20, CellTheme::Classic, ColorTheme::Green);
+        renderer.set_screen_size(10, 10 + STATUS_ROWS);
+        game.set_rule(Rule::parse("B36/S23").unwrap());
+        for c in "7jjjlll ".chars() {
+            game.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), &mut renderer);
+        }
+        renderer.zoom(1);
+        game.step();
+        game.save_session(&renderer, &path).unwrap();
+        
+        let resumed = Game::load_session(&path, 60).unwrap();
+        let view = SessionState::load_from_file(&path).unwrap().view;
+        std::fs::remove_file(&path).unwrap();
+        let mut resumed_renderer = Renderer::new(Vec::new(), 30, 20, CellTheme::Classic, ColorTheme::Green);
+        resumed_renderer.set_screen_size(10, 10 + STATUS_ROWS);
+        resumed_renderer.set_view_state(view);
+        
+        assert!(resumed.grid() == game.grid());
+        assert_eq!(resumed.grid().rule_notation(), "B36/S23");
+        assert_eq!(resumed.generation(), 1);
+        assert_eq!(resumed.session_state(&resumed_renderer), game.session_state(&renderer));
+        assert_eq!(resumed_renderer.get_cursor_pos(), (18, 13));
+    }
+    
+    #[test]
+    fn test_render_to_string_shows_the_grid() {
+        let mut game = Game::new(5, 5, 60, BoundaryType::Fixed, None);
+        game.set_cell(1, 2, true);
+        game.set_cell(2, 2, true);
+        game.set_cell(3, 2, true);
+        assert_eq!(game.render_to_string(), ".....\n.....\n.OOO.\n.....\n.....");
+        
+        game.step();
+        assert_eq!(game.render_to_string(), ".....\n..O..\n..O..\n..O..\n.....");
+        
+        // Wider cells repeat each character
+        game.set_cell_aspect(2);
+        assert_eq!(game.render_to_string().lines().nth(1), Some("....OO...."));
+    }
+    
+    #[test]
+    fn test_randomize_uses_the_adjustable_density() {
+        let mut game = Game::new(10, 10, 60, BoundaryType::Fixed, None);
+        let mut renderer = Renderer::new(Vec::new(), 10, 10, CellTheme::Classic, ColorTheme::Green);
+        let mut type_keys = |game: &mut Game, keys: &str| {
+            for c in keys.chars() {
+                game.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), &mut renderer);
+            }
+        };
+        assert_eq!(game.random_density(), 0.3);
+        
+        // Steps stop at a full grid
+        type_keys(&mut game, &"}".repeat(20));
+        assert_eq!(game.random_density(), 1.0);
+        type_keys(&mut game, "r");
+        assert_eq!(game.population(), 100);
+        
+        type_keys(&mut game, "{{");
+        assert_eq!(game.random_density(), 0.9);
+        
+        game.set_random_density(-1.0);
+        type_keys(&mut game, "R");
+        assert_eq!(game.population(), 0);
+    }
+    
+    #[test]
+    fn test_randomize_on_start_is_reproducible() {
+        let random_game = || {
+            let mut game = Game::new(20, 20, 60, BoundaryType::Wrap, None);
+            game.set_seed(42);
+            game.set_random_density(0.5);
+            game.randomize();
+            game
+        };
+        
+        let game = random_game();
+        assert!(game.population() > 100 && game.population() < 300);
+        assert!(random_game().grid() == game.grid());
+    }
+}
\ No newline at end of file
@@ -1,23 +1,157 @@
+#[cfg(feature = "images")]
+use image::imageops::FilterType;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
 use std::path::Path;
+use std::sync::Arc;
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::automaton::Automaton;
 use crate::config::BoundaryType;
+use crate::rule::{RangeRule, Rule};
+
+// Grids with fewer cells than this update on one thread, since handing a few rows to
+// the thread pool costs more than computing them
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 40_000;
+
+// Symmetry applied by randomize_symmetric
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Symmetry {
+    Horizontal,   // Left half mirrored onto the right half
+    Vertical,     // Top half mirrored onto the bottom half
+    Both,         // Mirrored in both directions
+    Rotational4,  // Unchanged by quarter turns about the center
+}
+
+// Summary of the live cells in a grid, gathered in one pass by Grid::stats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridStats {
+    pub population: usize,
+    pub density: f64,  // Population as a fraction of all cells
+    pub bounding_box: Option<(usize, usize, usize, usize)>,  // (min_x, min_y, max_x, max_y), None when empty
+    pub occupied_area: usize,  // Cells inside the bounding box, 0 when empty
+}
+
+// How a grid settled in Grid::run_until_stable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StabilizationResult {
+    pub generations: usize,      // Generation the repeating cycle starts at, or all generations run if none was found
+    pub period: Option<usize>,   // 1 for a still life, more for an oscillator, None if the cap was reached first
+}
+
+impl StabilizationResult {
+    pub fn is_stable(&self) -> bool {
+        self.period.is_some()
+    }
+}
+
+// Spots the first generation whose state the grid has already been in, comparing state hashes
+pub struct CycleDetector {
+    seen: HashMap<u64, usize>,  // Generation at which each state hash first appeared
+    generation: usize,
+}
+
+impl CycleDetector {
+    // Start watching from the grid's current state as generation 0
+    pub fn new(grid: &Grid) -> Self {
+        Self {
+            seen: HashMap::from([(grid.hash_state(), 0)]),
+            generation: 0,
+        }
+    }
+    
+    // Record the grid one generation later. Once it repeats a state, returns how the grid
+    // settled: the generation that state first appeared at and the period of the cycle.
+    pub fn observe(&mut self, grid: &Grid) -> Option<StabilizationResult> {
+        self.generation += 1;
+        let first_seen = *self.seen.entry(grid.hash_state()).or_insert(self.generation);
+        (first_seen < self.generation).then(|| StabilizationResult {
+            generations: first_seen,
+            period: Some(self.generation - first_seen),
+        })
+    }
+}
+
+// Error returned when combining grids of different sizes
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridError {
+    DimensionMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    // A coordinate list line that is not two whole numbers
+    InvalidCoordinates {
+        line: usize,
+        text: String,
+    },
+    // A coordinate list cell that lies outside the grid
+    CoordinatesOutOfBounds {
+        line: usize,
+        x: usize,
+        y: usize,
+    },
+    // A file that does not start with the header its format requires
+    MissingHeader {
+        expected: &'static str,
+    },
+    // A width or height of zero, which leaves no cells to simulate
+    EmptyDimensions {
+        width: usize,
+        height: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::DimensionMismatch { expected, found } => write!(
+                f,
+                "Grid of size {}x{} cannot be combined with a {}x{} grid",
+                found.0, found.1, expected.0, expected.1
+            ),
+            GridError::InvalidCoordinates { line, text } => {
+                write!(f, "Line {}: expected an \"x y\" pair, found \"{}\"", line, text)
+            },
+            GridError::CoordinatesOutOfBounds { line, x, y } => {
+                write!(f, "Line {}: cell ({}, {}) is outside the grid", line, x, y)
+            },
+            GridError::MissingHeader { expected } => write!(f, "Expected a \"{}\" header", expected),
+            GridError::EmptyDimensions { width, height } => {
+                write!(f, "A {}x{} grid has no cells: width and height must be at least 1", width, height)
+            },
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
 
 // We'll use a bit-packed grid representation for efficiency
 // Each u64 stores 64 cells (1 bit per cell)
+// Automata with more than two states add bit planes laid out the same way: a live cell's
+// state is one more than the number spelled by its bits in those planes
+#[derive(Clone)]
 pub struct Grid {
     width: usize,
     height: usize,
     stride: usize,        // Number of u64s per row (width / 64, rounded up)
     cells: Vec<u64>,      // Bit-packed cells
+    state_planes: Vec<Vec<u64>>,  // Extra state bits of live cells, lowest bit first; empty for two states
     boundary: BoundaryType,
+    rule: Rule,           // Birth/survival rule applied by update
+    range_rule: Option<RangeRule>,  // Larger neighborhood rule replacing the rule in update when set
+    automaton: Option<Arc<dyn Automaton>>,  // Replaces the rule in update when set
+    parallel_threshold: usize,  // Cell count from which update splits rows across threads
 }
 
 impl Grid {
     pub fn new(width: usize, height: usize, boundary: BoundaryType) -> Self {
-        let stride = (width + 63) / 64;  // Round up to nearest 64
+        let stride = width.div_ceil(64);  // Round up to nearest 64
         let cells = vec![0; stride * height];
         
         Self {
@@ -25,8 +159,22 @@ impl Grid {
             height,
             stride,
             cells,
+            state_planes: Vec::new(),
             boundary,
+            rule: Rule::default(),
+            range_rule: None,
+            automaton: None,
+            parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
+        }
+    }
+    
+    // Create a grid, rejecting a zero width or height. Grid::new accepts them for the empty
+    // result of trim, but nothing can be simulated or shown on such a grid.
+    pub fn try_new(width: usize, height: usize, boundary: BoundaryType) -> Result<Self, GridError> {
+        if width == 0 || height == 0 {
+            return Err(GridError::EmptyDimensions { width, height });
         }
+        Ok(Self::new(width, height, boundary))
     }
     
     // Get cell state (true = alive, false = dead)
@@ -63,6 +211,41 @@ impl Grid {
         } else {
             self.cells[chunk_index] &= !(1u64 << bit_index);
         }
+        
+        // A cell set alive starts in state 1
+        for plane in &mut self.state_planes {
+            plane[chunk_index] &= !(1u64 << bit_index);
+        }
+    }
+    
+    // Get the automaton state of a cell: 0 for dead, 1 for alive on a two-state grid
+    pub fn get_state(&self, x: usize, y: usize) -> u8 {
+        if !self.get(x, y) {
+            return 0;
+        }
+        
+        let bit_index = x % 64;
+        let chunk_index = (y * self.stride) + (x / 64);
+        self.state_planes.iter().enumerate().fold(1, |state, (bit, plane)| {
+            state + ((((plane[chunk_index] >> bit_index) & 1) as u8) << bit)
+        })
+    }
+    
+    // Set the automaton state of a cell. States the grid has no bits for are cut down
+    // to the bits it has, so set the automaton before its states.
+    pub fn set_state(&mut self, x: usize, y: usize, state: u8) {
+        self.set(x, y, state != 0);
+        if state <= 1 || x >= self.width || y >= self.height {
+            return;
+        }
+        
+        let bit_index = x % 64;
+        let chunk_index = (y * self.stride) + (x / 64);
+        for (bit, plane) in self.state_planes.iter_mut().enumerate() {
+            if ((state - 1) >> bit) & 1 != 0 {
+                plane[chunk_index] |= 1u64 << bit_index;
+            }
+        }
     }
     
     // Toggle cell state
@@ -79,21 +262,27 @@ impl Grid {
         }
         
         self.cells[chunk_index] ^= 1u64 << bit_index;
+        for plane in &mut self.state_planes {
+            plane[chunk_index] &= !(1u64 << bit_index);
+        }
     }
     
-    // Count neighbors for a cell
-    pub fn count_neighbors(&self, x: usize, y: usize) -> u8 {
+    // Count the live neighbors of a cell within the neighborhood radius, 1 unless a range
+    // rule is set. On a wrapped grid narrower than the neighborhood, cells are counted
+    // once for every time they fall inside it.
+    pub fn count_neighbors(&self, x: usize, y: usize) -> usize {
         let mut count = 0;
+        let radius = self.radius() as isize;
         
-        for dy in -1..=1 {
-            for dx in -1..=1 {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
                 if dx == 0 && dy == 0 {
                     continue;
                 }
                 
                 let nx = match self.boundary {
                     BoundaryType::Wrap => (x as isize + dx).rem_euclid(self.width as isize) as usize,
-                    BoundaryType::Fixed => {
+                    BoundaryType::Fixed | BoundaryType::Absorbing => {
                         let nx = x as isize + dx;
                         if nx < 0 || nx >= self.width as isize {
                             continue;
@@ -104,7 +293,7 @@ impl Grid {
                 
                 let ny = match self.boundary {
                     BoundaryType::Wrap => (y as isize + dy).rem_euclid(self.height as isize) as usize,
-                    BoundaryType::Fixed => {
+                    BoundaryType::Fixed | BoundaryType::Absorbing => {
                         let ny = y as isize + dy;
                         if ny < 0 || ny >= self.height as isize {
                             continue;
@@ -122,45 +311,413 @@ impl Grid {
         count
     }
     
-    // Update the grid to the next generation
+    // Quick screen for Garden of Eden states: look for a small window of cells that no
+    // previous generation could have produced. Only windows of up to ORPHAN_WINDOW cells
+    // square whose surrounding ring is constrained by the grid's edges are checked, so false
+    // negatives are expected; a true result is a proof, a false result proves nothing. Grids
+    // on a range rule or a custom automaton are never flagged, since the search only knows
+    // life-like rules.
+    pub fn has_orphan_pattern(&self) -> bool {
+        if self.range_rule.is_some() || self.automaton.is_some() {
+            return false;
+        }
+        
+        let window_width = ORPHAN_WINDOW.min(self.width);
+        let window_height = ORPHAN_WINDOW.min(self.height);
+        if window_width == 0 || window_height == 0 {
+            return false;
+        }
+        
+        // Under Conway's rule every window this small has a predecessor once it has a free
+        // ring of cells around it (checked exhaustively), so only windows whose ring runs
+        // into a fixed edge, or wraps onto itself on a tiny torus, are worth searching. Other
+        // rules get the same limit, which keeps the screen to the grid's border rather than
+        // a search of every window on the board.
+        (0..=self.height - window_height).any(|y| {
+            (0..=self.width - window_width).any(|x| {
+                self.window_ring_is_constrained(x, y, window_width, window_height)
+                    && !self.window_has_predecessor(x, y, window_width, window_height)
+            })
+        })
+    }
+    
+    // Whether the cells around a window are not all free to take any value: some lie past a
+    // fixed edge, where they are always dead, or wrap around onto the window itself
+    fn window_ring_is_constrained(&self, x0: usize, y0: usize, window_width: usize, window_height: usize) -> bool {
+        match self.boundary {
+            BoundaryType::Wrap => self.width < window_width + 2 || self.height < window_height + 2,
+            BoundaryType::Fixed | BoundaryType::Absorbing => {
+                x0 == 0 || y0 == 0 || x0 + window_width == self.width || y0 + window_height == self.height
+            }
+        }
+    }
+    
+    // Search for any previous state of the surrounding cells that evolves into this window
+    fn window_has_predecessor(&self, x0: usize, y0: usize, window_width: usize, window_height: usize) -> bool {
+        // One variable per distinct grid cell the window depends on; cells past a fixed edge are always dead
+        let mut variables: Vec<(usize, usize)> = Vec::new();
+        let mut variable_at = |x: isize, y: isize| -> Option<usize> {
+            let (x, y) = self.wrap_coordinates(x, y)?;
+            Some(match variables.iter().position(|&cell| cell == (x, y)) {
+                Some(index) => index,
+                None => {
+                    variables.push((x, y));
+                    variables.len() - 1
+                }
+            })
+        };
+        
+        // Each target cell: its desired state, its own variable and its neighbor variables
+        let mut targets = Vec::new();
+        for y in y0..y0 + window_height {
+            for x in x0..x0 + window_width {
+                let center = variable_at(x as isize, y as isize);
+                let mut neighbors = Vec::new();
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        if dx != 0 || dy != 0 {
+                            neighbors.extend(variable_at(x as isize + dx, y as isize + dy));
+                        }
+                    }
+                }
+                targets.push((self.get(x, y), center, neighbors));
+            }
+        }
+        
+        // Check each target as soon as the last variable it depends on has been assigned
+        let mut checks: Vec<Vec<usize>> = vec![Vec::new(); variables.len()];
+        for (index, (_, center, neighbors)) in targets.iter().enumerate() {
+            if let Some(&last) = neighbors.iter().chain(center).max() {
+                checks[last].push(index);
+            }
+        }
+        
+        let mut assignment = vec![false; variables.len()];
+        search_predecessor(&self.rule, &targets, &checks, &mut assignment, 0)
+    }
+    
+    // Map a possibly out-of-range coordinate onto the grid, or None past a fixed edge
+    fn wrap_coordinates(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        match self.boundary {
+            BoundaryType::Wrap => Some((
+                x.rem_euclid(self.width as isize) as usize,
+                y.rem_euclid(self.height as isize) as usize,
+            )),
+            BoundaryType::Fixed | BoundaryType::Absorbing => {
+                let inside = (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y);
+                inside.then_some((x as usize, y as usize))
+            }
+        }
+    }
+    
+    // Get the live neighbor count of every cell in row-major order, using the same
+    // boundary rules as update. Useful for showing where births and deaths are about to happen.
+    pub fn neighbor_counts(&self) -> Vec<u8> {
+        let mut counts = vec![0; self.width * self.height];
+        if self.width == 0 {
+            return counts;
+        }
+        
+        // Sum the eight neighbors straight out of the padded plane, with no bounds or boundary checks
+        let padded = self.ghost_padded(|_, _| 1);
+        let padded_width = self.width + 2;
+        counts.par_chunks_mut(self.width).enumerate().for_each(|(y, row)| {
+            let above = &padded[y * padded_width..(y + 1) * padded_width];
+            let current = &padded[(y + 1) * padded_width..(y + 2) * padded_width];
+            let below = &padded[(y + 2) * padded_width..(y + 3) * padded_width];
+            
+            for (x, count) in row.iter_mut().enumerate() {
+                *count = above[x] + above[x + 1] + above[x + 2]
+                    + current[x] + current[x + 2]
+                    + below[x] + below[x + 1] + below[x + 2];
+            }
+        });
+        
+        counts
+    }
+    
+    // Unpack the grid to one byte per cell inside a one-cell ghost border, so every cell
+    // has eight neighbors in bounds. Live cells hold the value `live` gives them and dead
+    // cells 0. The border is dead on a fixed grid and holds copies of the opposite edges
+    // on a wrapped one.
+    fn ghost_padded(&self, live: impl Fn(usize, usize) -> u8) -> Vec<u8> {
+        let padded_width = self.width + 2;
+        let mut padded = vec![0u8; padded_width * (self.height + 2)];
+        
+        for (x, y) in self.live_cells() {
+            padded[(y + 1) * padded_width + x + 1] = live(x, y);
+        }
+        
+        if matches!(self.boundary, BoundaryType::Wrap) && self.height > 0 {
+            for y in 1..=self.height {
+                let row = y * padded_width;
+                padded[row] = padded[row + self.width];
+                padded[row + self.width + 1] = padded[row + 1];
+            }
+            
+            // Copy whole rows last so the corners pick up the diagonally opposite cells
+            let last_row = self.height * padded_width;
+            padded.copy_within(last_row..last_row + padded_width, 0);
+            padded.copy_within(padded_width..2 * padded_width, last_row + padded_width);
+        }
+        
+        padded
+    }
+    
+    // Update the grid to the next generation.
+    // Works on whole u64 words: the eight neighbor bitboards of a row are summed with
+    // bit-sliced adders, so 64 cells are counted with a handful of logic operations.
     pub fn update(&mut self) {
+        if self.stride == 0 || self.height == 0 {
+            return;
+        }
+        
+        if let Some(automaton) = self.automaton.clone() {
+            self.update_automaton(automaton.as_ref());
+            self.absorb_border();
+            return;
+        }
+        
+        if let Some(range_rule) = self.range_rule {
+            self.update_range(&range_rule);
+            self.absorb_border();
+            return;
+        }
+        
         let mut new_cells = vec![0; self.cells.len()];
         
-        // Use Rayon for parallel processing of rows
         let height = self.height;
         let width = self.width;
         let stride = self.stride;
+        let wrap = matches!(self.boundary, BoundaryType::Wrap);
+        let cells = &self.cells;
+        let empty_row = vec![0u64; stride];
+        let rule = self.rule;
+        let conway = rule.is_conway();
         
-        // Process rows in parallel and collect results into individual vectors
-        let results: Vec<Vec<(usize, u64)>> = (0..height).into_par_iter().map(|y| {
-            let mut row_updates = Vec::new();
-            for x in 0..width {
-                let neighbors = self.count_neighbors(x, y);
-                let is_alive = self.get(x, y);
+        // Mask off the padding bits past the grid width in the last word of each row
+        let last_word_mask = match width % 64 {
+            0 => u64::MAX,
+            bits => (1u64 << bits) - 1,
+        };
+        
+        let update_row = |(y, out): (usize, &mut [u64])| {
+            let row = |ry: usize| &cells[ry * stride..(ry + 1) * stride];
+            
+            let above = if y > 0 {
+                row(y - 1)
+            } else if wrap {
+                row(height - 1)
+            } else {
+                &empty_row
+            };
+            let below = if y + 1 < height {
+                row(y + 1)
+            } else if wrap {
+                row(0)
+            } else {
+                &empty_row
+            };
+            let current = row(y);
+            
+            for (w, out_word) in out.iter_mut().enumerate() {
+                let (a_west, a_center, a_east) = shifted_words(above, w, width, wrap);
+                let (c_west, c_center, c_east) = shifted_words(current, w, width, wrap);
+                let (b_west, b_center, b_east) = shifted_words(below, w, width, wrap);
                 
-                let will_be_alive = match (is_alive, neighbors) {
-                    (true, 2) | (true, 3) => true,
-                    (false, 3) => true,
-                    _ => false,
-                };
+                let neighbors = [a_west, a_center, a_east, c_west, c_east, b_west, b_center, b_east];
                 
-                if will_be_alive {
-                    let bit_index = x % 64;
-                    let chunk_index = (y * stride) + (x / 64);
-                    row_updates.push((chunk_index, 1u64 << bit_index));
+                let mut next = if conway {
+                    // Bit-sliced counter: ones/twos hold the low bits of the count,
+                    // fours is sticky once any cell reaches four neighbors
+                    let (mut ones, mut twos, mut fours) = (0u64, 0u64, 0u64);
+                    for neighbor in neighbors {
+                        let carry = ones & neighbor;
+                        ones ^= neighbor;
+                        fours |= twos & carry;
+                        twos ^= carry;
+                    }
+                    
+                    // Alive next generation with exactly 3 neighbors, or 2 neighbors and alive now
+                    twos & !fours & (ones | c_center)
+                } else {
+                    rule_next_word(&rule, c_center, neighbors)
+                };
+                if w + 1 == stride {
+                    next &= last_word_mask;
                 }
+                *out_word = next;
+            }
+        };
+        
+        // Use Rayon for parallel processing of rows, unless the grid is too small to repay the threads
+        if self.runs_parallel() {
+            new_cells.par_chunks_mut(stride).enumerate().for_each(update_row);
+        } else {
+            new_cells.chunks_mut(stride).enumerate().for_each(update_row);
+        }
+        
+        self.cells = new_cells;
+        self.absorb_border();
+    }
+    
+    // Whether update is big enough to be worth spreading across the thread pool
+    fn runs_parallel(&self) -> bool {
+        self.width * self.height >= self.parallel_threshold
+    }
+    
+    // Get the cell count from which update runs rows in parallel
+    pub fn parallel_threshold(&self) -> usize {
+        self.parallel_threshold
+    }
+    
+    // Set the cell count from which update runs rows in parallel; smaller grids update on
+    // the calling thread. 0 always runs in parallel and usize::MAX never does.
+    pub fn set_parallel_threshold(&mut self, cells: usize) {
+        self.parallel_threshold = cells;
+    }
+    
+    // Update the grid under a range rule. Neighborhood sums come from a summed-area table
+    // over the grid padded by the radius on every side, so each cell costs four lookups
+    // whatever the radius.
+    fn update_range(&mut self, rule: &RangeRule) {
+        let (width, height, stride) = (self.width, self.height, self.stride);
+        let radius = rule.radius();
+        let wrap = matches!(self.boundary, BoundaryType::Wrap);
+        
+        // Grid coordinate of a padded offset, or None past a fixed edge
+        let source = |padded: usize, length: usize| -> Option<usize> {
+            let coordinate = padded as isize - radius as isize;
+            if wrap {
+                Some(coordinate.rem_euclid(length as isize) as usize)
+            } else {
+                (0..length as isize).contains(&coordinate).then_some(coordinate as usize)
+            }
+        };
+        
+        // table[(py + 1) * table_width + px + 1] holds the live cells above and left of (px, py), inclusive
+        let (padded_width, padded_height) = (width + 2 * radius, height + 2 * radius);
+        let table_width = padded_width + 1;
+        let mut table = vec![0u32; table_width * (padded_height + 1)];
+        let columns: Vec<Option<usize>> = (0..padded_width).map(|px| source(px, width)).collect();
+        for py in 0..padded_height {
+            let y = source(py, height);
+            let mut row_sum = 0;
+            for (px, &x) in columns.iter().enumerate() {
+                if let (Some(x), Some(y)) = (x, y) {
+                    row_sum += self.get(x, y) as u32;
+                }
+                table[(py + 1) * table_width + px + 1] = table[py * table_width + px + 1] + row_sum;
+            }
+        }
+        
+        let side = 2 * radius + 1;
+        let box_sum = |x: usize, y: usize| -> usize {
+            let (top, bottom) = (y * table_width, (y + side) * table_width);
+            (table[bottom + x + side] + table[top + x] - table[top + x + side] - table[bottom + x]) as usize
+        };
+        
+        let cells = &self.cells;
+        let mut new_cells = vec![0u64; cells.len()];
+        let update_row = |(y, out): (usize, &mut [u64])| {
+            for x in 0..width {
+                let alive = cells[y * stride + x / 64] & (1u64 << (x % 64)) != 0;
+                let neighbors = box_sum(x, y) - alive as usize;
+                if rule.next_state(alive, neighbors) {
+                    out[x / 64] |= 1u64 << (x % 64);
+                }
+            }
+        };
+        if self.runs_parallel() {
+            new_cells.par_chunks_mut(stride).enumerate().for_each(update_row);
+        } else {
+            new_cells.chunks_mut(stride).enumerate().for_each(update_row);
+        }
+        
+        self.cells = new_cells;
+    }
+    
+    // Update the grid by asking the automaton for the next state of every cell,
+    // one byte per cell, then pack the states back into the cell and state planes
+    fn update_automaton(&mut self, automaton: &dyn Automaton) {
+        let width = self.width;
+        let padded_width = width + 2;
+        let padded = self.ghost_padded(|x, y| self.get_state(x, y));
+        
+        let mut states = vec![0u8; width * self.height];
+        let update_row = |(y, row): (usize, &mut [u8])| {
+            let above = &padded[y * padded_width..(y + 1) * padded_width];
+            let current = &padded[(y + 1) * padded_width..(y + 2) * padded_width];
+            let below = &padded[(y + 2) * padded_width..(y + 3) * padded_width];
+            
+            for (x, state) in row.iter_mut().enumerate() {
+                let neighbors = [
+                    above[x], above[x + 1], above[x + 2],
+                    current[x], current[x + 2],
+                    below[x], below[x + 1], below[x + 2],
+                ];
+                *state = automaton.next_state(current[x + 1], &neighbors);
             }
-            row_updates
-        }).collect();
+        };
+        if self.runs_parallel() {
+            states.par_chunks_mut(width).enumerate().for_each(update_row);
+        } else {
+            states.chunks_mut(width).enumerate().for_each(update_row);
+        }
         
-        // Apply all updates to the new_cells vector
-        for row_updates in results {
-            for (chunk_index, bit_mask) in row_updates {
-                new_cells[chunk_index] |= bit_mask;
+        self.cells.fill(0);
+        for plane in &mut self.state_planes {
+            plane.fill(0);
+        }
+        for (index, &state) in states.iter().enumerate() {
+            if state != 0 {
+                self.set_state(index % width, index / width, state);
+            }
+        }
+    }
+    
+    // Update the grid one cell at a time from the per-cell neighbor counts.
+    // Much slower than update, but kept as the reference the bit-parallel path is tested against.
+    // Range rules count each cell's neighborhood directly.
+    pub fn update_per_cell(&mut self) {
+        let counts = self.neighbor_counts();
+        let mut new_cells = vec![0; self.cells.len()];
+        
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let is_alive = self.get(x, y);
+                let next = match &self.range_rule {
+                    Some(rule) => rule.next_state(is_alive, self.count_neighbors(x, y)),
+                    None => self.rule.next_state(is_alive, counts[y * self.width + x]),
+                };
+                
+                if next {
+                    new_cells[(y * self.stride) + (x / 64)] |= 1u64 << (x % 64);
+                }
             }
         }
         
         self.cells = new_cells;
+        self.absorb_border();
+    }
+    
+    // On an absorbing boundary, kill every cell on the outermost ring so nothing
+    // can pile up against the edges
+    fn absorb_border(&mut self) {
+        if !matches!(self.boundary, BoundaryType::Absorbing) || self.width == 0 || self.height == 0 {
+            return;
+        }
+        
+        let stride = self.stride;
+        let last_row = (self.height - 1) * stride;
+        self.cells[..stride].fill(0);
+        self.cells[last_row..].fill(0);
+        
+        let last_x = self.width - 1;
+        for row in self.cells.chunks_mut(stride) {
+            row[0] &= !1;
+            row[last_x / 64] &= !(1u64 << (last_x % 64));
+        }
     }
     
     // Clear all cells (set to dead)
@@ -168,13 +725,23 @@ impl Grid {
         for cell in &mut self.cells {
             *cell = 0;
         }
+        for plane in &mut self.state_planes {
+            plane.fill(0);
+        }
     }
     
     // Randomize the grid with a given density
     pub fn randomize(&mut self, density: f64) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        
+        self.randomize_with_rng(density, &mut rand::thread_rng());
+    }
+    
+    // Randomize the grid from a seed; the same seed always gives the same board
+    pub fn randomize_seeded(&mut self, density: f64, seed: u64) {
+        self.randomize_with_rng(density, &mut StdRng::seed_from_u64(seed));
+    }
+    
+    // Randomize the grid using the supplied RNG, so callers can reproduce a board
+    pub fn randomize_with_rng<R: Rng>(&mut self, density: f64, rng: &mut R) {
         for y in 0..self.height {
             for x in 0..self.width {
                 let alive = rng.gen_bool(density);
@@ -183,6 +750,91 @@ impl Grid {
         }
     }
     
+    // Randomize the grid with a density that changes evenly from the left column to the right one
+    pub fn randomize_gradient(&mut self, left_density: f64, right_density: f64) {
+        self.randomize_gradient_with_rng(left_density, right_density, &mut rand::thread_rng());
+    }
+    
+    // Randomize the grid with a left-to-right density gradient from a seed
+    pub fn randomize_gradient_seeded(&mut self, left_density: f64, right_density: f64, seed: u64) {
+        self.randomize_gradient_with_rng(left_density, right_density, &mut StdRng::seed_from_u64(seed));
+    }
+    
+    // Randomize the grid with a left-to-right density gradient using the supplied RNG. Densities
+    // outside 0.0 to 1.0 are clamped.
+    pub fn randomize_gradient_with_rng<R: Rng>(&mut self, left_density: f64, right_density: f64, rng: &mut R) {
+        let span = self.width.saturating_sub(1).max(1) as f64;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let density = left_density + (right_density - left_density) * x as f64 / span;
+                let alive = rng.gen_bool(density.clamp(0.0, 1.0));
+                self.set(x, y, alive);
+            }
+        }
+    }
+    
+    // Randomize the grid so that it has the given symmetry
+    pub fn randomize_symmetric(&mut self, density: f64, symmetry: Symmetry) {
+        self.randomize_symmetric_with_rng(density, symmetry, &mut rand::thread_rng());
+    }
+    
+    // Randomize the grid symmetrically from a seed
+    pub fn randomize_symmetric_seeded(&mut self, density: f64, symmetry: Symmetry, seed: u64) {
+        self.randomize_symmetric_with_rng(density, symmetry, &mut StdRng::seed_from_u64(seed));
+    }
+    
+    // Randomize the grid symmetrically using the supplied RNG. Each cell is drawn once for its
+    // whole set of mirror images, so the first image in row-major order decides the rest.
+    pub fn randomize_symmetric_with_rng<R: Rng>(&mut self, density: f64, symmetry: Symmetry, rng: &mut R) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let images = self.symmetric_images(x, y, symmetry);
+                if images.iter().any(|&(ix, iy)| (iy, ix) < (y, x)) {
+                    continue;
+                }
+                
+                let alive = rng.gen_bool(density);
+                for (ix, iy) in images {
+                    self.set(ix, iy, alive);
+                }
+            }
+        }
+    }
+    
+    // Every cell that must match (x, y) under the given symmetry, including (x, y) itself.
+    // On non-square grids a quarter turn can leave the board, in which case only the
+    // half-turn image is kept.
+    fn symmetric_images(&self, x: usize, y: usize, symmetry: Symmetry) -> Vec<(usize, usize)> {
+        let mirror_x = self.width - 1 - x;
+        let mirror_y = self.height - 1 - y;
+        
+        match symmetry {
+            Symmetry::Horizontal => vec![(x, y), (mirror_x, y)],
+            Symmetry::Vertical => vec![(x, y), (x, mirror_y)],
+            Symmetry::Both => vec![(x, y), (mirror_x, y), (x, mirror_y), (mirror_x, mirror_y)],
+            Symmetry::Rotational4 => {
+                let mut images = vec![(x, y), (mirror_x, mirror_y)];
+                
+                // Rotate about the center using doubled coordinates to keep half-cells exact
+                let dx = 2 * x as isize - (self.width as isize - 1);
+                let dy = 2 * y as isize - (self.height as isize - 1);
+                let quarter_x = self.width as isize - 1 - dy;
+                let quarter_y = self.height as isize - 1 + dx;
+                
+                let fits = quarter_x % 2 == 0 && quarter_y % 2 == 0 &&
+                    (0..2 * self.width as isize).contains(&quarter_x) &&
+                    (0..2 * self.height as isize).contains(&quarter_y);
+                if fits {
+                    let (qx, qy) = ((quarter_x / 2) as usize, (quarter_y / 2) as usize);
+                    images.push((qx, qy));
+                    images.push((self.width - 1 - qx, self.height - 1 - qy));
+                }
+                
+                images
+            },
+        }
+    }
+    
     // Place a glider at a given position
     pub fn place_glider(&mut self, x: usize, y: usize) {
         if x + 2 >= self.width || y + 2 >= self.height {
@@ -206,13 +858,20 @@ impl Grid {
     
     // Place a random pattern at a given position
     pub fn place_random_pattern(&mut self, x: usize, y: usize) {
+        self.place_random_pattern_with_rng(x, y, &mut rand::thread_rng());
+    }
+    
+    // Place a random pattern at a given position from a seed
+    pub fn place_random_pattern_seeded(&mut self, x: usize, y: usize, seed: u64) {
+        self.place_random_pattern_with_rng(x, y, &mut StdRng::seed_from_u64(seed));
+    }
+    
+    // Place a random pattern at a given position using the supplied RNG
+    pub fn place_random_pattern_with_rng<R: Rng>(&mut self, x: usize, y: usize, rng: &mut R) {
         if x + 3 >= self.width || y + 3 >= self.height {
             return;
         }
         
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        
         for dy in 0..4 {
             for dx in 0..4 {
                 let alive = rng.gen_bool(0.4);
@@ -221,11 +880,76 @@ impl Grid {
         }
     }
     
+    // The rule update applies
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+    
+    // Change the rule applied by later updates; the cells are left as they are
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+    
+    // The range rule update applies in place of the rule, if any
+    pub fn range_rule(&self) -> Option<&RangeRule> {
+        self.range_rule.as_ref()
+    }
+    
+    // Count neighbors over a larger radius with a range rule, or go back to the life-like
+    // rule and its eight neighbors with None
+    pub fn set_range_rule(&mut self, range_rule: Option<RangeRule>) {
+        self.range_rule = range_rule;
+    }
+    
+    // How far count_neighbors looks from a cell in each direction
+    pub fn radius(&self) -> usize {
+        self.range_rule.map_or(1, |rule| rule.radius())
+    }
+    
+    // The active rule in its usual notation, "B3/S23" or Larger than Life for a range rule
+    pub fn rule_notation(&self) -> String {
+        match &self.range_rule {
+            Some(rule) => rule.to_string(),
+            None => self.rule.to_string(),
+        }
+    }
+    
+    // Whether the live cell at (x, y) stays alive in the next generation under the
+    // active rule. Automata are not consulted.
+    pub fn survives(&self, x: usize, y: usize) -> bool {
+        let neighbors = self.count_neighbors(x, y);
+        match &self.range_rule {
+            Some(rule) => rule.survives(neighbors),
+            None => self.rule.survives(neighbors as u8),
+        }
+    }
+    
+    // The automaton update applies in place of the rule, if any
+    pub fn automaton(&self) -> Option<&dyn Automaton> {
+        self.automaton.as_deref()
+    }
+    
+    // Hand updates to an automaton, or back to the rule with None. Adds enough state planes
+    // for the automaton's states; every live cell starts in state 1.
+    pub fn set_automaton(&mut self, automaton: Option<Arc<dyn Automaton>>) {
+        // Live states 1..states need enough bits to count up to states - 2
+        let highest = automaton.as_ref().map_or(0, |automaton| automaton.states().saturating_sub(2));
+        let planes = (u8::BITS - highest.leading_zeros()) as usize;
+        
+        self.state_planes = vec![vec![0; self.cells.len()]; planes];
+        self.automaton = automaton;
+    }
+    
     // Get grid dimensions
     pub fn dimensions(&self) -> (usize, usize) {
         (self.width, self.height)
     }
     
+    // Get the boundary condition
+    pub fn boundary(&self) -> &BoundaryType {
+        &self.boundary
+    }
+    
     // Count total number of live cells
     pub fn count_alive(&self) -> usize {
         self.cells.iter()
@@ -233,6 +957,303 @@ impl Grid {
             .sum()
     }
     
+    // Count the live cells in each row, top to bottom
+    pub fn row_densities(&self) -> Vec<usize> {
+        if self.stride == 0 {
+            return vec![0; self.height];
+        }
+        
+        self.cells.chunks(self.stride)
+            .map(|row| row.iter().map(|&chunk| chunk.count_ones() as usize).sum())
+            .collect()
+    }
+    
+    // Count the live cells in each column, left to right
+    pub fn column_densities(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.width];
+        for (x, _) in self.live_cells() {
+            counts[x] += 1;
+        }
+        counts
+    }
+    
+    // Count the live cells of row y in columns start..end, masking whole words instead of
+    // reading the cells one at a time
+    pub fn count_alive_in_row(&self, y: usize, start: usize, end: usize) -> usize {
+        let end = end.min(self.width);
+        if y >= self.height || start >= end {
+            return 0;
+        }
+        
+        let row = &self.cells[y * self.stride..(y + 1) * self.stride];
+        let (first, last) = (start / 64, (end - 1) / 64);
+        (first..=last)
+            .map(|word| {
+                let low = if word == first { start % 64 } else { 0 };
+                let high = if word == last { (end - 1) % 64 + 1 } else { 64 };
+                let mask = (u64::MAX >> (64 - (high - low))) << low;
+                (row[word] & mask).count_ones() as usize
+            })
+            .sum()
+    }
+    
+    // Get the tight extent of live cells as (min_x, min_y, max_x, max_y), or None when empty
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.stats().bounding_box
+    }
+    
+    // Gather population, density and extent in a single scan of the cell words
+    pub fn stats(&self) -> GridStats {
+        let mut population = 0;
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        
+        for y in 0..self.height {
+            let row = &self.cells[y * self.stride..(y + 1) * self.stride];
+            population += row.iter().map(|&word| word.count_ones() as usize).sum::<usize>();
+            
+            let first = row.iter().position(|&word| word != 0);
+            let last = row.iter().rposition(|&word| word != 0);
+            
+            if let (Some(first), Some(last)) = (first, last) {
+                let row_min_x = first * 64 + row[first].trailing_zeros() as usize;
+                let row_max_x = last * 64 + 63 - row[last].leading_zeros() as usize;
+                
+                bounds = Some(match bounds {
+                    Some((min_x, min_y, max_x, _)) => (min_x.min(row_min_x), min_y, max_x.max(row_max_x), y),
+                    None => (row_min_x, y, row_max_x, y),
+                });
+            }
+        }
+        
+        let total = self.width * self.height;
+        GridStats {
+            population,
+            density: if total == 0 { 0.0 } else { population as f64 / total as f64 },
+            bounding_box: bounds,
+            occupied_area: bounds.map_or(0, |(min_x, min_y, max_x, max_y)| (max_x - min_x + 1) * (max_y - min_y + 1)),
+        }
+    }
+    
+    // Iterate the coordinates of live cells in row order, skipping empty words entirely
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        set_bits(&self.cells, self.stride)
+    }
+    
+    // Advance one generation and list every cell that was born or died as (x, y, alive),
+    // in row order, so a display can repaint just those cells. Multi-state cells that stay
+    // alive while changing state are not listed.
+    pub fn update_returning_changes(&mut self) -> Vec<(usize, usize, bool)> {
+        let previous = self.cells.clone();
+        self.update();
+        
+        let flipped: Vec<u64> = previous.iter().zip(&self.cells).map(|(&before, &after)| before ^ after).collect();
+        set_bits(&flipped, self.stride)
+            .map(|(x, y)| (x, y, self.get(x, y)))
+            .collect()
+    }
+    
+    // Advance until the grid returns to a state it was in before, so still lifes and oscillators
+    // both count as settled, or until max_generations have run. The grid is left one period
+    // past the start of its cycle.
+    pub fn run_until_stable(&mut self, max_generations: usize) -> StabilizationResult {
+        let mut detector = CycleDetector::new(self);
+        for _ in 0..max_generations {
+            self.update();
+            if let Some(result) = detector.observe(self) {
+                return result;
+            }
+        }
+        
+        StabilizationResult { generations: max_generations, period: None }
+    }
+    
+    // Hash the grid state from the raw cell words, for fast cycle detection
+    pub fn hash_state(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    // State bits of the live cells in each plane. Dead cells may keep stale bits, which never count.
+    fn live_state_words(&self) -> impl Iterator<Item = u64> + '_ {
+        self.state_planes.iter()
+            .flat_map(|plane| plane.iter().zip(&self.cells).map(|(&bits, &alive)| bits & alive))
+    }
+    
+    // Get a grid with the cells alive in either grid, e.g. to overlay a pattern without
+    // clearing what is already around it. The result keeps this grid's boundary, rule and
+    // automaton, with every live cell in state 1.
+    pub fn union(&self, other: &Grid) -> Result<Grid, GridError> {
+        self.combine(other, |a, b| a | b)
+    }
+    
+    // Get a grid with the cells alive in both grids
+    pub fn intersection(&self, other: &Grid) -> Result<Grid, GridError> {
+        self.combine(other, |a, b| a & b)
+    }
+    
+    // Get a grid with the cells alive in this grid but not the other, e.g. what died between two states
+    pub fn difference(&self, other: &Grid) -> Result<Grid, GridError> {
+        self.combine(other, |a, b| a & !b)
+    }
+    
+    // Combine two grids of the same size a word at a time. Padding bits are dead in both
+    // grids and every operation keeps two dead bits dead, so the padding stays clear.
+    fn combine(&self, other: &Grid, op: impl Fn(u64, u64) -> u64) -> Result<Grid, GridError> {
+        if self.dimensions() != other.dimensions() {
+            return Err(GridError::DimensionMismatch {
+                expected: self.dimensions(),
+                found: other.dimensions(),
+            });
+        }
+        
+        Ok(Grid {
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            cells: self.cells.iter().zip(&other.cells).map(|(&a, &b)| op(a, b)).collect(),
+            state_planes: vec![vec![0; self.cells.len()]; self.state_planes.len()],
+            boundary: self.boundary.clone(),
+            rule: self.rule,
+            range_rule: self.range_rule,
+            automaton: self.automaton.clone(),
+            parallel_threshold: self.parallel_threshold,
+        })
+    }
+    
+    // Get a minimal grid containing just the live cells, with no empty margin
+    pub fn trim(&self) -> Grid {
+        let Some((min_x, min_y, max_x, max_y)) = self.bounding_box() else {
+            return Grid::new(0, 0, self.boundary.clone());
+        };
+        
+        let mut trimmed = Grid::new(max_x - min_x + 1, max_y - min_y + 1, self.boundary.clone());
+        trimmed.set_rule(self.rule);
+        trimmed.set_range_rule(self.range_rule);
+        trimmed.set_automaton(self.automaton.clone());
+        trimmed.set_parallel_threshold(self.parallel_threshold);
+        for (x, y) in self.live_cells() {
+            trimmed.set_state(x - min_x, y - min_y, self.get_state(x, y));
+        }
+        
+        trimmed
+    }
+    
+    // List the live cells as plain text, one "x y" pair per line in row order,
+    // for scripts that find RLE or the binary format awkward
+    pub fn to_coordinate_list(&self) -> String {
+        self.live_cells().map(|(x, y)| format!("{} {}\n", x, y)).collect()
+    }
+    
+    // Build a grid from a coordinate list like the one to_coordinate_list writes.
+    // Blank lines and anything after a '#' are ignored.
+    pub fn from_coordinate_list(
+        text: &str,
+        width: usize,
+        height: usize,
+        boundary: BoundaryType,
+    ) -> Result<Grid, GridError> {
+        let mut grid = Grid::new(width, height, boundary);
+        
+        for (index, line) in text.lines().enumerate() {
+            let content = line.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            
+            let invalid = || GridError::InvalidCoordinates { line: index + 1, text: content.to_string() };
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let [x, y] = fields[..] else {
+                return Err(invalid());
+            };
+            let (x, y) = (x.parse().map_err(|_| invalid())?, y.parse().map_err(|_| invalid())?);
+            
+            if x >= width || y >= height {
+                return Err(GridError::CoordinatesOutOfBounds { line: index + 1, x, y });
+            }
+            grid.set(x, y, true);
+        }
+        
+        Ok(grid)
+    }
+    
+    // Build a grid from rows of ASCII art, '#' for a live cell and anything else dead.
+    // The grid is as wide as the longest row; shorter rows are padded with dead cells.
+    pub fn from_ascii(rows: &[&str], boundary: BoundaryType) -> Grid {
+        let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+        let mut grid = Grid::new(width, rows.len(), boundary);
+        
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    grid.set(x, y, true);
+                }
+            }
+        }
+        
+        grid
+    }
+    
+    // Draw the grid as rows of ASCII art in the form from_ascii reads
+    pub fn to_ascii(&self) -> Vec<String> {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| if self.get(x, y) { '#' } else { '.' }).collect())
+            .collect()
+    }
+    
+    // Build a grid from a Life 1.06 file: a "#Life 1.06" header followed by "x y" pairs
+    // that may be negative. The pattern is centered on the grid, and cells that still fall
+    // outside it are clipped. Returns the grid and the number of cells dropped.
+    pub fn from_life106(
+        text: &str,
+        width: usize,
+        height: usize,
+        boundary: BoundaryType,
+    ) -> Result<(Grid, usize), GridError> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let header = lines.next().map(|(_, line)| line.trim());
+        if header != Some(LIFE_106_HEADER) {
+            return Err(GridError::MissingHeader { expected: LIFE_106_HEADER });
+        }
+        
+        let mut cells = Vec::new();
+        for (index, line) in lines {
+            let content = line.trim();
+            if content.starts_with('#') {
+                continue;
+            }
+            
+            let invalid = || GridError::InvalidCoordinates { line: index + 1, text: content.to_string() };
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let [x, y] = fields[..] else {
+                return Err(invalid());
+            };
+            cells.push((x.parse::<i64>().map_err(|_| invalid())?, y.parse::<i64>().map_err(|_| invalid())?));
+        }
+        
+        let mut grid = Grid::new(width, height, boundary);
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let max_x = cells.iter().map(|&(x, _)| x).max().unwrap_or(0);
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let max_y = cells.iter().map(|&(_, y)| y).max().unwrap_or(0);
+        
+        // Shift the pattern's bounding box to the middle of the grid
+        let offset_x = (width as i64 - (max_x - min_x + 1)) / 2 - min_x;
+        let offset_y = (height as i64 - (max_y - min_y + 1)) / 2 - min_y;
+        
+        let mut dropped = 0;
+        for (x, y) in cells {
+            let (x, y) = (x + offset_x, y + offset_y);
+            if x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+                dropped += 1;
+                continue;
+            }
+            grid.set(x as usize, y as usize, true);
+        }
+        
+        Ok((grid, dropped))
+    }
+    
     // Save grid state to a file
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
         let mut file = OpenOptions::new()
@@ -279,7 +1300,202 @@ impl Grid {
             file.read_exact(&mut buffer)?;
             *cell = u64::from_le_bytes(buffer);
         }
+        for plane in &mut self.state_planes {
+            plane.fill(0);
+        }
         
         Ok(())
     }
-}
\ No newline at end of file
+    
+    // Create a grid from an image, scaled to the given dimensions. Pixels darker than the
+    // threshold (0-255 luminance) become live cells; transparent pixels stay dead.
+    #[cfg(feature = "images")]
+    pub fn from_image<P: AsRef<Path>>(
+        path: P,
+        width: usize,
+        height: usize,
+        boundary: BoundaryType,
+        threshold: u8,
+    ) -> image::ImageResult<Grid> {
+        let image = image::open(path)?.to_luma_alpha8();
+        let scaled = image::imageops::resize(&image, width as u32, height as u32, FilterType::Triangle);
+        
+        let mut grid = Grid::new(width, height, boundary);
+        for (x, y, pixel) in scaled.enumerate_pixels() {
+            let [luma, alpha] = pixel.0;
+            if alpha >= 128 && luma < threshold {
+                grid.set(x as usize, y as usize, true);
+            }
+        }
+        
+        Ok(grid)
+    }
+    
+    // Render the whole grid to a PNG, each cell a square of `cell_size` pixels in the alive
+    // or dead color. A plain still of one generation, without the visualizer's age coloring.
+    #[cfg(feature = "images")]
+    pub fn save_png<P: AsRef<Path>>(
+        &self,
+        path: P,
+        cell_size: u32,
+        alive_color: [u8; 3],
+        dead_color: [u8; 3],
+    ) -> image::ImageResult<()> {
+        let cell_size = cell_size.max(1);
+        let image = image::RgbImage::from_fn(
+            self.width as u32 * cell_size,
+            self.height as u32 * cell_size,
+            |px, py| {
+                let alive = self.get((px / cell_size) as usize, (py / cell_size) as usize);
+                image::Rgb(if alive { alive_color } else { dead_color })
+            },
+        );
+        image.save(path)
+    }
+}
+
+// Grids are equal when they have the same size and the same cells alive, in the same states.
+// Only the cells count: the boundary, rule, automaton and threading settings are left out,
+// so a pattern compares equal to itself on a differently configured grid. Grids whose
+// automata have different numbers of states are never equal.
+impl PartialEq for Grid {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.cells == other.cells
+            && self.state_planes.len() == other.state_planes.len()
+            && self.live_state_words().eq(other.live_state_words())
+    }
+}
+
+impl Eq for Grid {}
+
+// Hashes exactly what equality compares
+impl Hash for Grid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.cells.hash(state);
+        self.state_planes.len().hash(state);
+        for bits in self.live_state_words() {
+            bits.hash(state);
+        }
+    }
+}
+
+// First line of every Life 1.06 file
+const LIFE_106_HEADER: &str = "#Life 1.06";
+
+// Side length of the windows checked by has_orphan_pattern
+const ORPHAN_WINDOW: usize = 4;
+
+// A cell of the target state: whether it should be alive, its own predecessor variable
+// and the variables of its neighbors
+type OrphanTarget = (bool, Option<usize>, Vec<usize>);
+
+// Depth-first search over predecessor assignments, pruning as soon as a fully determined
+// target cell would come out wrong
+fn search_predecessor(rule: &Rule, targets: &[OrphanTarget], checks: &[Vec<usize>], assignment: &mut [bool], next: usize) -> bool {
+    if next == assignment.len() {
+        return true;
+    }
+    
+    for value in [false, true] {
+        assignment[next] = value;
+        
+        let consistent = checks[next].iter().all(|&index| {
+            let (alive, center, neighbors) = &targets[index];
+            let was_alive = center.is_some_and(|variable| assignment[variable]);
+            let count = neighbors.iter().filter(|&&variable| assignment[variable]).count();
+            rule.next_state(was_alive, count as u8) == *alive
+        });
+        
+        if consistent && search_predecessor(rule, targets, checks, assignment, next + 1) {
+            return true;
+        }
+    }
+    
+    false
+}
+
+// Apply any life-like rule to 64 cells at once. The neighbor words are summed into a full
+// 4-bit bit-sliced count, then compared against each neighbor count the rule responds to.
+fn rule_next_word(rule: &Rule, alive: u64, neighbors: [u64; 8]) -> u64 {
+    let mut bits = [0u64; 4];
+    for neighbor in neighbors {
+        let mut carry = neighbor;
+        for bit in &mut bits {
+            let next_carry = *bit & carry;
+            *bit ^= carry;
+            carry = next_carry;
+        }
+    }
+    
+    let mut next = 0;
+    for count in 0..=8u8 {
+        let (born, survives) = (rule.births(count), rule.survives(count));
+        if !born && !survives {
+            continue;
+        }
+        
+        let matches_count = bits.iter().enumerate().fold(u64::MAX, |mask, (i, &bit)| {
+            mask & if count >> i & 1 == 1 { bit } else { !bit }
+        });
+        
+        if born {
+            next |= matches_count & !alive;
+        }
+        if survives {
+            next |= matches_count & alive;
+        }
+    }
+    
+    next
+}
+
+// Iterate the coordinates of the set bits in row-major bit-packed words,
+// skipping empty words entirely
+fn set_bits(words: &[u64], stride: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    words.iter()
+        .enumerate()
+        .filter(|&(_, &word)| word != 0)
+        .flat_map(move |(index, &word)| {
+            let (y, w) = (index / stride, index % stride);
+            let mut bits = word;
+            
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some((w * 64 + bit, y))
+            })
+        })
+}
+
+// Get a row word together with its west (x - 1) and east (x + 1) shifted neighbors,
+// pulling the carried bits in from the adjacent words or across the wrapped edge
+fn shifted_words(row: &[u64], w: usize, width: usize, wrap: bool) -> (u64, u64, u64) {
+    let stride = row.len();
+    let center = row[w];
+    let last_bit = (width - 1) % 64;
+    
+    let west_carry = if w > 0 {
+        row[w - 1] >> 63
+    } else if wrap {
+        (row[stride - 1] >> last_bit) & 1
+    } else {
+        0
+    };
+    
+    let east_carry = if w + 1 < stride {
+        row[w + 1] << 63
+    } else if wrap {
+        (row[0] & 1) << last_bit
+    } else {
+        0
+    };
+    
+    ((center << 1) | west_carry, center, (center >> 1) | east_carry)
+}
@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use crate::grid::Grid;
+    use crate::grid::{Grid, GridError, StabilizationResult, Symmetry};
     use crate::config::BoundaryType;
+    use crate::rule::{RangeRule, Rule};
 
     #[test]
     fn test_new_grid() {
@@ -103,35 +104,723 @@ mod tests {
 
     #[test]
     fn test_update_rules() {
-        let mut grid = Grid::new(10, 10, BoundaryType::Wrap);
+        let mut grid = Grid::from_ascii(&[
+            ".....",
+            ".....",
+            ".###.",
+            ".....",
+            ".....",
+        ], BoundaryType::Wrap);
         
-        // Set up a blinker pattern:
-        // 0 0 0
-        // 1 1 1
-        // 0 0 0
-        grid.set(1, 1, true);
-        grid.set(2, 1, true);
-        grid.set(3, 1, true);
+        // A horizontal blinker turns vertical
+        grid.update();
+        assert_grid_eq!(grid, [
+            ".....",
+            "..#..",
+            "..#..",
+            "..#..",
+            ".....",
+        ]);
         
-        // After one update, it should transform to:
-        // 0 1 0
-        // 0 1 0
-        // 0 1 0
+        // And back to horizontal
         grid.update();
+        assert_grid_eq!(grid, [
+            ".....",
+            ".....",
+            ".###.",
+            ".....",
+            ".....",
+        ]);
+    }
+
+    #[test]
+    fn test_randomize_seeded_is_reproducible() {
+        let mut first = Grid::new(64, 32, BoundaryType::Wrap);
+        let mut second = Grid::new(64, 32, BoundaryType::Wrap);
         
-        assert!(!grid.get(1, 1));
-        assert!(grid.get(2, 0));
-        assert!(grid.get(2, 1));
-        assert!(grid.get(2, 2));
-        assert!(!grid.get(3, 1));
+        first.randomize_seeded(0.3, 42);
+        second.randomize_seeded(0.3, 42);
         
-        // After another update, it should go back to horizontal
+        for y in 0..32 {
+            for x in 0..64 {
+                assert_eq!(first.get(x, y), second.get(x, y));
+            }
+        }
+        
+        // A different seed should give a different board
+        second.randomize_seeded(0.3, 43);
+        assert!((0..32).any(|y| (0..64).any(|x| first.get(x, y) != second.get(x, y))));
+    }
+
+    #[test]
+    fn test_bit_parallel_update_matches_per_cell() {
+        let sizes = [(1, 1), (2, 3), (5, 7), (63, 10), (64, 9), (65, 12), (130, 20)];
+        
+        for (seed, &(width, height)) in sizes.iter().enumerate() {
+            for boundary in [BoundaryType::Wrap, BoundaryType::Fixed] {
+                let mut fast = Grid::new(width, height, boundary.clone());
+                let mut reference = Grid::new(width, height, boundary);
+                fast.randomize_seeded(0.4, seed as u64);
+                reference.randomize_seeded(0.4, seed as u64);
+                
+                for _ in 0..8 {
+                    fast.update();
+                    reference.update_per_cell();
+                    
+                    for y in 0..height {
+                        for x in 0..width {
+                            assert_eq!(fast.get(x, y), reference.get(x, y),
+                                "mismatch at ({}, {}) on a {}x{} grid", x, y, width, height);
+                        }
+                    }
+                    assert_eq!(fast.count_alive(), reference.count_alive());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_rules_match_per_cell() {
+        for text in ["B36/S23", "B2/S", "B1357/S1357", "B0/S8", "B012345678/S012345678"] {
+            let rule = Rule::parse(text).unwrap();
+            
+            for boundary in [BoundaryType::Wrap, BoundaryType::Fixed] {
+                let mut fast = Grid::new(70, 12, boundary.clone());
+                fast.set_rule(rule);
+                fast.randomize_seeded(0.3, 7);
+                let mut reference = fast.clone();
+                
+                for generation in 0..6 {
+                    fast.update();
+                    reference.update_per_cell();
+                    assert!(fast.difference(&reference).unwrap().count_alive() == 0
+                        && reference.difference(&fast).unwrap().count_alive() == 0,
+                        "{} diverged at generation {}", text, generation);
+                }
+            }
+        }
+        
+        // Seeds (B2/S) kills every live cell each generation
+        let mut grid = Grid::new(10, 10, BoundaryType::Fixed);
+        grid.set_rule(Rule::parse("B2/S").unwrap());
+        grid.set(4, 4, true);
+        grid.set(5, 4, true);
         grid.update();
+        assert!(!grid.get(4, 4) && !grid.get(5, 4));
+        assert_eq!(grid.count_alive(), 4);
+        assert_eq!(grid.rule().to_string(), "B2/S");
+    }
+
+    #[test]
+    fn test_bounding_box_and_trim() {
+        let mut grid = Grid::new(100, 10, BoundaryType::Wrap);
+        assert_eq!(grid.bounding_box(), None);
+        assert_eq!(grid.trim().dimensions(), (0, 0));
         
-        assert!(grid.get(1, 1));
-        assert!(grid.get(2, 1));
-        assert!(grid.get(3, 1));
+        // Cells spanning a word boundary
+        grid.set(60, 2, true);
+        grid.set(70, 5, true);
+        grid.set(62, 7, true);
+        assert_eq!(grid.bounding_box(), Some((60, 2, 70, 7)));
+        
+        let trimmed = grid.trim();
+        assert_eq!(trimmed.dimensions(), (11, 6));
+        assert!(trimmed.get(0, 0));
+        assert!(trimmed.get(10, 3));
+        assert!(trimmed.get(2, 5));
+        assert_eq!(trimmed.count_alive(), 3);
+    }
+
+    #[test]
+    fn test_stats_summarize_grid() {
+        let mut grid = Grid::new(100, 10, BoundaryType::Wrap);
+        let empty = grid.stats();
+        assert_eq!((empty.population, empty.bounding_box, empty.occupied_area), (0, None, 0));
+        assert_eq!(empty.density, 0.0);
+        
+        grid.set(60, 2, true);
+        grid.set(70, 5, true);
+        grid.set(62, 7, true);
+        grid.set(65, 4, true);
+        let stats = grid.stats();
+        assert_eq!(stats.population, 4);
+        assert_eq!(stats.density, 0.004);
+        assert_eq!(stats.bounding_box, Some((60, 2, 70, 7)));
+        assert_eq!(stats.occupied_area, 66);
+    }
+
+    #[test]
+    fn test_update_returning_changes() {
+        let mut grid = Grid::from_ascii(&[
+            ".....",
+            "..#..",
+            "..#..",
+            "..#..",
+            ".....",
+        ], BoundaryType::Fixed);
+        
+        assert_eq!(grid.update_returning_changes(), vec![
+            (2, 1, false),
+            (1, 2, true),
+            (3, 2, true),
+            (2, 3, false),
+        ]);
+        assert_grid_eq!(grid, [
+            ".....",
+            ".....",
+            ".###.",
+            ".....",
+            ".....",
+        ]);
+        
+        // Flips past the first word of a row are found too
+        let mut wide = Grid::new(130, 3, BoundaryType::Wrap);
+        wide.set(127, 0, true);
+        assert_eq!(wide.update_returning_changes(), vec![(127, 0, false)]);
+        assert!(wide.update_returning_changes().is_empty());
+    }
+
+    #[test]
+    fn test_hash_state_matches_equality() {
+        // Every possible 3x3 grid
+        let grids: Vec<Grid> = (0..512u32).map(|bits| {
+            let mut grid = Grid::new(3, 3, BoundaryType::Wrap);
+            for i in 0..9 {
+                grid.set(i % 3, i / 3, bits & (1 << i) != 0);
+            }
+            grid
+        }).collect();
+        
+        for (i, a) in grids.iter().enumerate() {
+            for (j, b) in grids.iter().enumerate() {
+                assert_eq!(a.hash_state() == b.hash_state(), i == j);
+            }
+        }
+        
+        // Rebuilding the same state gives the same hash
+        let mut rebuilt = Grid::new(3, 3, BoundaryType::Wrap);
+        rebuilt.set(0, 0, true);
+        rebuilt.set(2, 1, true);
+        assert_eq!(rebuilt.hash_state(), grids[0b100001].hash_state());
+    }
+
+    #[test]
+    fn test_randomize_symmetric() {
+        for &(width, height) in &[(12, 12), (13, 13), (20, 9), (70, 11)] {
+            for symmetry in [Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Both, Symmetry::Rotational4] {
+                let mut grid = Grid::new(width, height, BoundaryType::Wrap);
+                grid.randomize_symmetric_seeded(0.5, symmetry, 7);
+                assert!(grid.count_alive() > 0);
+                
+                for y in 0..height {
+                    for x in 0..width {
+                        let alive = grid.get(x, y);
+                        let (mx, my) = (width - 1 - x, height - 1 - y);
+                        match symmetry {
+                            Symmetry::Horizontal => assert_eq!(alive, grid.get(mx, y)),
+                            Symmetry::Vertical => assert_eq!(alive, grid.get(x, my)),
+                            Symmetry::Both => {
+                                assert_eq!(alive, grid.get(mx, y));
+                                assert_eq!(alive, grid.get(x, my));
+                            },
+                            Symmetry::Rotational4 => {
+                                assert_eq!(alive, grid.get(mx, my));
+                                if width == height {
+                                    assert_eq!(alive, grid.get(width - 1 - y, x));
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbor_counts() {
+        // Horizontal blinker in the middle of a fixed grid
+        let mut grid = Grid::new(5, 5, BoundaryType::Fixed);
+        grid.set(1, 2, true);
+        grid.set(2, 2, true);
+        grid.set(3, 2, true);
+        
+        let counts = grid.neighbor_counts();
+        assert_eq!(counts, vec![
+            0, 0, 0, 0, 0,
+            1, 2, 3, 2, 1,
+            1, 1, 2, 1, 1,
+            1, 2, 3, 2, 1,
+            0, 0, 0, 0, 0,
+        ]);
+        
+        // Counts predict the next generation under the B3/S23 rules
+        let mut wrapped = Grid::new(7, 6, BoundaryType::Wrap);
+        wrapped.randomize_seeded(0.4, 3);
+        let counts = wrapped.neighbor_counts();
+        let mut next = Grid::new(7, 6, BoundaryType::Wrap);
+        for y in 0..6 {
+            for x in 0..7 {
+                next.set(x, y, wrapped.get(x, y));
+            }
+        }
+        next.update();
+        for y in 0..6 {
+            for x in 0..7 {
+                let count = counts[y * 7 + x];
+                assert_eq!(next.get(x, y), count == 3 || (count == 2 && wrapped.get(x, y)));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "images")]
+    fn test_from_image_thresholds_dark_pixels() {
+        let path = std::env::temp_dir().join(format!("conway_grid_image_test_{}.png", std::process::id()));
+        
+        // A 4x2 image: black, dark grey, light grey, transparent black / white row
+        let mut image = image::RgbaImage::from_pixel(4, 2, image::Rgba([255, 255, 255, 255]));
+        image.put_pixel(0, 0, image::Rgba([0, 0, 0, 255]));
+        image.put_pixel(1, 0, image::Rgba([90, 90, 90, 255]));
+        image.put_pixel(2, 0, image::Rgba([200, 200, 200, 255]));
+        image.put_pixel(3, 0, image::Rgba([0, 0, 0, 0]));
+        image.save(&path).unwrap();
+        
+        let grid = Grid::from_image(&path, 4, 2, BoundaryType::Wrap, 128).unwrap();
+        assert!(grid.get(0, 0));
+        assert!(grid.get(1, 0));
         assert!(!grid.get(2, 0));
-        assert!(!grid.get(2, 2));
+        assert!(!grid.get(3, 0));
+        assert_eq!(grid.count_alive(), 2);
+        
+        // A lower threshold keeps only the darkest pixel
+        let grid = Grid::from_image(&path, 4, 2, BoundaryType::Wrap, 50).unwrap();
+        assert_eq!(grid.count_alive(), 1);
+        
+        // The image is scaled to the requested dimensions
+        let grid = Grid::from_image(&path, 8, 4, BoundaryType::Wrap, 128).unwrap();
+        assert_eq!(grid.dimensions(), (8, 4));
+        assert!(grid.get(0, 0));
+        assert!(!grid.get(7, 3));
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+    
+    #[test]
+    #[cfg(feature = "images")]
+    fn test_save_png_draws_each_cell_as_a_square() {
+        let path = std::env::temp_dir().join(format!("conway_grid_png_test_{}.png", std::process::id()));
+        let grid = Grid::from_ascii(&[
+            "#..",
+            ".#.",
+        ], BoundaryType::Fixed);
+        grid.save_png(&path, 3, [255, 255, 255], [10, 20, 30]).unwrap();
+        
+        let image = image::open(&path).unwrap().to_rgb8();
+        assert_eq!(image.dimensions(), (9, 6));
+        assert_eq!(image.get_pixel(2, 2).0, [255, 255, 255]);
+        assert_eq!(image.get_pixel(3, 0).0, [10, 20, 30]);
+        assert_eq!(image.get_pixel(4, 4).0, [255, 255, 255]);
+        assert_eq!(image.get_pixel(8, 5).0, [10, 20, 30]);
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_has_orphan_pattern() {
+        // Nothing outside a fixed grid can help these two corner cells into existence
+        let mut corner = Grid::new(10, 10, BoundaryType::Fixed);
+        corner.set(0, 0, true);
+        corner.set(1, 1, true);
+        assert!(corner.has_orphan_pattern());
+        
+        // The same cells away from the edge, or on a torus, have predecessors
+        let mut interior = Grid::new(10, 10, BoundaryType::Fixed);
+        interior.set(4, 4, true);
+        interior.set(5, 5, true);
+        assert!(!interior.has_orphan_pattern());
+        
+        let mut wrapped = Grid::new(10, 10, BoundaryType::Wrap);
+        wrapped.set(0, 0, true);
+        wrapped.set(1, 1, true);
+        assert!(!wrapped.has_orphan_pattern());
+        
+        // A state produced by update always has a predecessor, so it is never flagged
+        for seed in 0..5 {
+            for boundary in [BoundaryType::Fixed, BoundaryType::Wrap] {
+                let mut grid = Grid::new(12, 9, boundary);
+                grid.randomize_seeded(0.4, seed);
+                grid.update();
+                assert!(!grid.has_orphan_pattern());
+            }
+        }
+        
+        // Rules outside the life-like family are not screened at all
+        corner.set_range_rule(Some(RangeRule::new(1, 3..=3, 2..=3)));
+        assert!(!corner.has_orphan_pattern());
+    }
+    
+    #[test]
+    fn test_live_cells_matches_scan() {
+        // 130 columns spans three words per row, with a partial last word
+        let mut grid = Grid::new(130, 7, BoundaryType::Fixed);
+        grid.randomize_seeded(0.2, 11);
+        grid.set(63, 0, true);
+        grid.set(64, 0, true);
+        grid.set(129, 6, true);
+        
+        let mut expected = Vec::new();
+        for y in 0..7 {
+            for x in 0..130 {
+                if grid.get(x, y) {
+                    expected.push((x, y));
+                }
+            }
+        }
+        
+        let live: Vec<(usize, usize)> = grid.live_cells().collect();
+        assert_eq!(live, expected);
+        assert_eq!(live.len(), grid.count_alive());
+        
+        assert_eq!(Grid::new(70, 3, BoundaryType::Wrap).live_cells().count(), 0);
+    }
+    
+    #[test]
+    fn test_ghost_border_counts_match_count_neighbors() {
+        // Tiny wrapped grids see the same cell through several edges, which the border must reproduce
+        let sizes = [(1, 1), (2, 2), (3, 1), (5, 4), (66, 5)];
+        
+        for (seed, &(width, height)) in sizes.iter().enumerate() {
+            for boundary in [BoundaryType::Wrap, BoundaryType::Fixed] {
+                let mut grid = Grid::new(width, height, boundary);
+                grid.randomize_seeded(0.5, seed as u64);
+                
+                let counts = grid.neighbor_counts();
+                for y in 0..height {
+                    for x in 0..width {
+                        assert_eq!(counts[y * width + x] as usize, grid.count_neighbors(x, y),
+                            "mismatch at ({}, {}) on a {}x{} grid", x, y, width, height);
+                    }
+                }
+            }
+        }
+    }
+    
+    #[test]
+    fn test_row_and_column_densities() {
+        let mut grid = Grid::new(70, 4, BoundaryType::Fixed);
+        grid.set(0, 0, true);
+        grid.set(65, 0, true);
+        grid.set(65, 2, true);
+        grid.set(65, 3, true);
+        grid.set(3, 3, true);
+        
+        assert_eq!(grid.row_densities(), vec![2, 0, 1, 2]);
+        
+        let columns = grid.column_densities();
+        assert_eq!(columns.len(), 70);
+        assert_eq!((columns[0], columns[3], columns[65]), (1, 1, 3));
+        assert_eq!(columns.iter().sum::<usize>(), grid.count_alive());
+        
+        assert_eq!(Grid::new(0, 3, BoundaryType::Wrap).row_densities(), vec![0, 0, 0]);
+    }
+    
+    #[test]
+    fn test_union_intersection_difference() {
+        let mut a = Grid::new(70, 3, BoundaryType::Wrap);
+        let mut b = Grid::new(70, 3, BoundaryType::Fixed);
+        a.set(1, 0, true);
+        a.set(66, 1, true);
+        b.set(66, 1, true);
+        b.set(2, 2, true);
+        
+        let union = a.union(&b).unwrap();
+        assert_eq!(union.live_cells().collect::<Vec<_>>(), vec![(1, 0), (66, 1), (2, 2)]);
+        assert!(matches!(union.boundary(), BoundaryType::Wrap));
+        
+        let intersection = a.intersection(&b).unwrap();
+        assert_eq!(intersection.live_cells().collect::<Vec<_>>(), vec![(66, 1)]);
+        
+        let difference = a.difference(&b).unwrap();
+        assert_eq!(difference.live_cells().collect::<Vec<_>>(), vec![(1, 0)]);
+        assert_eq!(b.difference(&a).unwrap().live_cells().collect::<Vec<_>>(), vec![(2, 2)]);
+        
+        // Grids of different sizes cannot be combined
+        let small = Grid::new(10, 3, BoundaryType::Wrap);
+        assert_eq!(a.union(&small).err(), Some(GridError::DimensionMismatch {
+            expected: (70, 3),
+            found: (10, 3),
+        }));
+    }
+
+    #[test]
+    fn test_coordinate_list_round_trip() {
+        let mut grid = Grid::new(70, 5, BoundaryType::Fixed);
+        grid.place_glider(1, 1);
+        grid.set(66, 0, true);
+        
+        let text = grid.to_coordinate_list();
+        assert_eq!(text, "66 0\n2 1\n3 2\n1 3\n2 3\n3 3\n");
+        
+        let loaded = Grid::from_coordinate_list(&text, 70, 5, BoundaryType::Fixed).unwrap();
+        assert_eq!(loaded.to_coordinate_list(), text);
+        assert_eq!(loaded.count_alive(), 6);
+        
+        // Comments, blank lines and extra spacing are skipped
+        let commented = "# from a script\n\n  1\t2  # trailing note\n3 4\n";
+        let loaded = Grid::from_coordinate_list(commented, 10, 10, BoundaryType::Wrap).unwrap();
+        assert_eq!(loaded.to_coordinate_list(), "1 2\n3 4\n");
+        
+        assert_eq!(
+            Grid::from_coordinate_list("1 2\n3\n", 10, 10, BoundaryType::Wrap).err(),
+            Some(GridError::InvalidCoordinates { line: 2, text: "3".to_string() })
+        );
+        assert_eq!(
+            Grid::from_coordinate_list("1 -2\n", 10, 10, BoundaryType::Wrap).err(),
+            Some(GridError::InvalidCoordinates { line: 1, text: "1 -2".to_string() })
+        );
+        assert_eq!(
+            Grid::from_coordinate_list("\n10 0\n", 10, 10, BoundaryType::Wrap).err(),
+            Some(GridError::CoordinatesOutOfBounds { line: 2, x: 10, y: 0 })
+        );
+    }
+    
+    #[test]
+    fn test_absorbing_boundary_clears_border() {
+        // A block in the corner is stable against a fixed wall but loses its outer cells
+        // to an absorbing one, and the single cell left dies of loneliness
+        for (boundary, expected) in [(BoundaryType::Fixed, 4), (BoundaryType::Absorbing, 0)] {
+            let mut grid = Grid::new(12, 12, boundary);
+            for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                grid.set(x, y, true);
+            }
+            grid.update();
+            grid.update();
+            assert_eq!(grid.count_alive(), expected);
+        }
+        
+        // Nothing survives on the outer ring, across word boundaries too
+        let mut fast = Grid::new(65, 9, BoundaryType::Absorbing);
+        fast.randomize_seeded(0.5, 3);
+        let mut reference = fast.clone();
+        fast.update();
+        reference.update_per_cell();
+        assert_eq!(fast.to_coordinate_list(), reference.to_coordinate_list());
+        assert!(fast.live_cells().all(|(x, y)| x > 0 && x < 64 && y > 0 && y < 8));
+        assert!(fast.count_alive() > 0);
+    }
+    
+    #[test]
+    fn test_parallel_update_matches_serial_over_many_generations() {
+        // Tall grids give Rayon many row chunks to schedule; widths straddle word boundaries
+        let sizes = [(40, 300), (128, 64), (129, 97), (200, 150)];
+        
+        for (seed, &(width, height)) in sizes.iter().enumerate() {
+            for boundary in [BoundaryType::Wrap, BoundaryType::Fixed, BoundaryType::Absorbing] {
+                let mut parallel = Grid::new(width, height, boundary);
+                parallel.randomize_seeded(0.35, 100 + seed as u64);
+                parallel.set_parallel_threshold(0);
+                let mut serial = parallel.clone();
+                let mut repeat = parallel.clone();
+                let mut single_thread = parallel.clone();
+                single_thread.set_parallel_threshold(usize::MAX);
+                
+                for generation in 0..120 {
+                    parallel.update();
+                    repeat.update();
+                    serial.update_per_cell();
+                    single_thread.update();
+                    
+                    // Same result as the serial reference, and the same on every run
+                    assert_eq!(parallel.hash_state(), serial.hash_state(),
+                        "{}x{} diverged from the serial update at generation {}", width, height, generation);
+                    assert_eq!(parallel.hash_state(), repeat.hash_state(),
+                        "{}x{} was not deterministic at generation {}", width, height, generation);
+                    assert_eq!(parallel.hash_state(), single_thread.hash_state(),
+                        "{}x{} differed below the parallel threshold at generation {}", width, height, generation);
+                }
+            }
+        }
+    }
+    
+    #[test]
+    fn test_life106_is_centered_and_clipped() {
+        // Glider around the origin, with a comment line
+        let glider = "#Life 1.06\n#D Glider\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+        let (grid, dropped) = Grid::from_life106(glider, 9, 9, BoundaryType::Fixed).unwrap();
+        assert_eq!(dropped, 0);
+        assert_eq!(grid.to_coordinate_list(), "4 3\n5 4\n3 5\n4 5\n5 5\n");
+        
+        // A row wider than the grid loses its ends
+        let row = "#Life 1.06\n-3 0\n-2 0\n-1 0\n0 0\n1 0\n2 0\n3 0\n";
+        let (grid, dropped) = Grid::from_life106(row, 5, 3, BoundaryType::Fixed).unwrap();
+        assert_eq!(dropped, 2);
+        assert_eq!(grid.count_alive(), 5);
+        
+        assert!(matches!(
+            Grid::from_life106("0 0\n", 5, 5, BoundaryType::Wrap),
+            Err(GridError::MissingHeader { .. })
+        ));
+        assert!(matches!(
+            Grid::from_life106("#Life 1.06\n0 x\n", 5, 5, BoundaryType::Wrap),
+            Err(GridError::InvalidCoordinates { line: 2, .. })
+        ));
+    }
+    
+    #[test]
+    fn test_ascii_round_trip_and_diff() {
+        let grid = Grid::from_ascii(&["#..", ".#", "..#"], BoundaryType::Fixed);
+        assert_eq!(grid.dimensions(), (3, 3));
+        assert_eq!(grid.to_ascii(), vec!["#..", ".#.", "..#"]);
+        
+        let expected = vec!["#.".to_string(), ".#".to_string()];
+        let actual = vec!["#.".to_string(), "##".to_string()];
+        assert_eq!(
+            crate::test_support::grid_diff(&expected, &actual),
+            "expected   actual\n#.       | #.\n.#       | ##  <\n"
+        );
+    }
+    
+    #[test]
+    fn test_range_rule_matches_direct_counts() {
+        for radius in [2, 3] {
+            for boundary in [BoundaryType::Wrap, BoundaryType::Fixed, BoundaryType::Absorbing] {
+                let mut fast = Grid::new(70, 23, boundary);
+                fast.randomize_seeded(0.4, radius as u64);
+                fast.set_range_rule(Some(RangeRule::new(radius, 5..=9, 4..=11)));
+                let mut reference = fast.clone();
+                
+                for generation in 0..10 {
+                    fast.update();
+                    reference.update_per_cell();
+                    assert_eq!(fast.hash_state(), reference.hash_state(),
+                        "radius {} diverged at generation {}", radius, generation);
+                }
+            }
+        }
+    }
+    
+    #[test]
+    fn test_count_neighbors_over_radius() {
+        let mut grid = Grid::new(9, 9, BoundaryType::Fixed);
+        for (x, y) in (0..9).flat_map(|x| (0..9).map(move |y| (x, y))) {
+            grid.set(x, y, true);
+        }
+        assert_eq!(grid.count_neighbors(4, 4), 8);
+        
+        grid.set_range_rule(Some(RangeRule::new(2, 6..=6, 0..=24)));
+        assert_eq!(grid.radius(), 2);
+        assert_eq!(grid.count_neighbors(4, 4), 24);
+        assert_eq!(grid.count_neighbors(0, 0), 8);
+        assert_eq!(grid.rule_notation(), "R2,C0,M0,S0..24,B6..6");
+        
+        // Every live cell survives, and the dead center sees 24 neighbors, too many to be born
+        grid.set(4, 4, false);
+        grid.update();
+        assert!(!grid.get(4, 4));
+        assert_eq!(grid.count_alive(), 80);
+    }
+    
+    #[test]
+    fn test_try_new_rejects_zero_dimensions() {
+        assert_eq!(
+            Grid::try_new(0, 5, BoundaryType::Wrap).err(),
+            Some(GridError::EmptyDimensions { width: 0, height: 5 })
+        );
+        assert!(Grid::try_new(3, 0, BoundaryType::Fixed).is_err());
+        
+        let grid = Grid::try_new(3, 3, BoundaryType::Fixed).unwrap();
+        assert_eq!(grid.dimensions(), (3, 3));
+    }
+    
+    #[test]
+    fn test_run_until_stable_detects_cycles() {
+        // A blinker repeats itself every other generation from the start
+        let mut grid = Grid::from_ascii(&[
+            ".....",
+            "..#..",
+            "..#..",
+            "..#..",
+            ".....",
+        ], BoundaryType::Fixed);
+        let result = grid.run_until_stable(100);
+        assert_eq!(result, StabilizationResult { generations: 0, period: Some(2) });
+        
+        // Three cells in an L become a block after one generation
+        let mut grid = Grid::from_ascii(&[
+            "....",
+            ".#..",
+            ".##.",
+            "....",
+        ], BoundaryType::Fixed);
+        assert_eq!(grid.run_until_stable(100), StabilizationResult { generations: 1, period: Some(1) });
+        
+        // A glider takes 40 generations to come back around a 10x10 torus
+        let mut grid = Grid::new(10, 10, BoundaryType::Wrap);
+        grid.place_glider(1, 1);
+        assert!(!grid.run_until_stable(39).is_stable());
+        let mut grid = Grid::new(10, 10, BoundaryType::Wrap);
+        grid.place_glider(1, 1);
+        assert_eq!(grid.run_until_stable(100).period, Some(40));
+    }
+    
+    #[test]
+    fn test_grids_compare_and_hash_by_cells() {
+        let glider = |boundary| {
+            let mut grid = Grid::new(8, 8, boundary);
+            grid.place_glider(2, 2);
+            grid
+        };
+        
+        // The boundary is a setting, not part of the state
+        assert!(glider(BoundaryType::Wrap) == glider(BoundaryType::Fixed));
+        
+        let mut moved = glider(BoundaryType::Wrap);
+        moved.update();
+        assert!(moved != glider(BoundaryType::Wrap));
+        assert!(Grid::new(8, 8, BoundaryType::Wrap) != Grid::new(8, 9, BoundaryType::Wrap));
+        
+        // A glider returns to its own shape, shifted, so a set of grids sees every phase once
+        let mut grid = glider(BoundaryType::Wrap);
+        let mut seen = std::collections::HashSet::new();
+        while seen.insert(grid.clone()) {
+            grid.update();
+        }
+        assert_eq!(seen.len(), 32);
+    }
+    
+    #[test]
+    fn test_randomize_gradient_interpolates_density() {
+        let mut grid = Grid::new(11, 200, BoundaryType::Wrap);
+        grid.randomize_gradient_seeded(1.0, 0.0, 7);
+        
+        let column = |grid: &Grid, x: usize| (0..200).filter(|&y| grid.get(x, y)).count();
+        assert_eq!(column(&grid, 0), 200);
+        assert_eq!(column(&grid, 10), 0);
+        assert!(column(&grid, 2) > column(&grid, 8));
+        
+        // Half density in the middle column, give or take sampling noise
+        assert!((70..130).contains(&column(&grid, 5)));
+        
+        // The same seed gives the same board
+        let mut again = Grid::new(11, 200, BoundaryType::Wrap);
+        again.randomize_gradient_seeded(1.0, 0.0, 7);
+        assert!(again == grid);
+    }
+    
+    #[test]
+    fn test_count_alive_in_row_spans_words() {
+        let mut grid = Grid::new(150, 2, BoundaryType::Fixed);
+        for x in [0, 3, 63, 64, 100, 128, 149] {
+            grid.set(x, 1, true);
+        }
+        
+        // Ranges within one word, across word boundaries and past the right edge
+        assert_eq!(grid.count_alive_in_row(1, 0, 4), 2);
+        assert_eq!(grid.count_alive_in_row(1, 60, 68), 2);
+        assert_eq!(grid.count_alive_in_row(1, 1, 150), 6);
+        assert_eq!(grid.count_alive_in_row(1, 64, 500), 4);
+        assert_eq!(grid.count_alive_in_row(1, 5, 5), 0);
+        assert_eq!(grid.count_alive_in_row(0, 0, 150), 0);
+        assert_eq!(grid.count_alive_in_row(2, 0, 150), 0);
     }
 }
\ No newline at end of file
@@ -0,0 +1,88 @@
+// Conway's Game of Life Generation History
+// A ring buffer of grid snapshots for scrubbing backward and forward through generations
+
+use std::collections::VecDeque;
+
+use crate::grid::Grid;
+
+/// A grid state stored as the list of its live cells
+pub struct Snapshot {
+    pub generation: usize,
+    pub cells: Vec<(u32, u32)>,
+}
+
+impl Snapshot {
+    fn capture(generation: usize, grid: &Grid) -> Self {
+        let cells = grid.live_cells()
+            .map(|(x, y)| (x as u32, y as u32))
+            .collect();
+
+        Self { generation, cells }
+    }
+
+    /// Overwrite the grid with this snapshot
+    pub fn restore(&self, grid: &mut Grid) {
+        grid.clear();
+        for &(x, y) in &self.cells {
+            grid.set(x as usize, y as usize, true);
+        }
+    }
+}
+
+/// Fixed-depth history of snapshots, ordered by generation
+pub struct GridHistory {
+    snapshots: VecDeque<Snapshot>,
+    depth: usize,
+    interval: usize,
+}
+
+impl GridHistory {
+    /// Keep up to `depth` snapshots, taken every `interval` generations
+    pub fn new(depth: usize, interval: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(depth),
+            depth: depth.max(1),
+            interval: interval.max(1),
+        }
+    }
+
+    /// Snapshot the grid if this generation falls on the recording interval
+    pub fn record(&mut self, generation: usize, grid: &Grid) {
+        if generation.is_multiple_of(self.interval) {
+            self.record_now(generation, grid);
+        }
+    }
+
+    /// Snapshot the grid unconditionally, replacing any history from this generation onward
+    pub fn record_now(&mut self, generation: usize, grid: &Grid) {
+        while self.snapshots.back().is_some_and(|snapshot| snapshot.generation >= generation) {
+            self.snapshots.pop_back();
+        }
+
+        if self.snapshots.len() == self.depth {
+            self.snapshots.pop_front();
+        }
+
+        self.snapshots.push_back(Snapshot::capture(generation, grid));
+    }
+
+    /// The latest snapshot before the given generation
+    pub fn before(&self, generation: usize) -> Option<&Snapshot> {
+        self.snapshots.iter().rev().find(|snapshot| snapshot.generation < generation)
+    }
+
+    /// The earliest snapshot after the given generation, or None at the recorded frontier
+    pub fn after(&self, generation: usize) -> Option<&Snapshot> {
+        self.snapshots.iter().find(|snapshot| snapshot.generation > generation)
+    }
+
+    /// Number of stored snapshots
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Whether no snapshots are stored
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
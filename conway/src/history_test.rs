@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+    use crate::config::BoundaryType;
+    use crate::history::GridHistory;
+
+    #[test]
+    fn test_history_rewinds_and_evicts_oldest() {
+        let mut grid = Grid::new(10, 10, BoundaryType::Wrap);
+        grid.set(4, 5, true);
+        grid.set(5, 5, true);
+        grid.set(6, 5, true);
+        
+        let mut history = GridHistory::new(3, 1);
+        history.record(0, &grid);
+        for generation in 1..=4 {
+            grid.update();
+            history.record(generation, &grid);
+        }
+        
+        // Only the three most recent generations are kept
+        assert_eq!(history.len(), 3);
+        assert!(history.before(2).is_none());
+        assert_eq!(history.before(4).unwrap().generation, 3);
+        assert!(history.after(4).is_none());
+        
+        // Generation 3 is the vertical phase of the blinker
+        let mut restored = Grid::new(10, 10, BoundaryType::Wrap);
+        history.before(4).unwrap().restore(&mut restored);
+        assert!(restored.get(5, 4) && restored.get(5, 5) && restored.get(5, 6));
+        assert_eq!(restored.count_alive(), 3);
+    }
+
+    #[test]
+    fn test_record_now_discards_future() {
+        let grid = Grid::new(5, 5, BoundaryType::Wrap);
+        let mut history = GridHistory::new(10, 2);
+        
+        history.record(0, &grid);
+        history.record(1, &grid);
+        history.record(2, &grid);
+        history.record(4, &grid);
+        assert_eq!(history.len(), 3);
+        
+        // Editing at generation 2 replaces everything from there onward
+        history.record_now(2, &grid);
+        assert_eq!(history.len(), 2);
+        assert!(history.after(2).is_none());
+        assert_eq!(history.before(2).unwrap().generation, 0);
+    }
+}
@@ -1,12 +1,54 @@
 pub mod config;
 pub mod grid;
+#[cfg(feature = "terminal")]
 pub mod renderer;
+#[cfg(all(feature = "terminal", feature = "images"))]
 pub mod game;
 pub mod patterns;
 pub mod benchmark;
+#[cfg(feature = "terminal")]
 pub mod tutorial;
 pub mod analyzer;
+#[cfg(feature = "images")]
 pub mod visualizer;
+#[cfg(feature = "terminal")]
+pub mod replay;
+pub mod history;
+pub mod rle;
+pub mod rule;
+pub mod automaton;
+pub mod cast;
+#[cfg(feature = "terminal")]
+pub mod session;
 
 #[cfg(test)]
-mod grid_test;
\ No newline at end of file
+#[macro_use]
+mod test_support;
+#[cfg(test)]
+mod grid_test;
+#[cfg(all(test, feature = "terminal"))]
+mod replay_test;
+#[cfg(test)]
+mod patterns_test;
+#[cfg(all(test, feature = "terminal"))]
+mod tutorial_test;
+#[cfg(all(test, feature = "terminal", feature = "images"))]
+mod game_test;
+#[cfg(test)]
+mod analyzer_test;
+#[cfg(test)]
+mod history_test;
+#[cfg(all(test, feature = "images"))]
+mod visualizer_test;
+#[cfg(all(test, feature = "terminal"))]
+mod renderer_test;
+#[cfg(test)]
+mod rle_test;
+#[cfg(test)]
+mod benchmark_test;
+#[cfg(test)]
+mod rule_test;
+#[cfg(test)]
+mod automaton_test;
+#[cfg(test)]
+mod cast_test;
\ No newline at end of file
@@ -1,44 +1,190 @@
-mod config;
-mod grid;
-mod renderer;
-mod game;
-mod patterns;
-
 use std::path::Path;
 use std::fs::File;
 use std::io::{self, Read};
 use clap::Parser;
-use config::{Config, CellTheme, ColorTheme, BoundaryType};
-use game::Game;
-use patterns::PatternLibrary;
+use crossterm::terminal;
+use conway::config::{Config, CellTheme, ColorTheme, BoundaryType, CursorStyle};
+use conway::game::Game;
+use conway::grid::Grid;
+use conway::patterns::PatternLibrary;
+use conway::renderer::grid_size_for_terminal;
+use conway::replay::Replay;
+use conway::rule;
+use conway::tutorial::Tutorial;
+use conway::visualizer::{VisualTheme, Visualizer, VisualizerSettings};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments
-    let config = Config::parse();
-    
-    // Create game instance
-    let mut game = Game::new(
-        config.width,
-        config.height,
-        config.max_fps,
-        BoundaryType::from_string(&config.boundary),
-        config.file.clone(),
-    );
-    
-    // Apply initial pattern if specified
-    if let Some(pattern_name) = &config.initial_pattern {
-        if let Some(pattern) = PatternLibrary::get_by_name(pattern_name) {
-            let x = config.width / 2 - pattern.width / 2;
-            let y = config.height / 2 - pattern.height / 2;
-            game.initialize_with_pattern(&pattern, x, y);
+    let mut config = Config::parse();
+    
+    // Larger than Life rules start with their radius, e.g. R5,C0,M0,S33..57,B34..45
+    let (rule, range_rule) = rule::parse_any(&config.rule)?;
+    
+    // Describe the rule instead of running it
+    if config.print_rule {
+        match &range_rule {
+            Some(range_rule) => print!("{}", range_rule.summary()),
+            None => print!("{}", rule.truth_table()),
         }
+        return Ok(());
+    }
+    
+    // Render a pattern to a GIF instead of running the game
+    if let (Some(pattern_name), Some(gif_path)) = (&config.visualize, &config.gif) {
+        let pattern = PatternLibrary::get_by_name_in(PatternLibrary::all_with_user_patterns(), pattern_name)
+            .ok_or_else(|| format!("Unknown pattern '{}'", pattern_name))?;
+        let mut visualizer = Visualizer::new(VisualizerSettings {
+            cell_size: config.cell_size,
+            theme: VisualTheme::from_string(&config.visual_theme),
+            generations: config.gif_generations,
+            ..Default::default()
+        });
+        
+        visualizer.create_pattern_gif(
+            &pattern,
+            gif_path,
+            (config.width, config.height),
+            config.boundary.clone(),
+        )?;
+        println!("Saved {} generations of {} to {}", config.gif_generations, pattern.name, gif_path.display());
+        return Ok(());
     }
     
-    // If generate-from-seed is specified, create a custom pattern
-    if let Some(seed_path) = &config.generate_from_seed {
-        if let Ok(complexity) = read_complexity_from_file(seed_path) {
-            generate_custom_pattern(&mut game, complexity);
+    // The tutorial is a separate mode with its own grids
+    if let Some(tutorial_path) = &config.tutorial_file {
+        Tutorial::from_file(tutorial_path)?.run()?;
+        return Ok(());
+    }
+    
+    if config.tutorial {
+        Tutorial::new().run()?;
+        return Ok(());
+    }
+    
+    // Fill the terminal, leaving room for the status bar. Every cell theme draws one column per
+    // character, so cells are as wide as --cell-aspect, the same width the renderer prints them.
+    if config.auto_size {
+        let (columns, rows) = terminal::size()?;
+        (config.width, config.height) = grid_size_for_terminal(columns as usize, rows as usize, config.cell_aspect);
+    }
+    
+    // Resume a saved session if there is one, otherwise create a new game
+    let resumed = config.session.as_ref().filter(|path| path.exists());
+    let mut game = match resumed {
+        Some(path) => Game::load_session(path, config.max_fps)?,
+        None => Game::new(
+            config.width,
+            config.height,
+            config.max_fps,
+            config.boundary.clone(),
+            config.file.clone(),
+        ),
+    };
+    
+    // Widen cells so the grid keeps its aspect ratio on screen
+    game.set_cell_aspect(config.cell_aspect);
+    
+    // Show births, survivors and doomed cells in their own colors
+    game.set_change_highlights(config.highlight_changes, config.mark_dying);
+    game.set_dead_marks(config.dead_marks);
+    
+    // Mark the cursor so the cell under it stays readable if asked
+    game.set_cursor_style(CursorStyle::from_string(&config.cursor_style));
+    
+    // Randomize with r and R at the requested density
+    game.set_random_density(config.density);
+    
+    // Seed the game's RNG so random edits are reproducible
+    if let Some(seed) = config.seed {
+        game.set_seed(seed);
+    }
+    
+    // A resumed session brings its own board and rule
+    if resumed.is_none() {
+        // Seed the grid from an image if specified
+        if let Some(image_path) = &config.image {
+            game.set_grid(Grid::from_image(
+                image_path,
+                config.width,
+                config.height,
+                config.boundary.clone(),
+                config.image_threshold,
+            )?);
+        }
+        
+        // Apply initial pattern if specified
+        if let Some(pattern_name) = &config.initial_pattern {
+            if let Some(pattern) = PatternLibrary::get_by_name_in(PatternLibrary::all_with_user_patterns(), pattern_name) {
+                // Guns and spaceships on a cramped grid soon crash into their own output
+                let (recommended_width, recommended_height) = pattern.recommended_grid_size();
+                if config.width < recommended_width || config.height < recommended_height {
+                    eprintln!(
+                        "Warning: {} needs a grid of at least {}x{} to evolve undisturbed; \
+                         the {}x{} grid may make it run into {}",
+                        pattern.name, recommended_width, recommended_height, config.width, config.height,
+                        if matches!(config.boundary, BoundaryType::Wrap) { "itself across the edges" } else { "the edges" },
+                    );
+                }
+                
+                let (x, y) = pattern.centered_position(config.width, config.height);
+                game.initialize_with_pattern(&pattern, x, y);
+            }
         }
+        
+        // If generate-from-seed is specified, create a custom pattern
+        if let Some(seed_path) = &config.generate_from_seed {
+            if let Ok(complexity) = read_complexity_from_file(seed_path) {
+                generate_custom_pattern(&mut game, complexity, config.seed);
+            }
+        }
+        
+        // Otherwise start from a random board if asked
+        if config.randomize_on_start {
+            game.randomize();
+        }
+    }
+    
+    // Stop after a fixed number of generations, e.g. for demos and unattended runs
+    game.set_generation_limit(config.max_generations, config.exit_on_limit);
+    
+    // Simulate several generations per frame when fast-forwarding
+    game.set_steps_per_frame(config.steps_per_frame);
+    
+    // Keep a rewind history if requested
+    if let Some(depth) = config.history {
+        game.enable_history(depth, config.history_interval);
+    }
+    
+    // Record the session if requested
+    if let Some(record_path) = &config.record {
+        game.record_to(record_path.clone());
+    }
+    
+    // Capture what is drawn as a terminal recording if requested
+    if let Some(cast_path) = &config.record_cast {
+        game.record_cast_to(cast_path.clone());
+    }
+    
+    // Allow screenshots if a path for them was given
+    if let Some(screenshot_path) = &config.screenshot {
+        game.screenshot_to(screenshot_path.clone());
+    }
+    
+    // Save the session on exit so it can be resumed
+    if let Some(session_path) = &config.session {
+        game.save_session_to(session_path.clone());
+    }
+    
+    // Apply the rule once the grid is in place, since loading an image replaces it.
+    // Resumed sessions and replays bring their own rule.
+    if resumed.is_none() {
+        game.set_rule(rule);
+        game.set_range_rule(range_rule);
+    }
+    
+    // Replay a recorded session on top of everything else
+    if let Some(replay_path) = &config.replay {
+        game.play_replay(Replay::load_from_file(replay_path)?);
     }
     
     // Start the game
@@ -60,9 +206,13 @@ fn read_complexity_from_file(path: &Path) -> io::Result<usize> {
     })
 }
 
-fn generate_custom_pattern(game: &mut Game, complexity: usize) {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
+fn generate_custom_pattern(game: &mut Game, complexity: usize, seed: Option<u64>) {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
     
     // Get all available patterns
     let patterns = PatternLibrary::get_all_patterns();
@@ -1,15 +1,106 @@
 // Conway's Game of Life Pattern Library
 // This module contains implementations of common Game of Life patterns
 
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::analyzer::{PatternAnalyzer, PatternKind};
+use crate::config::BoundaryType;
 use crate::grid::Grid;
+use crate::rle;
+
+/// Error returned when a pattern cannot be placed on a grid
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlacementError {
+    /// The pattern would extend past the edge of the grid
+    OutOfBounds {
+        x: usize,
+        y: usize,
+        pattern_size: (usize, usize),
+        grid_size: (usize, usize),
+    },
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementError::OutOfBounds { x, y, pattern_size, grid_size } => write!(
+                f,
+                "Pattern of size {}x{} at ({}, {}) does not fit in a {}x{} grid",
+                pattern_size.0, pattern_size.1, x, y, grid_size.0, grid_size.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlacementError {}
+
+/// Generations of evolution that `Pattern::recommended_grid_size` leaves room for
+const RECOMMENDED_GENERATIONS: usize = 150;
+
+/// Empty cells `Pattern::recommended_grid_size` keeps around the footprint on every side,
+/// enough that cells on opposite sides of a wrapped edge never become neighbors
+const RECOMMENDED_MARGIN: usize = 4;
+
+/// What kind of object a library pattern is, for organizing the catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatternCategory {
+    /// Never changes from one generation to the next
+    StillLife,
+    /// Returns to its starting shape in place after a fixed period
+    Oscillator,
+    /// Returns to its starting shape displaced, so it travels across the grid
+    Spaceship,
+    /// A small pattern that takes a long time to settle
+    Methuselah,
+    /// Stays in place while periodically emitting spaceships
+    Gun,
+    /// Travels across the grid leaving debris behind it
+    Puffer,
+    /// Not assigned a category, such as patterns saved from the game
+    Uncategorized,
+}
+
+impl PatternCategory {
+    /// Every category, in the order they are listed in
+    pub const ALL: [PatternCategory; 7] = [
+        PatternCategory::StillLife,
+        PatternCategory::Oscillator,
+        PatternCategory::Spaceship,
+        PatternCategory::Methuselah,
+        PatternCategory::Gun,
+        PatternCategory::Puffer,
+        PatternCategory::Uncategorized,
+    ];
+}
+
+impl fmt::Display for PatternCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PatternCategory::StillLife => "still life",
+            PatternCategory::Oscillator => "oscillator",
+            PatternCategory::Spaceship => "spaceship",
+            PatternCategory::Methuselah => "methuselah",
+            PatternCategory::Gun => "gun",
+            PatternCategory::Puffer => "puffer",
+            PatternCategory::Uncategorized => "uncategorized",
+        };
+        f.write_str(name)
+    }
+}
 
 /// Structure representing a pattern that can be placed on the grid
 pub struct Pattern {
     pub name: &'static str,
     pub description: &'static str,
+    pub category: PatternCategory,
     pub width: usize,
     pub height: usize,
     pub cells: Vec<(usize, usize)>,
+    pub author: Option<String>,   // Who discovered or drew the pattern, from an RLE #O line
+    pub comments: Vec<String>,    // Free-form notes, from RLE #C lines
 }
 
 impl Pattern {
@@ -31,12 +122,173 @@ impl Pattern {
             }
         }
     }
+    
+    /// Whether placing this pattern at the position would destroy live cells already on the grid.
+    /// `place` clears the pattern's whole bounding rectangle, so any live cell inside it counts,
+    /// not only those under the pattern's own cells. Parts past the grid edges are ignored, as
+    /// `place` clips them.
+    pub fn would_collide(&self, grid: &Grid, x: usize, y: usize) -> bool {
+        let (width, height) = grid.dimensions();
+        (y..(y + self.height).min(height))
+            .any(|cy| (x..(x + self.width).min(width)).any(|cx| grid.get(cx, cy)))
+    }
+    
+    /// Place this pattern with its top-left corner at a possibly negative or out-of-range position.
+    /// On wrapping grids every cell is taken modulo the grid size, so the pattern tiles across
+    /// the seam of the torus; on fixed grids cells past the edges are clipped as in `place`.
+    pub fn place_wrapped(&self, grid: &mut Grid, x: isize, y: isize) {
+        let (width, height) = grid.dimensions();
+        if width == 0 || height == 0 {
+            return;
+        }
+        
+        let wraps = matches!(grid.boundary(), BoundaryType::Wrap);
+        let position = |dx: usize, dy: usize| {
+            let (cx, cy) = (x + dx as isize, y + dy as isize);
+            if wraps {
+                Some((cx.rem_euclid(width as isize) as usize, cy.rem_euclid(height as isize) as usize))
+            } else if (0..width as isize).contains(&cx) && (0..height as isize).contains(&cy) {
+                Some((cx as usize, cy as usize))
+            } else {
+                None
+            }
+        };
+        
+        // Clear the area
+        for dy in 0..self.height {
+            for dx in 0..self.width {
+                if let Some((cx, cy)) = position(dx, dy) {
+                    grid.set(cx, cy, false);
+                }
+            }
+        }
+        
+        // Place the pattern
+        for &(px, py) in &self.cells {
+            if let Some((cx, cy)) = position(px, py) {
+                grid.set(cx, cy, true);
+            }
+        }
+    }
+    
+    /// Encode this pattern as RLE at its declared size, keeping its author and comments
+    pub fn to_rle(&self) -> String {
+        let mut grid = Grid::new(self.width, self.height, BoundaryType::Fixed);
+        self.place(&mut grid, 0, 0);
+        rle::encode_with_metadata(self.name, self.author.as_deref(), &self.comments, &grid)
+    }
+    
+    /// Draw the live cells as `#` on spaces, fitting within `max_width` by `max_height` characters.
+    /// Larger patterns are scaled down, each character covering a square block of cells that
+    /// shows `#` if any of them is alive. Lines are separated by newlines, without trailing spaces.
+    pub fn ascii_preview(&self, max_width: usize, max_height: usize) -> String {
+        if self.cells.is_empty() || max_width == 0 || max_height == 0 {
+            return String::new();
+        }
+        
+        let (min_x, min_y, max_x, max_y) = self.tight_bounds();
+        let (content_width, content_height) = (max_x - min_x + 1, max_y - min_y + 1);
+        let scale = content_width.div_ceil(max_width).max(content_height.div_ceil(max_height));
+        
+        let mut rows = vec![vec![' '; content_width.div_ceil(scale)]; content_height.div_ceil(scale)];
+        for &(x, y) in &self.cells {
+            rows[(y - min_y) / scale][(x - min_x) / scale] = '#';
+        }
+        
+        rows.iter()
+            .map(|row| row.iter().collect::<String>().trim_end().to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+    
+    /// Number of live cells in the pattern
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+    
+    /// Tight extent of the live cells as (min_x, min_y, max_x, max_y), ignoring any empty
+    /// rows or columns in the declared width and height. A pattern without cells gives (0, 0, 0, 0).
+    pub fn tight_bounds(&self) -> (usize, usize, usize, usize) {
+        let Some(&(first_x, first_y)) = self.cells.first() else {
+            return (0, 0, 0, 0);
+        };
+        
+        self.cells.iter().fold((first_x, first_y, first_x, first_y), |(min_x, min_y, max_x, max_y), &(x, y)| {
+            (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+        })
+    }
+    
+    /// Position to place this pattern at so its live cells sit in the middle of a grid
+    /// of the given size. Clamped at zero when the pattern is larger than the grid.
+    pub fn centered_position(&self, grid_width: usize, grid_height: usize) -> (usize, usize) {
+        let (min_x, min_y, max_x, max_y) = self.tight_bounds();
+        let (content_width, content_height) = (max_x - min_x + 1, max_y - min_y + 1);
+        (
+            (grid_width / 2).saturating_sub(min_x + content_width / 2),
+            (grid_height / 2).saturating_sub(min_y + content_height / 2),
+        )
+    }
+    
+    /// Smallest grid that leaves this pattern room to evolve for RECOMMENDED_GENERATIONS
+    /// without touching a fixed edge or meeting its own debris across a wrapped one.
+    /// The footprint is measured by running the pattern on an open plane, so guns include
+    /// their glider streams and spaceships the distance they travel.
+    pub fn recommended_grid_size(&self) -> (usize, usize) {
+        // Nothing spreads faster than one cell per generation, so with this much room
+        // the fixed edges never influence the evolution being measured
+        let reach = RECOMMENDED_GENERATIONS + 1;
+        let mut grid = Grid::new(self.width + 2 * reach, self.height + 2 * reach, BoundaryType::Fixed);
+        self.place(&mut grid, reach, reach);
+        
+        // Union of the bounding boxes of every generation
+        let mut footprint = (reach, reach, reach + self.width.max(1) - 1, reach + self.height.max(1) - 1);
+        for _ in 0..RECOMMENDED_GENERATIONS {
+            grid.update();
+            let Some((min_x, min_y, max_x, max_y)) = grid.bounding_box() else {
+                break;
+            };
+            footprint = (
+                footprint.0.min(min_x),
+                footprint.1.min(min_y),
+                footprint.2.max(max_x),
+                footprint.3.max(max_y),
+            );
+        }
+        
+        let (min_x, min_y, max_x, max_y) = footprint;
+        (
+            max_x - min_x + 1 + 2 * RECOMMENDED_MARGIN,
+            max_y - min_y + 1 + 2 * RECOMMENDED_MARGIN,
+        )
+    }
+    
+    /// Place this pattern only if it fits entirely on the grid.
+    /// Unlike `place`, nothing is clipped and the grid is left untouched on failure.
+    pub fn try_place(&self, grid: &mut Grid, x: usize, y: usize) -> Result<(), PlacementError> {
+        let (grid_width, grid_height) = grid.dimensions();
+        
+        let fits = x.checked_add(self.width).is_some_and(|right| right <= grid_width)
+            && y.checked_add(self.height).is_some_and(|bottom| bottom <= grid_height);
+        
+        if !fits {
+            return Err(PlacementError::OutOfBounds {
+                x,
+                y,
+                pattern_size: (self.width, self.height),
+                grid_size: (grid_width, grid_height),
+            });
+        }
+        
+        self.place(grid, x, y);
+        Ok(())
+    }
 }
 
 /// Collection of common patterns
 pub struct PatternLibrary;
 
 impl PatternLibrary {
+    /// Every pattern in the built-in library
     pub fn get_all_patterns() -> Vec<Pattern> {
         vec![
             Self::glider(),
@@ -49,12 +301,131 @@ impl PatternLibrary {
             Self::r_pentomino(),
             Self::diehard(),
             Self::acorn(),
+            Self::pentadecathlon(),
+            Self::middleweight_spaceship(),
+            Self::heavyweight_spaceship(),
+            Self::queen_bee_shuttle(),
+            Self::switch_engine(),
+            Self::p46_gun(),
         ]
     }
     
-    /// Get a pattern by name
+    /// The built-in library followed by the patterns saved from the game, for the places
+    /// the player looks patterns up by name
+    pub fn all_with_user_patterns() -> Vec<Pattern> {
+        let mut patterns = Self::get_all_patterns();
+        patterns.extend(Self::user_patterns());
+        patterns
+    }
+    
+    /// Where patterns saved from the game are kept: `$CONWAY_PATTERNS` if set,
+    /// otherwise `.conway-patterns.rle` in the home directory
+    pub fn user_patterns_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("CONWAY_PATTERNS") {
+            return Some(PathBuf::from(path));
+        }
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".conway-patterns.rle"))
+    }
+    
+    /// Patterns saved from the game, read from the user patterns file the first time they
+    /// are needed. Patterns saved later in the same session appear on the next launch.
+    pub fn user_patterns() -> Vec<Pattern> {
+        static USER_PATTERNS: OnceLock<Vec<(&'static str, rle::RlePattern)>> = OnceLock::new();
+        
+        let loaded = USER_PATTERNS.get_or_init(|| {
+            let Some(path) = Self::user_patterns_path().filter(|path| path.exists()) else {
+                return Vec::new();
+            };
+            
+            match rle::load_file(&path) {
+                // Library patterns have static names; these are loaded once per run, so leaking them is bounded
+                Ok(patterns) => patterns.into_iter()
+                    .map(|pattern| (&*Box::leak(pattern.name.clone().into_boxed_str()), pattern))
+                    .collect(),
+                Err(e) => {
+                    eprintln!("Failed to load user patterns from {}: {}", path.display(), e);
+                    Vec::new()
+                },
+            }
+        });
+        
+        loaded.iter()
+            .map(|(name, pattern)| Pattern {
+                name,
+                description: "A pattern saved from the game",
+                category: PatternCategory::Uncategorized,
+                width: pattern.width,
+                height: pattern.height,
+                cells: pattern.cells.clone(),
+                author: pattern.author.clone(),
+                comments: pattern.comments.clone(),
+            })
+            .collect()
+    }
+    
+    /// Get every library pattern in the given category, in library order
+    pub fn by_category(category: PatternCategory) -> Vec<Pattern> {
+        Self::get_all_patterns()
+            .into_iter()
+            .filter(|pattern| pattern.category == category)
+            .collect()
+    }
+    
+    /// Get a pattern by name, ignoring case, spaces, dashes and underscores,
+    /// so "glider_gun" finds "Glider Gun"
     pub fn get_by_name(name: &str) -> Option<Pattern> {
-        Self::get_all_patterns().into_iter().find(|p| p.name.to_lowercase() == name.to_lowercase())
+        Self::get_by_name_in(Self::get_all_patterns(), name)
+    }
+    
+    /// Get a pattern by name from the given patterns, matching names as `get_by_name` does
+    pub fn get_by_name_in(patterns: Vec<Pattern>, name: &str) -> Option<Pattern> {
+        let key = |name: &str| -> String {
+            name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+        };
+        let wanted = key(name);
+        patterns.into_iter().find(|p| key(p.name) == wanted)
+    }
+    
+    /// Find patterns whose names contain the query's characters in order, ignoring case.
+    /// Results are ranked by where the match starts, then by how tightly it is packed,
+    /// so "gun" puts "Glider Gun" ahead of looser matches. An empty query returns every pattern.
+    pub fn search(query: &str) -> Vec<Pattern> {
+        Self::search_in(Self::get_all_patterns(), query)
+    }
+    
+    /// Search the given patterns, ranking matches as `search` does
+    pub fn search_in(patterns: Vec<Pattern>, query: &str) -> Vec<Pattern> {
+        let mut matches: Vec<((usize, usize), Pattern)> = patterns
+            .into_iter()
+            .filter_map(|pattern| Some((fuzzy_match(query, pattern.name)?, pattern)))
+            .collect();
+        
+        // Stable sort keeps library order among equally good matches
+        matches.sort_by_key(|&(score, _)| score);
+        matches.into_iter().map(|(_, pattern)| pattern).collect()
+    }
+    
+    /// Group the names of all library patterns by how they behave under the given analyzer.
+    /// Each pattern is analyzed from the center of the analyzer's grid; patterns too large
+    /// for that grid are listed as `Unknown`. Analysis is deterministic, so the result only
+    /// depends on the analyzer's settings and can be kept for reuse.
+    pub fn filter_by_type(analyzer: &PatternAnalyzer) -> HashMap<PatternKind, Vec<&'static str>> {
+        let (grid_width, grid_height) = analyzer.grid_size();
+        let mut groups: HashMap<PatternKind, Vec<&'static str>> = HashMap::new();
+        
+        for pattern in Self::get_all_patterns() {
+            let kind = if pattern.width <= grid_width && pattern.height <= grid_height {
+                let x = (grid_width - pattern.width) / 2;
+                let y = (grid_height - pattern.height) / 2;
+                analyzer.analyze_pattern(&pattern, x, y).pattern_type.kind()
+            } else {
+                PatternKind::Unknown
+            };
+            
+            groups.entry(kind).or_default().push(pattern.name);
+        }
+        
+        groups
     }
     
     /// Simple glider pattern
@@ -62,9 +433,12 @@ impl PatternLibrary {
         Pattern {
             name: "Glider",
             description: "The smallest, most common spaceship",
+            category: PatternCategory::Spaceship,
             width: 3,
             height: 3,
             cells: vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)],
+            author: Some("Richard K. Guy".to_string()),
+            comments: Vec::new(),
         }
     }
     
@@ -73,9 +447,12 @@ impl PatternLibrary {
         Pattern {
             name: "Blinker",
             description: "The smallest oscillator with period 2",
+            category: PatternCategory::Oscillator,
             width: 3,
             height: 3,
             cells: vec![(1, 0), (1, 1), (1, 2)],
+            author: None,
+            comments: Vec::new(),
         }
     }
     
@@ -84,9 +461,12 @@ impl PatternLibrary {
         Pattern {
             name: "Toad",
             description: "A period 2 oscillator",
+            category: PatternCategory::Oscillator,
             width: 4,
             height: 2,
             cells: vec![(1, 0), (2, 0), (3, 0), (0, 1), (1, 1), (2, 1)],
+            author: None,
+            comments: Vec::new(),
         }
     }
     
@@ -95,9 +475,12 @@ impl PatternLibrary {
         Pattern {
             name: "Beacon",
             description: "A period 2 oscillator",
+            category: PatternCategory::Oscillator,
             width: 4,
             height: 4,
             cells: vec![(0, 0), (1, 0), (0, 1), (3, 2), (2, 3), (3, 3)],
+            author: None,
+            comments: Vec::new(),
         }
     }
     
@@ -106,6 +489,7 @@ impl PatternLibrary {
         Pattern {
             name: "Pulsar",
             description: "A period 3 oscillator",
+            category: PatternCategory::Oscillator,
             width: 13,
             height: 13,
             cells: vec![
@@ -120,6 +504,8 @@ impl PatternLibrary {
                 (0, 10), (5, 10), (7, 10), (12, 10),
                 (2, 12), (3, 12), (4, 12), (8, 12), (9, 12), (10, 12),
             ],
+            author: None,
+            comments: Vec::new(),
         }
     }
     
@@ -128,6 +514,7 @@ impl PatternLibrary {
         Pattern {
             name: "Glider Gun",
             description: "Gosper's Glider Gun - produces gliders periodically",
+            category: PatternCategory::Gun,
             width: 36,
             height: 9,
             cells: vec![
@@ -141,6 +528,8 @@ impl PatternLibrary {
                 (11, 7), (15, 7),
                 (12, 8), (13, 8),
             ],
+            author: Some("Bill Gosper".to_string()),
+            comments: Vec::new(),
         }
     }
     
@@ -149,6 +538,7 @@ impl PatternLibrary {
         Pattern {
             name: "LWSS",
             description: "Lightweight Spaceship - moves across the grid",
+            category: PatternCategory::Spaceship,
             width: 5,
             height: 4,
             cells: vec![
@@ -157,6 +547,8 @@ impl PatternLibrary {
                 (0, 2), (4, 2),
                 (0, 3), (1, 3), (2, 3), (3, 3),
             ],
+            author: None,
+            comments: Vec::new(),
         }
     }
     
@@ -165,9 +557,12 @@ impl PatternLibrary {
         Pattern {
             name: "R-pentomino",
             description: "A methuselah that evolves for many generations",
+            category: PatternCategory::Methuselah,
             width: 3,
             height: 3,
             cells: vec![(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)],
+            author: None,
+            comments: Vec::new(),
         }
     }
     
@@ -176,9 +571,12 @@ impl PatternLibrary {
         Pattern {
             name: "Diehard",
             description: "A methuselah that vanishes after 130 generations",
+            category: PatternCategory::Methuselah,
             width: 8,
             height: 3,
             cells: vec![(6, 0), (0, 1), (1, 1), (1, 2), (5, 2), (6, 2), (7, 2)],
+            author: None,
+            comments: Vec::new(),
         }
     }
     
@@ -187,9 +585,165 @@ impl PatternLibrary {
         Pattern {
             name: "Acorn",
             description: "A methuselah that evolves for thousands of generations",
+            category: PatternCategory::Methuselah,
             width: 7,
             height: 3,
             cells: vec![(1, 0), (3, 1), (0, 2), (1, 2), (4, 2), (5, 2), (6, 2)],
+            author: Some("Charles Corderman".to_string()),
+            comments: Vec::new(),
+        }
+    }
+    
+    /// Pentadecathlon oscillator pattern
+    pub fn pentadecathlon() -> Pattern {
+        Pattern {
+            name: "Pentadecathlon",
+            description: "A period 15 oscillator",
+            category: PatternCategory::Oscillator,
+            width: 10,
+            height: 3,
+            cells: vec![
+                (2, 0), (7, 0),
+                (0, 1), (1, 1), (3, 1), (4, 1), (5, 1), (6, 1), (8, 1), (9, 1),
+                (2, 2), (7, 2),
+            ],
+            author: None,
+            comments: Vec::new(),
         }
     }
+    
+    /// Middleweight spaceship pattern
+    pub fn middleweight_spaceship() -> Pattern {
+        Pattern {
+            name: "MWSS",
+            description: "Middleweight Spaceship - moves across the grid",
+            category: PatternCategory::Spaceship,
+            width: 6,
+            height: 5,
+            cells: vec![
+                (3, 0),
+                (1, 1), (5, 1),
+                (0, 2),
+                (0, 3), (5, 3),
+                (0, 4), (1, 4), (2, 4), (3, 4), (4, 4),
+            ],
+            author: None,
+            comments: Vec::new(),
+        }
+    }
+    
+    /// Heavyweight spaceship pattern
+    pub fn heavyweight_spaceship() -> Pattern {
+        Pattern {
+            name: "HWSS",
+            description: "Heavyweight Spaceship - the largest of the standard orthogonal spaceships",
+            category: PatternCategory::Spaceship,
+            width: 7,
+            height: 5,
+            cells: vec![
+                (3, 0), (4, 0),
+                (1, 1), (6, 1),
+                (0, 2),
+                (0, 3), (6, 3),
+                (0, 4), (1, 4), (2, 4), (3, 4), (4, 4), (5, 4),
+            ],
+            author: None,
+            comments: Vec::new(),
+        }
+    }
+    
+    /// Queen Bee Shuttle oscillator pattern
+    pub fn queen_bee_shuttle() -> Pattern {
+        Pattern {
+            name: "Queen Bee Shuttle",
+            description: "A period 30 oscillator where a queen bee bounces between two blocks",
+            category: PatternCategory::Oscillator,
+            width: 22,
+            height: 7,
+            cells: vec![
+                (9, 0),
+                (7, 1), (9, 1),
+                (6, 2), (8, 2),
+                (0, 3), (1, 3), (5, 3), (8, 3), (20, 3), (21, 3),
+                (0, 4), (1, 4), (6, 4), (8, 4), (20, 4), (21, 4),
+                (7, 5), (9, 5),
+                (9, 6),
+            ],
+            author: Some("Bill Gosper".to_string()),
+            comments: Vec::new(),
+        }
+    }
+    
+    /// Block-laying switch engine pattern
+    pub fn switch_engine() -> Pattern {
+        Pattern {
+            name: "Switch Engine",
+            description: "Ten cells that become a block-laying switch engine, growing forever",
+            category: PatternCategory::Puffer,
+            width: 8,
+            height: 6,
+            cells: vec![
+                (6, 0),
+                (4, 1), (6, 1), (7, 1),
+                (4, 2), (6, 2),
+                (4, 3),
+                (2, 4),
+                (0, 5), (2, 5),
+            ],
+            author: Some("Charles Corderman".to_string()),
+            comments: Vec::new(),
+        }
+    }
+    
+    /// Gosper's second glider gun, built from two twin bees shuttles
+    pub fn p46_gun() -> Pattern {
+        Pattern {
+            name: "P46 Gun",
+            description: "Gosper's second gun - two twin bees shuttles that fire a glider every 46 generations",
+            category: PatternCategory::Gun,
+            width: 49,
+            height: 14,
+            cells: vec![
+                (31, 0),
+                (30, 1), (31, 1), (47, 1), (48, 1),
+                (29, 2), (30, 2), (47, 2), (48, 2),
+                (17, 3), (18, 3), (30, 3), (31, 3), (34, 3), (35, 3),
+                (0, 4), (1, 4), (17, 4), (19, 4),
+                (0, 5), (1, 5), (19, 5),
+                (17, 6), (18, 6), (19, 6),
+                (30, 7), (31, 7), (34, 7), (35, 7),
+                (29, 8), (30, 8), (47, 8), (48, 8),
+                (30, 9), (31, 9), (47, 9), (48, 9),
+                (17, 10), (18, 10), (19, 10), (31, 10),
+                (0, 11), (1, 11), (19, 11),
+                (0, 12), (1, 12), (17, 12), (19, 12),
+                (17, 13), (18, 13),
+            ],
+            author: Some("Bill Gosper".to_string()),
+            comments: Vec::new(),
+        }
+    }
+}
+
+/// Match the query as a case-insensitive subsequence of the name, preferring the match
+/// that starts earliest. Returns the start position and span of the match, or None.
+fn fuzzy_match(query: &str, name: &str) -> Option<(usize, usize)> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    
+    let Some(&first) = query.first() else {
+        return Some((0, 0));
+    };
+    
+    // Try each possible starting character and keep the earliest that completes,
+    // matching the rest greedily
+    (0..name.len())
+        .filter(|&start| name[start] == first)
+        .find_map(|start| {
+            let mut position = start;
+            for &c in &query[1..] {
+                position += 1 + name[position + 1..].iter().position(|&n| n == c)?;
+            }
+            Some((start, position - start))
+        })
 }
\ No newline at end of file
@@ -0,0 +1,256 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    
+    use crate::grid::Grid;
+    use crate::config::BoundaryType;
+    use crate::analyzer::{PatternAnalyzer, PatternKind};
+    use crate::patterns::{Pattern, PatternCategory, PatternLibrary, PlacementError};
+
+    fn snapshot(grid: &Grid) -> Vec<bool> {
+        let (width, height) = grid.dimensions();
+        (0..height).flat_map(|y| (0..width).map(move |x| grid.get(x, y))).collect()
+    }
+
+    #[test]
+    fn test_try_place_fits() {
+        let mut grid = Grid::new(10, 10, BoundaryType::Fixed);
+        let glider = PatternLibrary::glider();
+        
+        assert!(glider.try_place(&mut grid, 7, 7).is_ok());
+        assert_eq!(grid.count_alive(), 5);
+    }
+
+    #[test]
+    fn test_try_place_out_of_bounds_leaves_grid_untouched() {
+        let mut grid = Grid::new(10, 10, BoundaryType::Fixed);
+        grid.set(9, 9, true);
+        let glider = PatternLibrary::glider();
+        
+        let result = glider.try_place(&mut grid, 8, 7);
+        assert_eq!(result, Err(PlacementError::OutOfBounds {
+            x: 8,
+            y: 7,
+            pattern_size: (3, 3),
+            grid_size: (10, 10),
+        }));
+        
+        // Nothing was cleared or placed
+        assert!(grid.get(9, 9));
+        assert_eq!(grid.count_alive(), 1);
+    }
+
+    #[test]
+    fn test_library_patterns_have_no_duplicate_cells() {
+        for pattern in PatternLibrary::get_all_patterns() {
+            let unique: HashSet<(usize, usize)> = pattern.cells.iter().copied().collect();
+            assert_eq!(unique.len(), pattern.population(), "{} lists a cell more than once", pattern.name);
+        }
+    }
+
+    #[test]
+    fn test_library_patterns_fit_their_bounds() {
+        for pattern in PatternLibrary::get_all_patterns() {
+            assert!(!pattern.cells.is_empty(), "{} has no cells", pattern.name);
+            for &(x, y) in &pattern.cells {
+                assert!(x < pattern.width && y < pattern.height,
+                    "{} has cell ({}, {}) outside its {}x{} bounds", pattern.name, x, y, pattern.width, pattern.height);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pentadecathlon_has_period_15() {
+        let pattern = PatternLibrary::pentadecathlon();
+        let mut grid = Grid::new(30, 20, BoundaryType::Fixed);
+        pattern.place(&mut grid, 10, 8);
+        let initial = snapshot(&grid);
+        
+        for generation in 1..=15 {
+            grid.update();
+            assert_eq!(snapshot(&grid) == initial, generation == 15);
+        }
+    }
+
+    #[test]
+    fn test_p46_gun_has_period_46_and_fires_gliders() {
+        let pattern = PatternLibrary::p46_gun();
+        let mut grid = Grid::new(90, 50, BoundaryType::Fixed);
+        pattern.place(&mut grid, 30, 25);
+        let initial_population = grid.count_alive();
+        
+        // The gun's own cells, read back from its footprint
+        let footprint = |grid: &Grid| -> Vec<bool> {
+            (25..25 + pattern.height).flat_map(|y| (30..30 + pattern.width).map(move |x| grid.get(x, y))).collect()
+        };
+        let initial = footprint(&grid);
+        
+        for generation in 1..=46 {
+            grid.update();
+            assert_eq!(footprint(&grid) == initial, generation == 46);
+        }
+        
+        // Each period leaves one more glider flying away from the gun
+        assert_eq!(grid.count_alive(), initial_population + 5);
+        for _ in 0..46 {
+            grid.update();
+        }
+        assert_eq!(footprint(&grid), initial);
+        assert_eq!(grid.count_alive(), initial_population + 10);
+    }
+
+    #[test]
+    fn test_filter_by_type() {
+        let analyzer = PatternAnalyzer::new(200, (60, 60), BoundaryType::Wrap);
+        let groups = PatternLibrary::filter_by_type(&analyzer);
+        
+        // Every pattern lands in exactly one group
+        let total: usize = groups.values().map(Vec::len).sum();
+        assert_eq!(total, PatternLibrary::get_all_patterns().len());
+        
+        assert!(groups[&PatternKind::Spaceship].contains(&"Glider"));
+        assert!(groups[&PatternKind::Stable].contains(&"Blinker"));
+        assert!(groups[&PatternKind::Stable].contains(&"Pulsar"));
+        
+        // Patterns that do not fit the analyzer's grid are reported as unknown
+        let tiny = PatternAnalyzer::new(10, (5, 5), BoundaryType::Wrap);
+        let groups = PatternLibrary::filter_by_type(&tiny);
+        assert!(groups[&PatternKind::Unknown].contains(&"Pulsar"));
+    }
+
+    #[test]
+    fn test_place_wrapped_tiles_across_seam() {
+        let glider = PatternLibrary::glider();
+        
+        let mut grid = Grid::new(10, 8, BoundaryType::Wrap);
+        glider.place_wrapped(&mut grid, 9, 7);
+        assert_eq!(grid.count_alive(), glider.population());
+        for &(px, py) in &glider.cells {
+            assert!(grid.get((9 + px) % 10, (7 + py) % 8));
+        }
+        
+        // Negative positions wrap too
+        let mut negative = Grid::new(10, 8, BoundaryType::Wrap);
+        glider.place_wrapped(&mut negative, -1, -1);
+        assert_eq!(snapshot(&negative), snapshot(&grid));
+        
+        // Fixed grids keep clipping
+        let mut fixed = Grid::new(10, 8, BoundaryType::Fixed);
+        glider.place_wrapped(&mut fixed, 9, 7);
+        let inside = glider.cells.iter().filter(|&&(px, py)| 9 + px < 10 && 7 + py < 8).count();
+        assert_eq!(fixed.count_alive(), inside);
+    }
+    
+    #[test]
+    fn test_search_ranks_fuzzy_matches() {
+        let names = |query: &str| -> Vec<&'static str> {
+            PatternLibrary::search(query).iter().map(|pattern| pattern.name).collect()
+        };
+        
+        // Subsequences match regardless of case, earliest start first
+        assert_eq!(names("GUN"), vec!["Glider Gun", "P46 Gun"]);
+        assert_eq!(names("ws"), vec!["LWSS", "MWSS", "HWSS"]);
+        assert_eq!(names("glr")[0], "Glider");
+        assert!(names("gun ").is_empty());
+        
+        // Among patterns starting with the query, the tighter match wins
+        assert_eq!(names("gl")[..2], ["Glider", "Glider Gun"]);
+        
+        // An empty query lists the whole library
+        assert_eq!(names("").len(), PatternLibrary::get_all_patterns().len());
+    }
+    
+    #[test]
+    fn test_recommended_grid_size() {
+        // An oscillator only needs a margin around its own bounds
+        assert_eq!(PatternLibrary::blinker().recommended_grid_size(), (11, 11));
+        
+        // A gun needs room for its glider stream as well as itself
+        let gun = PatternLibrary::glider_gun();
+        let (width, height) = gun.recommended_grid_size();
+        assert!(width > gun.width + 20 && height > gun.height + 20, "{}x{}", width, height);
+        
+        // Orthogonal spaceships need room along their direction of travel
+        let (width, height) = PatternLibrary::lightweight_spaceship().recommended_grid_size();
+        assert!(width > 2 * height);
+    }
+    
+    #[test]
+    fn test_get_by_name_ignores_separators() {
+        assert_eq!(PatternLibrary::get_by_name("glider_gun").map(|p| p.name), Some("Glider Gun"));
+        assert_eq!(PatternLibrary::get_by_name("R_PENTOMINO").map(|p| p.name), Some("R-pentomino"));
+        assert!(PatternLibrary::get_by_name("glider gunner").is_none());
+    }
+    
+    #[test]
+    fn test_tight_bounds_and_centering() {
+        // Declared 6x5 with an empty column and row around the cells
+        let padded = Pattern {
+            name: "Padded Blinker",
+            description: "A blinker with padding",
+            category: PatternCategory::Oscillator,
+            width: 6,
+            height: 5,
+            cells: vec![(2, 3), (3, 3), (4, 3)],
+            author: None,
+            comments: Vec::new(),
+        };
+        assert_eq!(padded.tight_bounds(), (2, 3, 4, 3));
+        assert_eq!(padded.population(), 3);
+        
+        // The live cells end up around the middle of the grid, not the declared box
+        let mut grid = Grid::new(11, 11, BoundaryType::Fixed);
+        let (x, y) = padded.centered_position(11, 11);
+        padded.place(&mut grid, x, y);
+        assert!(grid.get(4, 5) && grid.get(5, 5) && grid.get(6, 5));
+        
+        // Too large for the grid: placed at the origin rather than underflowing
+        assert_eq!(PatternLibrary::glider_gun().centered_position(10, 4), (0, 0));
+    }
+    
+    #[test]
+    fn test_ascii_preview() {
+        assert_eq!(PatternLibrary::glider().ascii_preview(10, 10), " #\n  #\n###");
+        assert_eq!(PatternLibrary::blinker().ascii_preview(1, 3), "#\n#\n#");
+        
+        // The 36x9 gun is halved to fit 18 columns, and no block with a live cell is dropped
+        let gun = PatternLibrary::glider_gun();
+        let preview = gun.ascii_preview(18, 10);
+        let lines: Vec<&str> = preview.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|line| line.chars().count() <= 18));
+        assert_eq!(lines[2], "#    # ## ###");
+        assert_eq!(PatternLibrary::glider().ascii_preview(0, 5), "");
+    }
+    
+    #[test]
+    fn test_patterns_by_category() {
+        let names = |category| -> Vec<&'static str> {
+            PatternLibrary::by_category(category).iter().map(|pattern| pattern.name).collect()
+        };
+        
+        assert_eq!(names(PatternCategory::Spaceship), vec!["Glider", "LWSS", "MWSS", "HWSS"]);
+        assert_eq!(names(PatternCategory::Methuselah), vec!["R-pentomino", "Diehard", "Acorn"]);
+        assert_eq!(names(PatternCategory::Gun), vec!["Glider Gun", "P46 Gun"]);
+        assert_eq!(names(PatternCategory::Puffer), vec!["Switch Engine"]);
+        assert_eq!(PatternLibrary::pulsar().category, PatternCategory::Oscillator);
+        assert_eq!(PatternCategory::StillLife.to_string(), "still life");
+    }
+    
+    #[test]
+    fn test_would_collide_checks_the_cleared_rectangle() {
+        let mut grid = Grid::new(10, 10, BoundaryType::Fixed);
+        let glider = PatternLibrary::glider();
+        assert!(!glider.would_collide(&grid, 2, 2));
+        
+        // A live cell in the glider's empty top-left corner would still be cleared
+        grid.set(2, 2, true);
+        assert!(glider.would_collide(&grid, 2, 2));
+        assert!(!glider.would_collide(&grid, 3, 2));
+        
+        // Cells past the edge are never placed, so they cannot collide
+        grid.set(9, 9, true);
+        assert!(glider.would_collide(&grid, 8, 8));
+        assert!(!glider.would_collide(&grid, 10, 10));
+    }
+}
\ No newline at end of file
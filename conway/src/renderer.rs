@@ -1,26 +1,245 @@
 use crossterm::{
     cursor::{MoveTo, Hide, Show},
     execute,
-    style::{Color, Print, SetBackgroundColor, SetForegroundColor, ResetColor},
+    style::{Attribute, Color, Print, SetAttribute, SetBackgroundColor, SetForegroundColor, ResetColor},
     terminal::{Clear, ClearType},
 };
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
 use std::io::Write;
 use std::time::Instant;
 
 use crate::grid::Grid;
-use crate::config::{CellTheme, ColorTheme};
+use crate::config::{CellTheme, ColorTheme, CursorStyle};
+
+// Fully saturated hues around the color wheel, red through violet
+const SPECTRUM_STOPS: [[u8; 3]; 6] = [
+    [255, 0, 0],
+    [255, 255, 0],
+    [0, 255, 0],
+    [0, 255, 255],
+    [0, 0, 255],
+    [255, 0, 255],
+];
+
+// Matplotlib's viridis colormap sampled at nine evenly spaced points
+const VIRIDIS_STOPS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [71, 45, 123],
+    [59, 82, 139],
+    [44, 114, 142],
+    [33, 145, 140],
+    [40, 174, 128],
+    [94, 201, 98],
+    [173, 220, 48],
+    [253, 231, 37],
+];
+
+// Linearly interpolate between evenly spaced color stops, with t from 0.0 to 1.0
+fn gradient_color(stops: &[[u8; 3]], t: f32) -> Color {
+    let scaled = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let index = (scaled as usize).min(stops.len() - 2);
+    let fraction = scaled - index as f32;
+    
+    let channel = |i: usize| {
+        let (start, end) = (stops[index][i] as f32, stops[index + 1][i] as f32);
+        (start + (end - start) * fraction).round() as u8
+    };
+    
+    Color::Rgb { r: channel(0), g: channel(1), b: channel(2) }
+}
+
+// Draw values as a row of block characters scaled between the smallest and largest value,
+// with a flat series drawn at mid height
+pub fn sparkline(values: impl Iterator<Item = usize> + Clone) -> String {
+    let min = values.clone().min().unwrap_or(0);
+    let max = values.clone().max().unwrap_or(0);
+    let top = SPARKLINE_GLYPHS.len() - 1;
+    
+    values
+        .map(|value| {
+            let level = if max == min {
+                top / 2
+            } else {
+                (value - min) * top / (max - min)
+            };
+            SPARKLINE_GLYPHS[level]
+        })
+        .collect()
+}
+
+// The first `columns` characters of a line of text, so a status or help line never wraps
+// onto the rows below it
+fn clip(text: &str, columns: usize) -> String {
+    text.chars().take(columns).collect()
+}
+
+// Move a wrapping viewport the shorter way around the torus just far enough to show the cursor
+fn wrapped_viewport(viewport: usize, cursor: usize, visible: usize, length: usize) -> usize {
+    if length == 0 {
+        return viewport;
+    }
+    
+    let offset = (cursor + length - viewport) % length;
+    if offset < visible {
+        return viewport;
+    }
+    
+    // Either slide back so the cursor is the first visible cell, or forward so it is the last
+    let back = length - offset;
+    let forward = offset + 1 - visible;
+    if back < forward {
+        cursor
+    } else {
+        (viewport + forward) % length
+    }
+}
+
+// Unchanged generations after which the status bar reports the grid as stabilized
+const STABILIZED_THRESHOLD: usize = 10;
+
+// Largest block of cells, per side, that one character stands for when zoomed out
+const MAX_OVERVIEW: usize = 16;
+
+// Shading for zoomed-out blocks from sparsest to fullest; any live cell shows at least the lightest
+const OVERVIEW_GLYPHS: [&str; 4] = ["░", "▒", "▓", "█"];
+
+// Color of the faint mark drawn for dead cells
+const DEAD_CELL_COLOR: Color = Color::DarkGrey;
+
+// Color of the cells a pattern being placed would bring to life
+const PREVIEW_COLOR: Color = Color::DarkCyan;
+
+// Background of the cells around the cursor that a larger brush edits
+const BRUSH_COLOR: Color = Color::DarkGrey;
+
+// Bars of the population sparkline, from lowest to highest
+const SPARKLINE_GLYPHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Terminal rows below the grid taken by the status bar and help lines
+pub const STATUS_ROWS: usize = 5;
+
+// Largest grid that fits a terminal of the given size when every cell is printed
+// `cell_chars` characters wide, leaving room for the status bar and help lines
+pub fn grid_size_for_terminal(columns: usize, rows: usize, cell_chars: usize) -> (usize, usize) {
+    ((columns / cell_chars.max(1)).max(1), rows.saturating_sub(STATUS_ROWS).max(1))
+}
+
+// The text a renderer's output leaves on a blank screen, one line per row with trailing spaces
+// trimmed. Cursor moves are followed and other escape sequences, such as colors, are dropped,
+// so the drawn frame can be checked as plain characters.
+pub fn screen_text(output: &[u8]) -> String {
+    let text = String::from_utf8_lossy(output);
+    let mut rows: Vec<Vec<char>> = Vec::new();
+    let (mut row, mut column) = (0, 0);
+    
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            if rows.len() <= row {
+                rows.resize(row + 1, Vec::new());
+            }
+            let line = &mut rows[row];
+            if line.len() <= column {
+                line.resize(column + 1, ' ');
+            }
+            line[column] = c;
+            column += 1;
+            continue;
+        }
+        
+        // A control sequence: ESC [ parameters final byte. Only MoveTo (row;column H) matters.
+        if chars.next_if_eq(&'[').is_none() {
+            continue;
+        }
+        let mut parameters = String::new();
+        for c in chars.by_ref() {
+            if ('@'..='~').contains(&c) {
+                if c == 'H' {
+                    let mut position = parameters.split(';').map(|n| n.parse::<usize>().unwrap_or(1).max(1) - 1);
+                    row = position.next().unwrap_or(0);
+                    column = position.next().unwrap_or(0);
+                }
+                break;
+            }
+            parameters.push(c);
+        }
+    }
+    
+    rows.iter()
+        .map(|line| line.iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Everything shown in the status bar besides what the renderer tracks itself
+pub struct StatusInfo<'a> {
+    pub game_state: &'a str,
+    pub generation: usize,
+    pub speed: usize,
+    pub population_delta: isize,
+    pub stable_generations: usize,
+    pub rule: &'a str,      // Active rule in its notation, e.g. "B3/S23"
+    pub auto_pause: Option<&'a str>,  // Auto-pause state when armed: waiting, or why it paused
+    pub notice: Option<&'a str>,      // Outcome of the last screenshot or save, if there is one to show
+    pub population_trend: &'a VecDeque<usize>,  // Recent populations, oldest first
+    pub random_density: f64,  // Share of cells r and R bring to life, 0.0 to 1.0
+}
+
+// What a screen cell shows, resolved to text for the cell theme and aspect when drawn
+#[derive(Clone, Copy, PartialEq)]
+enum Glyph {
+    Dead,
+    Alive,
+    Shade(usize),  // Index into OVERVIEW_GLYPHS
+}
+
+// One cell of a drawn frame, compared against the last frame to find what needs rewriting
+#[derive(Clone, Copy, PartialEq)]
+struct FrameCell {
+    glyph: Glyph,
+    color: Option<Color>,  // Foreground of a live cell; None draws the dim dead mark
+    cursor: bool,
+    beside_cursor: bool,   // Partly covered by an outline cursor bar
+    brush: bool,           // Under the brush footprint around the cursor
+}
+
+// Where the renderer is looking: the cursor, the top-left visible cell and the zoom level,
+// saved with a session so the view comes back as it was left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewState {
+    pub cursor: (usize, usize),
+    pub viewport: (usize, usize),
+    pub zoom: usize,
+    pub overview: usize,
+}
 
 pub struct Renderer<W: Write> {
     output: W,
     width: usize,
     height: usize,
+    screen_width: usize,   // Terminal columns available for cells
+    screen_height: usize,  // Terminal rows available for cells, above the status bar
     cell_theme: CellTheme,
     color_theme: ColorTheme,
     zoom: usize,
+    overview: usize,      // Cells per side of the block each character stands for; 1 draws single cells
     viewport_x: usize,
     viewport_y: usize,
     cursor_x: usize,
     cursor_y: usize,
+    cell_aspect: usize,   // Terminal characters printed per cell
+    cursor_style: CursorStyle,
+    wrap: bool,           // Pan and move the cursor around the torus instead of stopping at the edges
+    highlight_changes: bool,  // Color births and survivors differently
+    mark_dying: bool,         // Color live cells that will die in the next generation
+    dead_marks: bool,         // Draw dead cells as the theme's faint mark instead of a blank
+    last_grid: Option<Grid>,      // Grid as of the last render
+    previous_grid: Option<Grid>,  // Grid before the last change, for telling births from survivors
+    incremental: bool,        // Rewrite only the cells that changed instead of clearing the screen
+    last_frame: Option<(usize, Vec<Option<FrameCell>>)>,  // Columns and cells of the frame on screen
+    preview: HashSet<(usize, usize)>,  // Cells drawn as alive in the preview color, without being in the grid
+    brush: HashSet<(usize, usize)>,    // Cells a toggle at the cursor would edit, drawn on a shaded background
     fps_counter: FpsCounter,
 }
 
@@ -68,19 +287,129 @@ impl<W: Write> Renderer<W> {
             output,
             width,
             height,
+            screen_width: width,
+            screen_height: height,
             cell_theme,
             color_theme,
             zoom: 1,
+            overview: 1,
             viewport_x: 0,
             viewport_y: 0,
             cursor_x: width / 2,
             cursor_y: height / 2,
+            cell_aspect: 1,
+            cursor_style: CursorStyle::Block,
+            wrap: false,
+            highlight_changes: false,
+            mark_dying: false,
+            dead_marks: false,
+            last_grid: None,
+            previous_grid: None,
+            incremental: false,
+            last_frame: None,
+            preview: HashSet::new(),
+            brush: HashSet::new(),
             fps_counter: FpsCounter::new(),
         }
     }
 
+    // Print each cell this many characters wide. Terminal characters are about twice as tall
+    // as they are wide, so 2 makes cells look square.
+    pub fn set_cell_aspect(&mut self, chars_wide: usize) {
+        self.cell_aspect = chars_wide.max(1);
+        self.last_frame = None;
+        self.ensure_cursor_in_viewport();
+    }
+
+    // Switch to another color theme for live cells from the next frame on
+    pub fn set_color_theme(&mut self, theme: ColorTheme) {
+        self.color_theme = theme;
+    }
+
+    pub fn color_theme(&self) -> &ColorTheme {
+        &self.color_theme
+    }
+
+    // Change how the cell under the cursor is marked. Block hides whether the cell is alive;
+    // the other styles leave it visible.
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.cursor_style = style;
+        self.last_frame = None;
+    }
+
+    pub fn cursor_style(&self) -> CursorStyle {
+        self.cursor_style
+    }
+
+    // Let the viewport and cursor wrap around the grid edges, for grids with wrapping boundaries.
+    // Fixed grids keep the default clamped behavior.
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        self.ensure_cursor_in_viewport();
+    }
+
+    // Color cells born since the grid last changed green and surviving cells white,
+    // instead of using the color theme
+    pub fn set_highlight_changes(&mut self, enabled: bool) {
+        self.highlight_changes = enabled;
+        self.last_grid = None;
+        self.previous_grid = None;
+    }
+
+    // Color live cells red when the rules will kill them in the next generation
+    pub fn set_mark_dying(&mut self, enabled: bool) {
+        self.mark_dying = enabled;
+    }
+
+    // Draw dead cells as the cell theme's faint mark, e.g. `.` for classic, instead of leaving
+    // them blank
+    pub fn set_dead_marks(&mut self, enabled: bool) {
+        self.dead_marks = enabled;
+        self.last_frame = None;
+    }
+
+    // Redraw only the cells that changed since the last frame, moving to each one, instead
+    // of clearing the screen and drawing every cell. Cuts flicker and output on large grids,
+    // but anything else drawn over the grid must go through print_line so it gets repainted.
+    pub fn set_incremental(&mut self, enabled: bool) {
+        self.incremental = enabled;
+        self.last_frame = None;
+    }
+
+    // Draw these cells as alive in a dim preview color over whatever the grid holds there,
+    // e.g. to show where a pattern would land before it is placed. An empty list clears it.
+    // Only drawn at single-cell zoom levels, not in the zoomed-out overview.
+    pub fn set_preview(&mut self, cells: Vec<(usize, usize)>) {
+        self.preview = cells.into_iter().collect();
+    }
+
+    // Shade the background of these cells to show the footprint of a brush larger than one
+    // cell. Like the preview, it is only drawn at single-cell zoom levels.
+    pub fn set_brush_footprint(&mut self, cells: Vec<(usize, usize)>) {
+        self.brush = cells.into_iter().collect();
+    }
+
+    // Fit the view to a terminal of the given size, e.g. after it was resized. Until this is
+    // called the renderer assumes the terminal has room for the whole grid.
+    pub fn set_screen_size(&mut self, columns: usize, rows: usize) {
+        self.screen_width = columns;
+        self.screen_height = rows.saturating_sub(STATUS_ROWS);
+        self.last_frame = None;
+        self.ensure_cursor_in_viewport();
+    }
+
+    // Number of cells that fit on screen horizontally and vertically at the current zoom,
+    // never more than the grid has
+    fn visible_size(&self) -> (usize, usize) {
+        (
+            (self.screen_width * self.overview / (self.zoom * self.cell_aspect)).min(self.width),
+            (self.screen_height * self.overview / self.zoom).min(self.height),
+        )
+    }
+
     // Prepare terminal for rendering
     pub fn init(&mut self) -> crossterm::Result<()> {
+        self.last_frame = None;
         execute!(
             self.output,
             Hide,
@@ -88,8 +417,14 @@ impl<W: Write> Renderer<W> {
         )
     }
 
+    // Give up the renderer and get back the writer it drew to
+    pub fn into_output(self) -> W {
+        self.output
+    }
+
     // Cleanup terminal
     pub fn cleanup(&mut self) -> crossterm::Result<()> {
+        self.last_frame = None;
         execute!(
             self.output,
             ResetColor,
@@ -104,12 +439,18 @@ impl<W: Write> Renderer<W> {
         let new_x = self.cursor_x as isize + dx;
         let new_y = self.cursor_y as isize + dy;
 
-        if new_x >= 0 && new_x < self.width as isize {
-            self.cursor_x = new_x as usize;
-        }
+        if self.wrap && self.width > 0 && self.height > 0 {
+            // Step off one edge onto the opposite one
+            self.cursor_x = new_x.rem_euclid(self.width as isize) as usize;
+            self.cursor_y = new_y.rem_euclid(self.height as isize) as usize;
+        } else {
+            if new_x >= 0 && new_x < self.width as isize {
+                self.cursor_x = new_x as usize;
+            }
 
-        if new_y >= 0 && new_y < self.height as isize {
-            self.cursor_y = new_y as usize;
+            if new_y >= 0 && new_y < self.height as isize {
+                self.cursor_y = new_y as usize;
+            }
         }
 
         // Adjust viewport if cursor is outside
@@ -118,8 +459,17 @@ impl<W: Write> Renderer<W> {
 
     // Ensure cursor is visible in the viewport
     fn ensure_cursor_in_viewport(&mut self) {
-        let visible_width = self.width / self.zoom;
-        let visible_height = self.height / self.zoom;
+        let (visible_width, visible_height) = self.visible_size();
+
+        if self.wrap {
+            self.viewport_x = wrapped_viewport(self.viewport_x, self.cursor_x, visible_width, self.width);
+            self.viewport_y = wrapped_viewport(self.viewport_y, self.cursor_y, visible_height, self.height);
+            return;
+        }
+
+        // A view that grew, e.g. with the terminal, may now reach past the far edges
+        self.viewport_x = self.viewport_x.min(self.width.saturating_sub(visible_width));
+        self.viewport_y = self.viewport_y.min(self.height.saturating_sub(visible_height));
 
         if self.cursor_x < self.viewport_x {
             self.viewport_x = self.cursor_x;
@@ -136,12 +486,18 @@ impl<W: Write> Renderer<W> {
 
     // Move viewport
     pub fn pan_viewport(&mut self, dx: isize, dy: isize) {
-        let visible_width = self.width / self.zoom;
-        let visible_height = self.height / self.zoom;
+        let (visible_width, visible_height) = self.visible_size();
 
         let new_x = self.viewport_x as isize + dx;
         let new_y = self.viewport_y as isize + dy;
 
+        // Around a torus the viewport can keep going in any direction
+        if self.wrap && self.width > 0 && self.height > 0 {
+            self.viewport_x = new_x.rem_euclid(self.width as isize) as usize;
+            self.viewport_y = new_y.rem_euclid(self.height as isize) as usize;
+            return;
+        }
+
         if new_x >= 0 && new_x + visible_width as isize <= self.width as isize {
             self.viewport_x = new_x as usize;
         }
@@ -151,18 +507,33 @@ impl<W: Write> Renderer<W> {
         }
     }
 
-    // Change zoom level
+    // Change zoom level. Zooming out past 1x switches to an overview where each character
+    // stands for a block of cells, doubling the block size at every step.
     pub fn zoom(&mut self, delta: isize) {
-        let _old_zoom = self.zoom;
+        if self.zoom == 1 && (delta < 0 || self.overview > 1) {
+            self.overview = if delta < 0 {
+                (self.overview * 2).min(MAX_OVERVIEW)
+            } else {
+                self.overview / 2
+            };
+            self.ensure_cursor_in_viewport();
+            return;
+        }
         
         // Update zoom (min 1, max 10)
-        let new_zoom = (self.zoom as isize + delta).max(1).min(10) as usize;
+        let new_zoom = (self.zoom as isize + delta).clamp(1, 10) as usize;
         if new_zoom != self.zoom {
             self.zoom = new_zoom;
             
             // Adjust viewport to keep cursor position stable
-            let visible_width_new = self.width / new_zoom;
-            let visible_height_new = self.height / new_zoom;
+            let (visible_width_new, visible_height_new) = self.visible_size();
+            
+            // Center on cursor, wrapping around the torus if needed
+            if self.wrap {
+                self.viewport_x = (self.cursor_x + self.width - visible_width_new / 2) % self.width.max(1);
+                self.viewport_y = (self.cursor_y + self.height - visible_height_new / 2) % self.height.max(1);
+                return;
+            }
             
             // Center on cursor
             self.viewport_x = (self.cursor_x as isize - (visible_width_new / 2) as isize).max(0) as usize;
@@ -180,6 +551,7 @@ impl<W: Write> Renderer<W> {
     // Reset zoom and center viewport
     pub fn reset_view(&mut self) {
         self.zoom = 1;
+        self.overview = 1;
         self.viewport_x = 0;
         self.viewport_y = 0;
     }
@@ -189,6 +561,34 @@ impl<W: Write> Renderer<W> {
         (self.cursor_x, self.cursor_y)
     }
 
+    // Get the grid coordinates of the top-left visible cell
+    pub fn get_viewport_pos(&self) -> (usize, usize) {
+        (self.viewport_x, self.viewport_y)
+    }
+
+    // Current cursor, viewport and zoom, to be restored later with set_view_state
+    pub fn view_state(&self) -> ViewState {
+        ViewState {
+            cursor: (self.cursor_x, self.cursor_y),
+            viewport: (self.viewport_x, self.viewport_y),
+            zoom: self.zoom,
+            overview: self.overview,
+        }
+    }
+
+    // Put the cursor, viewport and zoom back as saved, kept within the grid and the zoom
+    // limits, with the viewport moved if needed to show the cursor
+    pub fn set_view_state(&mut self, view: ViewState) {
+        self.cursor_x = view.cursor.0.min(self.width.saturating_sub(1));
+        self.cursor_y = view.cursor.1.min(self.height.saturating_sub(1));
+        self.viewport_x = view.viewport.0.min(self.width.saturating_sub(1));
+        self.viewport_y = view.viewport.1.min(self.height.saturating_sub(1));
+        self.zoom = view.zoom.clamp(1, 10);
+        self.overview = if self.zoom == 1 { view.overview.clamp(1, MAX_OVERVIEW) } else { 1 };
+        self.last_frame = None;
+        self.ensure_cursor_in_viewport();
+    }
+
     // Get cell color based on theme and position
     fn get_cell_color(&self, x: usize, y: usize) -> Color {
         match self.color_theme {
@@ -207,97 +607,401 @@ impl<W: Write> Renderer<W> {
                     _ => Color::White,
                 }
             }
+            ColorTheme::Spectrum => gradient_color(&SPECTRUM_STOPS, self.diagonal_position(x, y)),
+            ColorTheme::Viridis => gradient_color(&VIRIDIS_STOPS, self.diagonal_position(x, y)),
+        }
+    }
+
+    // Color of a live cell, taking the birth/survival and dying highlights into account
+    fn live_cell_color(&self, grid: &Grid, x: usize, y: usize) -> Color {
+        if self.mark_dying && !grid.survives(x, y) {
+            return Color::Red;
         }
+        
+        if self.highlight_changes {
+            let born = self.previous_grid.as_ref().is_some_and(|previous| !previous.get(x, y));
+            return if born { Color::Green } else { Color::White };
+        }
+        
+        self.get_cell_color(x, y)
+    }
+
+    // Position of a cell along the grid's diagonal, from 0.0 at the top left to 1.0 at the bottom right
+    fn diagonal_position(&self, x: usize, y: usize) -> f32 {
+        let span = (self.width + self.height).saturating_sub(2).max(1);
+        (x + y) as f32 / span as f32
     }
 
     // Render the grid
-    pub fn render(&mut self, grid: &Grid, game_state: &str, generation: usize, speed: usize) -> crossterm::Result<()> {
+    pub fn render(&mut self, grid: &Grid, status: &StatusInfo) -> crossterm::Result<()> {
         self.fps_counter.update();
         
+        let visible_height = self.render_grid(grid)?;
+        
+        // Render status bar
+        let grid_stats = grid.stats();
+        let fps = self.fps_counter.get_fps();
+        let auto_pause = status.auto_pause
+            .map(|state| format!(" | Auto-pause: {}", state))
+            .unwrap_or_default();
+        let notice = status.notice
+            .map(|notice| format!(" | {}", notice))
+            .unwrap_or_default();
+        let zoom = if self.overview > 1 {
+            format!("1/{}x", self.overview)
+        } else {
+            format!("{}x", self.zoom)
+        };
+        let stability = if status.stable_generations >= STABILIZED_THRESHOLD {
+            format!("{} (stabilized)", status.stable_generations)
+        } else {
+            status.stable_generations.to_string()
+        };
+        
         execute!(
             self.output,
-            Clear(ClearType::All),
-            MoveTo(0, 0)
+            MoveTo(0, visible_height as u16 + 1),
+            Print(clip(&format!(
+                "Status: {}{}{} | Rule: {} | Gen: {} | Pop: {} ({:+}, {:.1}%) | Stable: {} | FPS: {:.1} | Speed: {} | Random: {:.0}% | Zoom: {} | Cursor: ({}, {})",
+                status.game_state, auto_pause, notice, status.rule, status.generation, grid_stats.population, status.population_delta,
+                grid_stats.density * 100.0, stability,
+                fps, status.speed, status.random_density * 100.0, zoom, self.cursor_x, self.cursor_y
+            ), self.screen_width)),
+            Clear(ClearType::UntilNewLine)
         )?;
         
+        // Population sparkline, colored by whether the window ends higher or lower than it starts
+        if !status.population_trend.is_empty() {
+            let trend = status.population_trend.iter().copied();
+            let label = clip("Trend: ", self.screen_width);
+            let bars = clip(&sparkline(trend), self.screen_width - label.chars().count());
+            let color = match (status.population_trend.front(), status.population_trend.back()) {
+                (Some(first), Some(last)) if last > first => Color::Green,
+                (Some(first), Some(last)) if last < first => Color::Red,
+                _ => Color::Yellow,
+            };
+            execute!(
+                self.output,
+                MoveTo(0, visible_height as u16 + 2),
+                Print(label),
+                SetForegroundColor(color),
+                Print(bars),
+                ResetColor,
+                Clear(ClearType::UntilNewLine)
+            )?;
+        }
+        
+        // Render help, cut to the terminal width so it never spills past the rows set aside for it
+        execute!(
+            self.output,
+            MoveTo(0, visible_height as u16 + 3),
+            Print(clip("Controls: hjkl-move | Space-toggle | Shift+Space-glider | Ctrl+Space-random | Enter-pause/resume", self.screen_width))
+        )?;
+        
+        execute!(
+            self.output,
+            MoveTo(0, visible_height as u16 + 4),
+            Print(clip("          r-randomize | R-symmetric | c-clear | C-colors | /-find pattern | s-save pattern | p-screenshot | [/]-brush size | {/}-random density | a-auto-pause | o-cursor style | Q-record macro | @-play macro | 0-9-speed | +/--zoom | Arrows-pan | z-reset view | q-quit", self.screen_width))
+        )?;
+        
+        Ok(())
+    }
+    
+    // Draw the visible part of the grid, returning the number of rows drawn
+    pub fn render_grid(&mut self, grid: &Grid) -> crossterm::Result<usize> {
         let (grid_width, grid_height) = grid.dimensions();
-        let visible_width = self.width / self.zoom;
-        let visible_height = self.height / self.zoom;
+        let (visible_width, visible_height) = self.visible_size();
+        
+        // Adjust viewport if necessary. A wrapping viewport may start anywhere
+        // and continues across the edges.
+        let (viewport_x, viewport_y) = if self.wrap {
+            (self.viewport_x, self.viewport_y)
+        } else {
+            let max_viewport_x = grid_width.saturating_sub(visible_width);
+            let max_viewport_y = grid_height.saturating_sub(visible_height);
+            (self.viewport_x.min(max_viewport_x), self.viewport_y.min(max_viewport_y))
+        };
         
-        // Adjust viewport if necessary
-        let max_viewport_x = grid_width.saturating_sub(visible_width);
-        let max_viewport_y = grid_height.saturating_sub(visible_height);
+        // Remember the grid before each change so births can be told apart from survivors
+        if self.highlight_changes {
+            let changed = self.last_grid.as_ref().is_none_or(|last| last != grid);
+            if changed {
+                self.previous_grid = self.last_grid.replace(grid.clone());
+            }
+        }
         
-        let viewport_x = self.viewport_x.min(max_viewport_x);
-        let viewport_y = self.viewport_y.min(max_viewport_y);
+        if self.overview > 1 {
+            return self.render_overview(grid, viewport_x, viewport_y, visible_width, visible_height);
+        }
         
-        // Render visible cells
+        // Work out what every visible cell shows
+        let mut frame = Vec::with_capacity(visible_width * visible_height);
+        let mut cursor_position = None;
         for vy in 0..visible_height {
             for vx in 0..visible_width {
-                let x = viewport_x + vx;
-                let y = viewport_y + vy;
+                let (mut x, mut y) = (viewport_x + vx, viewport_y + vy);
+                if self.wrap && grid_width > 0 && grid_height > 0 {
+                    x %= grid_width;
+                    y %= grid_height;
+                }
                 
                 if x >= grid_width || y >= grid_height {
+                    frame.push(None);
                     continue;
                 }
                 
                 let is_cursor = x == self.cursor_x && y == self.cursor_y;
+                let is_preview = self.preview.contains(&(x, y));
                 let is_alive = grid.get(x, y);
+                if is_cursor {
+                    cursor_position = Some((vx, vy));
+                }
                 
-                let cell_char = if is_alive {
-                    self.cell_theme.alive_cell()
+                let color = if is_preview {
+                    Some(PREVIEW_COLOR)
                 } else {
-                    self.cell_theme.dead_cell()
+                    is_alive.then(|| self.live_cell_color(grid, x, y))
                 };
+                frame.push(Some(FrameCell {
+                    glyph: if is_alive || is_preview { Glyph::Alive } else { Glyph::Dead },
+                    color,
+                    cursor: is_cursor,
+                    beside_cursor: false,
+                    brush: self.brush.contains(&(x, y)),
+                }));
+            }
+        }
+        
+        self.draw_frame(frame, visible_width, cursor_position)?;
+        Ok(visible_height)
+    }
+    
+    // Put a frame of cells on screen, `columns` cells to a row. The first frame, and every
+    // frame when not incremental, clears the screen and draws all of it; later incremental
+    // frames only rewrite the cells that differ from the frame before.
+    fn draw_frame(
+        &mut self,
+        mut frame: Vec<Option<FrameCell>>,
+        columns: usize,
+        cursor_position: Option<(usize, usize)>,
+    ) -> crossterm::Result<()> {
+        let columns = columns.max(1);
+        
+        // Outline bars are drawn over the cursor's neighbors, so those need repainting once it moves on
+        if let (Some((column, row)), CursorStyle::Outline) = (cursor_position, self.cursor_style) {
+            for neighbor in [column.checked_sub(1), Some(column + 1)].into_iter().flatten() {
+                if let Some(Some(cell)) = frame.get_mut(row * columns + neighbor).filter(|_| neighbor < columns) {
+                    cell.beside_cursor = true;
+                }
+            }
+        }
+        
+        // Widen each glyph to the configured number of characters
+        let dead = if self.dead_marks { self.cell_theme.dead_mark() } else { self.cell_theme.dead_cell() };
+        let dead = dead.repeat(self.cell_aspect);
+        let alive = self.cell_theme.alive_cell().repeat(self.cell_aspect);
+        let shades: Vec<String> = OVERVIEW_GLYPHS.iter().map(|glyph| glyph.repeat(self.cell_aspect)).collect();
+        let blank = " ".repeat(self.cell_aspect);
+        let text = |glyph: Glyph| match glyph {
+            Glyph::Dead => dead.as_str(),
+            Glyph::Alive => alive.as_str(),
+            Glyph::Shade(level) => shades[level].as_str(),
+        };
+        
+        let previous = self.last_frame.take()
+            .filter(|(last_columns, last)| self.incremental && *last_columns == columns && last.len() == frame.len());
+        match &previous {
+            Some((_, last)) => {
+                for (index, (cell, old)) in frame.iter().zip(last).enumerate() {
+                    if cell == old {
+                        continue;
+                    }
+                    execute!(self.output, MoveTo(((index % columns) * self.cell_aspect) as u16, (index / columns) as u16))?;
+                    match cell {
+                        Some(cell) => self.print_frame_cell(cell, text(cell.glyph))?,
+                        None => execute!(self.output, Print(&blank))?,
+                    }
+                }
+            },
+            None => {
+                execute!(self.output, Clear(ClearType::All))?;
+                for (row, cells) in frame.chunks(columns).enumerate() {
+                    execute!(self.output, MoveTo(0, row as u16))?;
+                    for cell in cells.iter().flatten() {
+                        self.print_frame_cell(cell, text(cell.glyph))?;
+                    }
+                }
+            },
+        }
+        
+        if let Some((column, row)) = cursor_position {
+            self.outline_cursor(column, row, columns)?;
+        }
+        
+        if self.incremental {
+            self.last_frame = Some((columns, frame));
+        }
+        Ok(())
+    }
+    
+    // Print one cell of a frame at the current position
+    fn print_frame_cell(&mut self, cell: &FrameCell, glyph: &str) -> crossterm::Result<()> {
+        if cell.brush && !cell.cursor {
+            execute!(self.output, SetBackgroundColor(BRUSH_COLOR))?;
+        }
+        match cell.color {
+            _ if cell.cursor => self.print_cursor_cell(glyph, cell.color),
+            Some(color) => execute!(self.output, SetForegroundColor(color), Print(glyph), ResetColor),
+            None => execute!(self.output, SetForegroundColor(DEAD_CELL_COLOR), Print(glyph), ResetColor),
+        }
+    }
+    
+    // Print the cell under the cursor in the cursor style, in the given color if it is alive
+    fn print_cursor_cell(&mut self, glyph: &str, color: Option<Color>) -> crossterm::Result<()> {
+        if let CursorStyle::Block = self.cursor_style {
+            return execute!(self.output, SetBackgroundColor(Color::Grey), Print(glyph), ResetColor);
+        }
+        
+        if let Some(color) = color {
+            execute!(self.output, SetForegroundColor(color))?;
+        }
+        if let CursorStyle::Underline = self.cursor_style {
+            execute!(self.output, SetAttribute(Attribute::Underlined), Print(glyph), SetAttribute(Attribute::NoUnderline))?;
+        } else {
+            execute!(self.output, Print(glyph))?;
+        }
+        execute!(self.output, ResetColor)
+    }
+    
+    // For the outline style, draw thin bars against the sides of the cursor cell, in the
+    // last character of the cell to its left and the first of the cell to its right.
+    // Takes the cursor's column and row among the visible cells.
+    fn outline_cursor(&mut self, column: usize, row: usize, visible_columns: usize) -> crossterm::Result<()> {
+        if self.cursor_style != CursorStyle::Outline {
+            return Ok(());
+        }
+        
+        let width = self.cell_aspect;
+        execute!(self.output, SetForegroundColor(Color::Grey))?;
+        if column > 0 {
+            execute!(self.output, MoveTo((column * width - 1) as u16, row as u16), Print('▕'))?;
+        }
+        if column + 1 < visible_columns {
+            execute!(self.output, MoveTo(((column + 1) * width) as u16, row as u16), Print('▏'))?;
+        }
+        execute!(self.output, ResetColor)
+    }
+    
+    // Draw the visible cells zoomed out, one character per overview-sized block, shaded by
+    // how many of its cells are alive. Returns the number of rows drawn.
+    fn render_overview(
+        &mut self,
+        grid: &Grid,
+        viewport_x: usize,
+        viewport_y: usize,
+        visible_width: usize,
+        visible_height: usize,
+    ) -> crossterm::Result<usize> {
+        let (grid_width, grid_height) = grid.dimensions();
+        let block = self.overview;
+        let rows = visible_height.div_ceil(block);
+        let columns = visible_width.div_ceil(block);
+        
+        // Grid coordinates of a visible offset, or None past a fixed edge
+        let wrap = self.wrap;
+        let position = |offset: usize, viewport: usize, length: usize| {
+            let coordinate = viewport + offset;
+            if wrap && length > 0 {
+                Some(coordinate % length)
+            } else {
+                (coordinate < length).then_some(coordinate)
+            }
+        };
+        
+        let mut frame = Vec::with_capacity(rows * columns);
+        let mut cursor_position = None;
+        for row in 0..rows {
+            for column in 0..columns {
+                let (mut alive, mut cells, mut has_cursor) = (0, 0, false);
+                let mut first_live = None;
                 
-                if is_cursor {
-                    execute!(
-                        self.output,
-                        SetBackgroundColor(Color::Grey),
-                        Print(cell_char),
-                        ResetColor
-                    )?;
-                } else if is_alive {
-                    let color = self.get_cell_color(x, y);
-                    execute!(
-                        self.output,
-                        SetForegroundColor(color),
-                        Print(cell_char),
-                        ResetColor
-                    )?;
-                } else {
-                    execute!(self.output, Print(cell_char))?;
+                for dy in row * block..((row + 1) * block).min(visible_height) {
+                    let Some(y) = position(dy, viewport_y, grid_height) else { continue };
+                    for dx in column * block..((column + 1) * block).min(visible_width) {
+                        let Some(x) = position(dx, viewport_x, grid_width) else { continue };
+                        cells += 1;
+                        has_cursor |= x == self.cursor_x && y == self.cursor_y;
+                        if grid.get(x, y) {
+                            alive += 1;
+                            first_live.get_or_insert((x, y));
+                        }
+                    }
+                }
+                
+                let glyph = match first_live {
+                    None => Glyph::Dead,
+                    Some(_) => Glyph::Shade((alive * OVERVIEW_GLYPHS.len()).div_ceil(cells) - 1),
+                };
+                if has_cursor {
+                    cursor_position = Some((column, row));
                 }
+                
+                frame.push(Some(FrameCell {
+                    glyph,
+                    color: first_live.map(|(x, y)| self.get_cell_color(x, y)),
+                    cursor: has_cursor,
+                    beside_cursor: false,
+                    brush: false,
+                }));
             }
-            execute!(self.output, Print("\n"))?;
         }
         
-        // Render status bar
-        let population = grid.count_alive();
-        let fps = self.fps_counter.get_fps();
+        self.draw_frame(frame, columns, cursor_position)?;
+        Ok(rows)
+    }
+    
+    // Draw the pattern search prompt over the top of the grid, with the selected candidate marked
+    // and a preview of its shape drawn to the right of the list
+    pub fn render_search(&mut self, query: &str, candidates: &[&str], selected: usize, preview: &str) -> crossterm::Result<()> {
+        self.render_prompt("/", query, "Enter-place | Esc-cancel | Up/Down-select")?;
         
-        execute!(
-            self.output,
-            MoveTo(0, visible_height as u16 + 1),
-            Print(format!(
-                "Status: {} | Gen: {} | Pop: {} | FPS: {:.1} | Speed: {} | Zoom: {}x | Cursor: ({}, {})",
-                game_state, generation, population, fps, speed, self.zoom, self.cursor_x, self.cursor_y
-            ))
-        )?;
+        if candidates.is_empty() {
+            return self.print_line(1, "  (no matching patterns)");
+        }
         
-        // Render help
-        execute!(
-            self.output,
-            MoveTo(0, visible_height as u16 + 3),
-            Print("Controls: hjkl-move | Space-toggle | Shift+Space-glider | Ctrl+Space-random | Enter-pause/resume")
-        )?;
+        for (i, name) in candidates.iter().enumerate() {
+            let marker = if i == selected { ">" } else { " " };
+            self.print_line(i + 1, &format!("{} {}", marker, name))?;
+        }
         
-        execute!(
-            self.output,
-            MoveTo(0, visible_height as u16 + 4),
-            Print("          r-randomize | c-clear | 0-9-speed | +/--zoom | Arrows-pan | z-reset view | q-quit")
-        )?;
+        // Pad the preview into a solid box so grid cells behind it don't show through
+        let column = candidates.iter().map(|name| name.chars().count()).max().unwrap_or(0) + 6;
+        let box_width = preview.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        for (i, line) in preview.lines().enumerate() {
+            execute!(
+                self.output,
+                MoveTo(column as u16, (i + 1) as u16),
+                Print(format!("{:<width$}", line, width = box_width))
+            )?;
+        }
         
         Ok(())
     }
+    
+    // Draw a one-line text prompt over the top of the grid
+    pub fn render_prompt(&mut self, prompt: &str, text: &str, hint: &str) -> crossterm::Result<()> {
+        self.print_line(0, &format!("{}{}_  ({})", prompt, text, hint))
+    }
+    
+    // Print a line of text at the given row, below or beside the grid
+    pub fn print_line(&mut self, row: usize, text: &str) -> crossterm::Result<()> {
+        // The line may cover grid cells, so the next frame has to draw everything again
+        self.last_frame = None;
+        execute!(
+            self.output,
+            MoveTo(0, row as u16),
+            Clear(ClearType::CurrentLine),
+            Print(text)
+        )
+    }
 }
\ No newline at end of file
@@ -0,0 +1,333 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::io::{self, Write};
+    use std::rc::Rc;
+    
+    use crossterm::style::{Attribute, Color, SetAttribute, SetBackgroundColor, SetForegroundColor};
+    use crossterm::terminal::{Clear, ClearType};
+    use crossterm::Command;
+    
+    use crate::config::{BoundaryType, CellTheme, ColorTheme, CursorStyle};
+    use crate::grid::Grid;
+    use crate::renderer::{grid_size_for_terminal, screen_text, sparkline, Renderer, StatusInfo, STATUS_ROWS};
+    
+    // Output shared with the test so it can be inspected while the renderer still owns the writer
+    #[derive(Clone, Default)]
+    struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+    
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    
+    impl SharedOutput {
+        // Number of cells drawn in the given color since the last call
+        fn take_count(&self, color: Color) -> usize {
+            let mut code = String::new();
+            SetForegroundColor(color).write_ansi(&mut code).unwrap();
+            self.take_text().matches(&code).count()
+        }
+        
+        // Everything written since the last call
+        fn take_text(&self) -> String {
+            String::from_utf8(std::mem::take(&mut *self.0.borrow_mut())).unwrap()
+        }
+    }
+
+    // A 20x10 grid zoomed to 2x, so the viewport shows 10x5 cells centered on the cursor at (10, 5)
+    fn zoomed_renderer(wrap: bool) -> Renderer<Vec<u8>> {
+        let mut renderer = Renderer::new(Vec::new(), 20, 10, CellTheme::Classic, ColorTheme::Green);
+        renderer.set_wrap(wrap);
+        renderer.zoom(1);
+        renderer
+    }
+
+    #[test]
+    fn test_fixed_viewport_clamps_at_edges() {
+        let mut renderer = zoomed_renderer(false);
+        assert_eq!(renderer.get_viewport_pos(), (5, 3));
+        
+        renderer.pan_viewport(-5, 0);
+        renderer.pan_viewport(-5, 0);
+        assert_eq!(renderer.get_viewport_pos(), (0, 3));
+        
+        for _ in 0..4 {
+            renderer.pan_viewport(5, 0);
+        }
+        assert_eq!(renderer.get_viewport_pos(), (10, 3));
+        
+        // The cursor stops at the edge too
+        renderer.move_cursor(30, 0);
+        assert_eq!(renderer.get_cursor_pos(), (10, 5));
+        for _ in 0..30 {
+            renderer.move_cursor(1, 0);
+        }
+        assert_eq!(renderer.get_cursor_pos(), (19, 5));
+    }
+
+    #[test]
+    fn test_wrapped_viewport_pans_around_torus() {
+        let mut renderer = zoomed_renderer(true);
+        assert_eq!(renderer.get_viewport_pos(), (5, 3));
+        
+        // Panning past zero continues from the opposite edge
+        renderer.pan_viewport(-5, -5);
+        assert_eq!(renderer.get_viewport_pos(), (0, 8));
+        renderer.pan_viewport(-5, 0);
+        assert_eq!(renderer.get_viewport_pos(), (15, 8));
+        
+        // A full lap comes back to the same place
+        for _ in 0..4 {
+            renderer.pan_viewport(5, 0);
+        }
+        assert_eq!(renderer.get_viewport_pos(), (15, 8));
+        
+        // The cursor steps across the edges, and the viewport follows it the short way round
+        renderer.move_cursor(-11, -6);
+        assert_eq!(renderer.get_cursor_pos(), (19, 9));
+        assert_eq!(renderer.get_viewport_pos(), (15, 8));
+        
+        renderer.move_cursor(6, 0);
+        assert_eq!(renderer.get_cursor_pos(), (5, 9));
+        assert_eq!(renderer.get_viewport_pos(), (16, 8));
+    }
+
+    #[test]
+    fn test_highlight_births_survivors_and_dying() {
+        let output = SharedOutput::default();
+        let mut renderer = Renderer::new(output.clone(), 7, 7, CellTheme::Classic, ColorTheme::Blue);
+        renderer.set_highlight_changes(true);
+        renderer.move_cursor(3, 3);
+        
+        // Vertical blinker; the cursor sits away from it in the corner
+        let mut grid = Grid::new(7, 7, BoundaryType::Fixed);
+        grid.set(3, 2, true);
+        grid.set(3, 3, true);
+        grid.set(3, 4, true);
+        
+        // Nothing to compare against on the first frame, so every cell counts as a survivor
+        renderer.render_grid(&grid).unwrap();
+        assert_eq!(output.take_count(Color::White), 3);
+        
+        // After a step the two end cells are new and the center survived
+        grid.update();
+        renderer.render_grid(&grid).unwrap();
+        assert_eq!(output.take_count(Color::Green), 2);
+        
+        // Redrawing the same generation keeps the same highlights
+        renderer.render_grid(&grid).unwrap();
+        assert_eq!(output.take_count(Color::Green), 2);
+        
+        // Both ends of a blinker die in the next generation
+        renderer.set_mark_dying(true);
+        renderer.render_grid(&grid).unwrap();
+        assert_eq!(output.take_count(Color::Red), 2);
+    }
+    
+    #[test]
+    fn test_view_fits_resized_terminal() {
+        // An 80x30 terminal leaves 25 rows for the grid, or 40 columns of double-width cells
+        assert_eq!(grid_size_for_terminal(80, 30, 1), (80, 30 - STATUS_ROWS));
+        assert_eq!(grid_size_for_terminal(80, 30, 2), (40, 25));
+        assert_eq!(grid_size_for_terminal(0, 3, 1), (1, 1));
+        
+        let grid = Grid::new(100, 50, BoundaryType::Fixed);
+        let mut renderer = Renderer::new(Vec::new(), 100, 50, CellTheme::Classic, ColorTheme::Green);
+        assert_eq!(renderer.render_grid(&grid).unwrap(), 50);
+        
+        // Shrinking the terminal shows fewer rows and keeps the cursor in view
+        renderer.set_screen_size(60, 20 + STATUS_ROWS);
+        assert_eq!(renderer.render_grid(&grid).unwrap(), 20);
+        let (x, y) = renderer.get_cursor_pos();
+        let (viewport_x, viewport_y) = renderer.get_viewport_pos();
+        assert!((viewport_x..viewport_x + 60).contains(&x) && (viewport_y..viewport_y + 20).contains(&y));
+        
+        // Growing the terminal again pulls a view scrolled to the far edge back inside the grid,
+        // so it can still be panned
+        renderer.move_cursor(45, 20);
+        assert_eq!(renderer.get_viewport_pos(), (36, 26));
+        renderer.set_screen_size(80, 40 + STATUS_ROWS);
+        assert_eq!(renderer.get_viewport_pos(), (20, 10));
+        renderer.pan_viewport(-5, -5);
+        assert_eq!(renderer.get_viewport_pos(), (15, 5));
+        
+        // A terminal larger than the grid never shows more rows than the grid has
+        renderer.set_screen_size(300, 200);
+        assert_eq!(renderer.render_grid(&grid).unwrap(), 50);
+        assert_eq!(renderer.get_viewport_pos(), (0, 0));
+    }
+    
+    #[test]
+    fn test_overview_never_hides_live_cells() {
+        let output = SharedOutput::default();
+        let mut renderer = Renderer::new(output.clone(), 64, 32, CellTheme::Classic, ColorTheme::Green);
+        renderer.set_screen_size(16, 8 + STATUS_ROWS);
+        
+        // One lone cell in the far corner, and a completely filled 4x4 block
+        let mut grid = Grid::new(64, 32, BoundaryType::Fixed);
+        grid.set(63, 31, true);
+        for (x, y) in (0..4).flat_map(|x| (0..4).map(move |y| (x, y))) {
+            grid.set(x, y, true);
+        }
+        
+        // Two steps out, each character covers 4x4 cells and the whole grid fits
+        renderer.zoom(-1);
+        renderer.zoom(-1);
+        assert_eq!(renderer.render_grid(&grid).unwrap(), 8);
+        let text = String::from_utf8(std::mem::take(&mut *output.0.borrow_mut())).unwrap();
+        assert_eq!(text.matches('░').count(), 1);
+        assert_eq!(text.matches('█').count(), 1);
+        
+        // Zooming back in returns to single cells
+        renderer.zoom(1);
+        renderer.zoom(1);
+        assert_eq!(renderer.render_grid(&grid).unwrap(), 8);
+        let text = String::from_utf8(std::mem::take(&mut *output.0.borrow_mut())).unwrap();
+        assert!(!text.contains('░') && !text.contains('█'));
+    }
+    
+    #[test]
+    fn test_sparkline_scales_to_window() {
+        assert_eq!(sparkline([0, 7, 14, 7].into_iter()), "▁▄█▄");
+        assert_eq!(sparkline([10, 17].into_iter()), "▁█");
+        
+        // A flat series sits at mid height
+        assert_eq!(sparkline([5, 5, 5].into_iter()), "▄▄▄");
+        assert_eq!(sparkline(std::iter::empty()), "");
+    }
+    
+    #[test]
+    fn test_cursor_styles_keep_the_cell_visible() {
+        let output = SharedOutput::default();
+        let mut renderer = Renderer::new(output.clone(), 5, 5, CellTheme::Classic, ColorTheme::Green);
+        let mut grid = Grid::new(5, 5, BoundaryType::Fixed);
+        grid.set(2, 2, true);
+        
+        fn ansi(command: impl Command) -> String {
+            let mut code = String::new();
+            command.write_ansi(&mut code).unwrap();
+            code
+        }
+        let grey_background = ansi(SetBackgroundColor(Color::Grey));
+        let underline = ansi(SetAttribute(Attribute::Underlined));
+        let green = ansi(SetForegroundColor(Color::Green));
+        
+        // The block cursor covers the live cell under it
+        renderer.render_grid(&grid).unwrap();
+        let text = output.take_text();
+        assert!(text.contains(&grey_background));
+        assert!(!text.contains(&green));
+        
+        renderer.set_cursor_style(CursorStyle::Outline);
+        renderer.render_grid(&grid).unwrap();
+        let text = output.take_text();
+        assert!(!text.contains(&grey_background));
+        assert!(text.contains(&green) && text.contains('▕') && text.contains('▏'));
+        
+        renderer.set_cursor_style(CursorStyle::Underline);
+        renderer.render_grid(&grid).unwrap();
+        let text = output.take_text();
+        assert!(text.contains(&green) && text.contains(&underline));
+    }
+    
+    #[test]
+    fn test_dead_cells_are_drawn_dimly() {
+        let output = SharedOutput::default();
+        let mut renderer = Renderer::new(output.clone(), 3, 3, CellTheme::Dot, ColorTheme::Green);
+        let mut grid = Grid::new(3, 3, BoundaryType::Fixed);
+        grid.set(0, 0, true);
+        
+        // Dead cells are blank unless marks are asked for
+        renderer.render_grid(&grid).unwrap();
+        assert!(!output.take_text().contains('·'));
+        
+        // Every dead cell then shows the mark, dimmed except under the cursor in the middle
+        renderer.set_dead_marks(true);
+        renderer.render_grid(&grid).unwrap();
+        let text = output.take_text();
+        assert_eq!(text.matches('·').count(), 8);
+        
+        renderer.render_grid(&grid).unwrap();
+        assert_eq!(output.take_count(Color::DarkGrey), 7);
+    }
+    
+    #[test]
+    fn test_incremental_frames_rewrite_only_changes() {
+        let output = SharedOutput::default();
+        let mut renderer = Renderer::new(output.clone(), 8, 8, CellTheme::Classic, ColorTheme::Green);
+        renderer.set_incremental(true);
+        renderer.set_dead_marks(true);
+        let mut grid = Grid::new(8, 8, BoundaryType::Fixed);
+        grid.set(1, 1, true);
+        
+        let clear_all = {
+            let mut code = String::new();
+            Clear(ClearType::All).write_ansi(&mut code).unwrap();
+            code
+        };
+        
+        // The first frame draws everything
+        renderer.render_grid(&grid).unwrap();
+        let text = output.take_text();
+        assert!(text.contains(&clear_all));
+        assert_eq!(text.matches('.').count(), 63);
+        
+        // An unchanged grid writes nothing, and one new cell is the only thing drawn
+        renderer.render_grid(&grid).unwrap();
+        assert_eq!(output.take_text(), "");
+        grid.set(6, 2, true);
+        renderer.render_grid(&grid).unwrap();
+        let text = output.take_text();
+        assert!(!text.contains(&clear_all));
+        assert_eq!(text.matches('O').count(), 1);
+        
+        // Moving the cursor repaints the cell it left and the one it moved to
+        renderer.move_cursor(1, 0);
+        renderer.render_grid(&grid).unwrap();
+        assert_eq!(output.take_text().matches('.').count(), 2);
+        
+        // Text drawn over the grid forces the next frame to start over
+        renderer.print_line(0, "note").unwrap();
+        output.take_text();
+        renderer.render_grid(&grid).unwrap();
+        assert!(output.take_text().contains(&clear_all));
+    }
+    
+    #[test]
+    fn test_status_and_help_fit_the_terminal_width() {
+        let grid = Grid::new(40, 10, BoundaryType::Fixed);
+        let mut renderer = Renderer::new(Vec::new(), 40, 10, CellTheme::Classic, ColorTheme::Green);
+        renderer.set_screen_size(80, 10 + STATUS_ROWS);
+        
+        let trend: VecDeque<usize> = (0..200).collect();
+        let status = StatusInfo {
+            game_state: "Running",
+            generation: 123456,
+            speed: 5,
+            population_delta: -12,
+            stable_generations: 0,
+            rule: "B3/S23",
+            auto_pause: Some("waiting"),
+            notice: None,
+            population_trend: &trend,
+            random_density: 0.3,
+        };
+        renderer.render(&grid, &status).unwrap();
+        
+        // Every line stays within the 80 columns, so none wraps into the rows below it
+        let text = screen_text(&renderer.into_output());
+        assert_eq!(text.lines().count(), 10 + STATUS_ROWS);
+        for line in text.lines() {
+            assert!(line.chars().count() <= 80, "line wraps: {}", line);
+        }
+        assert!(text.lines().any(|line| line.starts_with("Status: Running")));
+        assert!(text.lines().any(|line| line.starts_with("Controls: ")));
+    }
+}
\ No newline at end of file
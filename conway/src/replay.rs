@@ -0,0 +1,283 @@
+// Conway's Game of Life Session Replay
+// Records the input events of a play session and plays them back deterministically
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::config::BoundaryType;
+use crate::grid::Grid;
+use crate::rule::{self, RangeRule, Rule};
+
+const REPLAY_HEADER: &str = "conway-replay";
+
+/// A single recorded event
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayEvent {
+    /// A key press passed to the game's input handler
+    Key { code: KeyCode, modifiers: KeyModifiers },
+    /// Seed drawn for the next random operation (randomize or random pattern)
+    Seed(u64),
+    /// End of the recorded session
+    End,
+}
+
+/// An event stamped with the time and generation at which it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayEntry {
+    pub elapsed_ms: u64,
+    pub generation: usize,
+    pub event: ReplayEvent,
+}
+
+/// A complete recorded session: the starting board plus every event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub width: usize,
+    pub height: usize,
+    pub boundary: BoundaryType,
+    pub rule: Rule,
+    pub range_rule: Option<RangeRule>,
+    pub initial_cells: Vec<(usize, usize)>,
+    pub entries: Vec<ReplayEntry>,
+}
+
+impl Replay {
+    /// Start a new replay from the current state of a grid
+    pub fn from_grid(grid: &Grid) -> Self {
+        let (width, height) = grid.dimensions();
+        let initial_cells = grid.live_cells().collect();
+
+        Self {
+            width,
+            height,
+            boundary: grid.boundary().clone(),
+            rule: *grid.rule(),
+            range_rule: grid.range_rule().copied(),
+            initial_cells,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Build the grid this replay starts from
+    pub fn initial_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.width, self.height, self.boundary.clone());
+        grid.set_rule(self.rule);
+        grid.set_range_rule(self.range_rule);
+        for &(x, y) in &self.initial_cells {
+            grid.set(x, y, true);
+        }
+        grid
+    }
+
+    /// Save the replay to a text file
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let rule = match &self.range_rule {
+            Some(range_rule) => range_rule.to_string(),
+            None => self.rule.to_string(),
+        };
+        writeln!(writer, "{} {} {} {} {}", REPLAY_HEADER, self.width, self.height, self.boundary, rule)?;
+
+        for &(x, y) in &self.initial_cells {
+            writeln!(writer, "cell {} {}", x, y)?;
+        }
+
+        for entry in &self.entries {
+            let event = match &entry.event {
+                ReplayEvent::Key { code, modifiers } => {
+                    format!("key {} {}", encode_key_code(code), modifiers.bits())
+                },
+                ReplayEvent::Seed(seed) => format!("seed {}", seed),
+                ReplayEvent::End => "end".to_string(),
+            };
+            writeln!(writer, "{} {} {}", entry.elapsed_ms, entry.generation, event)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Load a replay from a text file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut lines = reader.lines();
+
+        let header = lines.next().ok_or_else(|| invalid_data("Empty replay file"))??;
+        let fields: Vec<&str> = header.split_whitespace().collect();
+        if !(4..=5).contains(&fields.len()) || fields[0] != REPLAY_HEADER {
+            return Err(invalid_data("Missing replay header"));
+        }
+
+        // Replays saved before the rule was recorded name none; they are taken as Conway's rule
+        let (rule, range_rule) = match fields.get(4) {
+            Some(notation) => rule::parse_any(notation).map_err(|e| invalid_data(&e.to_string()))?,
+            None => (Rule::default(), None),
+        };
+
+        let mut replay = Self {
+            width: parse_field(fields[1])?,
+            height: parse_field(fields[2])?,
+            boundary: BoundaryType::parse(fields[3])
+                .ok_or_else(|| invalid_data(&format!("Unknown boundary: {}", fields[3])))?,
+            rule,
+            range_rule,
+            initial_cells: Vec::new(),
+            entries: Vec::new(),
+        };
+
+        for line in lines {
+            let line = line?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            match fields.as_slice() {
+                [] => continue,
+                ["cell", x, y] => replay.initial_cells.push((parse_field(x)?, parse_field(y)?)),
+                [elapsed_ms, generation, event @ ..] => {
+                    let event = match event {
+                        ["key", code, modifiers] => ReplayEvent::Key {
+                            code: decode_key_code(code)?,
+                            modifiers: KeyModifiers::from_bits_truncate(parse_field(modifiers)?),
+                        },
+                        ["seed", seed] => ReplayEvent::Seed(parse_field(seed)?),
+                        ["end"] => ReplayEvent::End,
+                        _ => return Err(invalid_data(&format!("Unknown replay event: {}", line))),
+                    };
+
+                    replay.entries.push(ReplayEntry {
+                        elapsed_ms: parse_field(elapsed_ms)?,
+                        generation: parse_field(generation)?,
+                        event,
+                    });
+                },
+                _ => return Err(invalid_data(&format!("Malformed replay line: {}", line))),
+            }
+        }
+
+        Ok(replay)
+    }
+}
+
+/// Records events as they happen during a live session
+pub struct ReplayRecorder {
+    replay: Replay,
+    start: Instant,
+}
+
+impl ReplayRecorder {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            start: Instant::now(),
+        }
+    }
+
+    /// Append an event stamped with the current time and generation
+    pub fn record(&mut self, generation: usize, event: ReplayEvent) {
+        self.replay.entries.push(ReplayEntry {
+            elapsed_ms: self.start.elapsed().as_millis() as u64,
+            generation,
+            event,
+        });
+    }
+
+    /// Mark the end of the session and hand back the finished replay
+    pub fn finish(mut self, generation: usize) -> Replay {
+        self.record(generation, ReplayEvent::End);
+        self.replay
+    }
+}
+
+/// Feeds recorded events back to the game once they are due
+pub struct ReplayPlayer {
+    entries: Vec<ReplayEntry>,
+    position: usize,
+    start: Instant,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            entries: replay.entries,
+            position: 0,
+            start: Instant::now(),
+        }
+    }
+
+    /// The next event that has not been played yet
+    pub fn peek(&self) -> Option<&ReplayEntry> {
+        self.entries.get(self.position)
+    }
+
+    /// Take the next event if its timestamp has been reached
+    pub fn next_due(&mut self) -> Option<ReplayEntry> {
+        let entry = self.entries.get(self.position)?;
+        if self.start.elapsed().as_millis() < entry.elapsed_ms as u128 {
+            return None;
+        }
+
+        self.position += 1;
+        Some(entry.clone())
+    }
+
+    /// Take the next event if it is a seed, regardless of its timestamp
+    pub fn next_seed(&mut self) -> Option<u64> {
+        match self.entries.get(self.position) {
+            Some(ReplayEntry { event: ReplayEvent::Seed(seed), .. }) => {
+                let seed = *seed;
+                self.position += 1;
+                Some(seed)
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether every event has been played
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.entries.len()
+    }
+}
+
+fn encode_key_code(code: &KeyCode) -> String {
+    match code {
+        KeyCode::Char(c) => format!("char:{}", *c as u32),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn decode_key_code(s: &str) -> io::Result<KeyCode> {
+    if let Some(code) = s.strip_prefix("char:") {
+        let c = char::from_u32(parse_field(code)?)
+            .ok_or_else(|| invalid_data(&format!("Invalid character code: {}", code)))?;
+        return Ok(KeyCode::Char(c));
+    }
+
+    match s {
+        "enter" => Ok(KeyCode::Enter),
+        "backspace" => Ok(KeyCode::Backspace),
+        "esc" => Ok(KeyCode::Esc),
+        "up" => Ok(KeyCode::Up),
+        "down" => Ok(KeyCode::Down),
+        "left" => Ok(KeyCode::Left),
+        "right" => Ok(KeyCode::Right),
+        _ => Ok(KeyCode::Null),
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(s: &str) -> io::Result<T> {
+    s.parse().map_err(|_| invalid_data(&format!("Invalid replay field: {}", s)))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::grid::Grid;
+    use crate::config::BoundaryType;
+    use crate::replay::{Replay, ReplayEntry, ReplayEvent};
+    use crate::rule::{RangeRule, Rule};
+
+    #[test]
+    fn test_replay_round_trip() {
+        let mut grid = Grid::new(12, 8, BoundaryType::Fixed);
+        grid.set(1, 2, true);
+        grid.set(11, 7, true);
+        
+        let mut replay = Replay::from_grid(&grid);
+        replay.entries = vec![
+            ReplayEntry { elapsed_ms: 0, generation: 0, event: ReplayEvent::Key { code: KeyCode::Char(' '), modifiers: KeyModifiers::SHIFT } },
+            ReplayEntry { elapsed_ms: 15, generation: 3, event: ReplayEvent::Key { code: KeyCode::Enter, modifiers: KeyModifiers::NONE } },
+            ReplayEntry { elapsed_ms: 15, generation: 3, event: ReplayEvent::Seed(u64::MAX) },
+            ReplayEntry { elapsed_ms: 900, generation: 42, event: ReplayEvent::End },
+        ];
+        
+        let path = std::env::temp_dir().join(format!("conway_replay_test_{}.replay", std::process::id()));
+        replay.save_to_file(&path).unwrap();
+        let loaded = Replay::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        assert_eq!(loaded, replay);
+        
+        // The starting board is rebuilt exactly
+        let restored = loaded.initial_grid();
+        assert_eq!(restored.dimensions(), (12, 8));
+        assert!(restored.get(1, 2));
+        assert!(restored.get(11, 7));
+        assert_eq!(restored.count_alive(), 2);
+    }
+    
+    #[test]
+    fn test_boundary_names_round_trip() {
+        for boundary in [BoundaryType::Wrap, BoundaryType::Fixed, BoundaryType::Absorbing] {
+            assert_eq!(BoundaryType::parse(&boundary.to_string()), Some(boundary));
+        }
+        assert_eq!(BoundaryType::parse("Fixed"), Some(BoundaryType::Fixed));
+        assert_eq!(BoundaryType::parse("wrpa"), None);
+        
+        // Replays name the boundary and refuse ones they do not know
+        let path = std::env::temp_dir().join(format!("conway_replay_boundary_test_{}.replay", std::process::id()));
+        std::fs::write(&path, "conway-replay 4 4 absorbing\n").unwrap();
+        assert_eq!(Replay::load_from_file(&path).unwrap().boundary, BoundaryType::Absorbing);
+        std::fs::write(&path, "conway-replay 4 4 wrpa\n").unwrap();
+        assert!(Replay::load_from_file(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+    
+    #[test]
+    fn test_replay_keeps_the_rule() {
+        let path = std::env::temp_dir().join(format!("conway_replay_rule_test_{}.replay", std::process::id()));
+        let mut grid = Grid::new(16, 16, BoundaryType::Wrap);
+        grid.set_rule(Rule::parse("B36/S23").unwrap());
+        grid.set(3, 4, true);
+        
+        let replay = Replay::from_grid(&grid);
+        replay.save_to_file(&path).unwrap();
+        let loaded = Replay::load_from_file(&path).unwrap();
+        assert_eq!(loaded, replay);
+        assert_eq!(loaded.initial_grid().rule_notation(), "B36/S23");
+        
+        // Range rules come back too
+        let range_rule = RangeRule::parse("R2,C0,M0,S4..7,B5..6").unwrap();
+        grid.set_range_rule(Some(range_rule));
+        Replay::from_grid(&grid).save_to_file(&path).unwrap();
+        let restored = Replay::load_from_file(&path).unwrap().initial_grid();
+        assert_eq!(restored.range_rule(), Some(&range_rule));
+        
+        // Replays from before the rule was recorded run Conway's rule
+        std::fs::write(&path, "conway-replay 4 4 wrap\n").unwrap();
+        assert_eq!(Replay::load_from_file(&path).unwrap().initial_grid().rule_notation(), "B3/S23");
+        std::fs::write(&path, "conway-replay 4 4 wrap B9/S\n").unwrap();
+        assert!(Replay::load_from_file(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
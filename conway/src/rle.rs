@@ -0,0 +1,268 @@
+// Conway's Game of Life RLE Patterns
+// Reads and writes patterns in the run length encoded format used by most Life software
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::grid::Grid;
+
+// Longest line written in the encoded cell data
+const RLE_LINE_WIDTH: usize = 70;
+
+/// A pattern read from an RLE file
+#[derive(Debug, Clone, PartialEq)]
+pub struct RlePattern {
+    pub name: String,
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<(usize, usize)>,
+    pub author: Option<String>,   // From the #O line
+    pub comments: Vec<String>,    // From the #C lines, in order
+}
+
+/// Encode the whole grid as a named RLE pattern, including the `#N` name line and header
+pub fn encode(name: &str, grid: &Grid) -> String {
+    encode_with_metadata(name, None, &[], grid)
+}
+
+/// Encode the whole grid as a named RLE pattern, with `#O` author and `#C` comment lines
+/// between the name and the header
+pub fn encode_with_metadata(name: &str, author: Option<&str>, comments: &[String], grid: &Grid) -> String {
+    let (width, height) = grid.dimensions();
+    let mut rows: Vec<Vec<usize>> = vec![Vec::new(); height];
+    for (x, y) in grid.live_cells() {
+        rows[y].push(x);
+    }
+
+    // Build the runs: dead cells as 'b', live cells as 'o', row ends as '$'.
+    // Dead cells at the end of a row are left out, and blank rows fold into the next '$'.
+    let mut tokens = Vec::new();
+    let mut row_ends = 0;
+    for row in &rows {
+        if !row.is_empty() {
+            if row_ends > 0 {
+                tokens.push(run(row_ends, '$'));
+            }
+            row_ends = 0;
+
+            let mut x = 0;
+            let mut cells = row.iter().copied().peekable();
+            while let Some(start) = cells.next() {
+                let mut end = start + 1;
+                while cells.next_if_eq(&end).is_some() {
+                    end += 1;
+                }
+
+                if start > x {
+                    tokens.push(run(start - x, 'b'));
+                }
+                tokens.push(run(end - start, 'o'));
+                x = end;
+            }
+        }
+        row_ends += 1;
+    }
+    tokens.push("!".to_string());
+
+    let mut text = format!("#N {}\n", name);
+    if let Some(author) = author {
+        text.push_str(&format!("#O {}\n", author));
+    }
+    for comment in comments {
+        text.push_str(&format!("#C {}\n", comment));
+    }
+    text.push_str(&format!("x = {}, y = {}, rule = {}\n", width, height, grid.rule_notation()));
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length + token.len() > RLE_LINE_WIDTH {
+            text.push('\n');
+            line_length = 0;
+        }
+        line_length += token.len();
+        text.push_str(&token);
+    }
+    text.push('\n');
+
+    text
+}
+
+/// Parse every pattern in RLE text. Several patterns may follow one another,
+/// each with its own header and ending in '!'.
+pub fn parse(text: &str) -> io::Result<Vec<RlePattern>> {
+    let mut patterns = Vec::new();
+    let mut name: Option<String> = None;
+    let mut author: Option<String> = None;
+    let mut comments = Vec::new();
+    let mut size: Option<(usize, usize)> = None;
+    let mut cells = Vec::new();
+    let (mut x, mut y, mut count): (usize, usize, usize) = (0, 0, 0);
+
+    for line in text.lines().map(str::trim) {
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut chars = comment.chars();
+            let tag = chars.next();
+            let text = || chars.as_str().trim().to_string();
+            match tag {
+                Some('N') => name = Some(text()),
+                Some('O') => author = Some(text()),
+                Some('C' | 'c') => comments.push(text()),
+                _ => {},
+            }
+            continue;
+        }
+
+        if line.starts_with('x') {
+            size = Some(parse_header(line)?);
+            continue;
+        }
+
+        for c in line.chars().filter(|c| !c.is_whitespace()) {
+            let Some((width, height)) = size else {
+                return Err(invalid_data("RLE cell data before the 'x = ..., y = ...' header"));
+            };
+
+            if let Some(digit) = c.to_digit(10) {
+                count = count.checked_mul(10)
+                    .and_then(|count| count.checked_add(digit as usize))
+                    .ok_or_else(|| invalid_data("RLE run count is too large"))?;
+                continue;
+            }
+
+            let length = count.max(1);
+            count = 0;
+            // Runs may reach the edge of the header's bounding box but never go past it
+            let outside = || invalid_data(&format!("RLE run goes outside the {}x{} pattern", width, height));
+            match c {
+                'b' | '.' => {
+                    x = x.checked_add(length).filter(|&end| end <= width).ok_or_else(outside)?;
+                },
+                'o' => {
+                    let end = x.checked_add(length).filter(|&end| end <= width && y < height).ok_or_else(outside)?;
+                    cells.extend((x..end).map(|cx| (cx, y)));
+                    x = end;
+                },
+                '$' => {
+                    x = 0;
+                    y = y.checked_add(length).filter(|&end| end <= height).ok_or_else(outside)?;
+                },
+                '!' => {
+                    size = None;
+
+                    patterns.push(RlePattern {
+                        name: name.take().unwrap_or_else(|| format!("Unnamed {}", patterns.len() + 1)),
+                        width,
+                        height,
+                        cells: std::mem::take(&mut cells),
+                        author: author.take(),
+                        comments: std::mem::take(&mut comments),
+                    });
+                    (x, y) = (0, 0);
+                },
+                _ => return Err(invalid_data(&format!("Unexpected character '{}' in RLE data", c))),
+            }
+        }
+    }
+
+    if size.is_some() {
+        return Err(invalid_data("RLE pattern is missing its closing '!'"));
+    }
+
+    Ok(patterns)
+}
+
+/// Parse a pattern in the plaintext `.cells` format: `!` comment lines, with an optional
+/// `!Name:` line, then one row of cells per line with `O` (or `*`) for live cells and `.` for dead ones
+pub fn parse_plaintext(text: &str) -> io::Result<RlePattern> {
+    let mut name = None;
+    let mut comments = Vec::new();
+    let mut cells = Vec::new();
+    let (mut width, mut height) = (0, 0);
+
+    for line in text.lines().map(str::trim_end) {
+        if let Some(comment) = line.strip_prefix('!') {
+            match comment.strip_prefix("Name:") {
+                Some(text) => name = Some(text.trim().to_string()),
+                None => comments.push(comment.trim().to_string()),
+            }
+            continue;
+        }
+
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                'O' | '*' => cells.push((x, height)),
+                '.' => {},
+                _ => return Err(invalid_data(&format!("Unexpected character '{}' in plaintext pattern", c))),
+            }
+        }
+        width = width.max(line.chars().count());
+        height += 1;
+    }
+
+    Ok(RlePattern {
+        name: name.unwrap_or_else(|| "Unnamed 1".to_string()),
+        width,
+        height,
+        cells,
+        author: None,
+        comments,
+    })
+}
+
+/// Load every pattern in an RLE file
+pub fn load_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<RlePattern>> {
+    parse(&fs::read_to_string(path)?)
+}
+
+/// Load the patterns in an `.rle` or plaintext `.cells` file, picking the format by extension
+pub fn load_pattern_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<RlePattern>> {
+    let path = path.as_ref();
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("");
+
+    match extension.to_lowercase().as_str() {
+        "rle" => load_file(path),
+        "cells" => Ok(vec![parse_plaintext(&fs::read_to_string(path)?)?]),
+        _ => Err(invalid_data(&format!("Not an .rle or .cells file: {}", path.display()))),
+    }
+}
+
+/// Add the grid as a named pattern to the end of an RLE file, creating the file if needed
+pub fn append_to_file<P: AsRef<Path>>(path: P, name: &str, grid: &Grid) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(encode(name, grid).as_bytes())
+}
+
+// A run of a single tag, with the count left out when it is one
+fn run(length: usize, tag: char) -> String {
+    if length == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", length, tag)
+    }
+}
+
+// Read the width and height from an "x = 3, y = 3, rule = B3/S23" header line
+fn parse_header(line: &str) -> io::Result<(usize, usize)> {
+    let mut width = None;
+    let mut height = None;
+
+    for field in line.split(',') {
+        let Some((key, value)) = field.split_once('=') else {
+            continue;
+        };
+        let value = || value.trim().parse::<usize>()
+            .map_err(|_| invalid_data(&format!("Invalid RLE header: {}", line)));
+
+        match key.trim() {
+            "x" => width = Some(value()?),
+            "y" => height = Some(value()?),
+            _ => {},
+        }
+    }
+
+    width.zip(height).ok_or_else(|| invalid_data(&format!("Invalid RLE header: {}", line)))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
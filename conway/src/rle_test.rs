@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::BoundaryType;
+    use crate::grid::Grid;
+    use crate::patterns::PatternLibrary;
+    use crate::rle::{self, RlePattern};
+
+    #[test]
+    fn test_encode_glider() {
+        let glider = PatternLibrary::glider();
+        let mut grid = Grid::new(glider.width, glider.height, BoundaryType::Fixed);
+        glider.place(&mut grid, 0, 0);
+        
+        assert_eq!(rle::encode("Glider", &grid), "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+    }
+
+    #[test]
+    fn test_parse_round_trips_library_patterns() {
+        let text: String = PatternLibrary::get_all_patterns().iter().map(|pattern| pattern.to_rle()).collect();
+        
+        let parsed = rle::parse(&text).unwrap();
+        let library = PatternLibrary::get_all_patterns();
+        assert_eq!(parsed.len(), library.len());
+        
+        for (parsed, pattern) in parsed.iter().zip(&library) {
+            let mut cells = pattern.cells.clone();
+            cells.sort_by_key(|&(x, y)| (y, x));
+            assert_eq!(parsed, &RlePattern {
+                name: pattern.name.to_string(),
+                width: pattern.width,
+                height: pattern.height,
+                cells,
+                author: pattern.author.clone(),
+                comments: pattern.comments.clone(),
+            });
+        }
+    }
+
+    #[test]
+    fn test_parse_blank_rows_and_errors() {
+        // Blank rows fold into a counted row end; comments other than #N are ignored
+        let parsed = rle::parse("#C two blocks\nx = 2, y = 6\n2o$2o3$2o$2o!").unwrap();
+        assert_eq!(parsed[0].name, "Unnamed 1");
+        assert_eq!(parsed[0].cells, vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 4), (1, 4), (0, 5), (1, 5)]);
+        assert_eq!((parsed[0].width, parsed[0].height), (2, 6));
+        
+        assert!(rle::parse("bo$2bo$3o!").is_err());
+        assert!(rle::parse("x = 3, y = 3\nbo$2bo$3o").is_err());
+        assert!(rle::parse("x = 3, y = 3\nbo$2bq$3o!").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_oversized_runs() {
+        // A huge run count is an error rather than an overflow or a giant allocation
+        let error = rle::parse("x = 3, y = 3\n99999999999999999999999o!").unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        
+        // Runs past the header's width or height are rejected instead of growing the pattern
+        assert!(rle::parse("x = 3, y = 3\n4o!").is_err());
+        assert!(rle::parse("x = 3, y = 3\n2b2o!").is_err());
+        assert!(rle::parse("x = 3, y = 3\no3$o!").is_err());
+        assert!(rle::parse("x = 3, y = 2\n3o$3o$!").is_ok());
+    }
+    
+    #[test]
+    fn test_metadata_round_trip() {
+        let text = "#N Tweaked Gun\n#O Bill Gosper\n#C The classic gun,\n#C with a note of my own\nx = 2, y = 2, rule = B3/S23\n2o$2o!\n";
+        let parsed = rle::parse(text).unwrap();
+        assert_eq!(parsed[0].author.as_deref(), Some("Bill Gosper"));
+        assert_eq!(parsed[0].comments, ["The classic gun,", "with a note of my own"]);
+        
+        let mut grid = Grid::new(2, 2, BoundaryType::Fixed);
+        for &(x, y) in &parsed[0].cells {
+            grid.set(x, y, true);
+        }
+        let encoded = rle::encode_with_metadata(&parsed[0].name, parsed[0].author.as_deref(), &parsed[0].comments, &grid);
+        assert_eq!(encoded, text);
+        
+        // Metadata belongs to the pattern it precedes
+        let two = rle::parse("#O First\nx = 1, y = 1\no!\nx = 1, y = 1\no!").unwrap();
+        assert_eq!((two[0].author.as_deref(), two[1].author.as_deref()), (Some("First"), None));
+        
+        assert!(PatternLibrary::glider_gun().to_rle().starts_with("#N Glider Gun\n#O Bill Gosper\nx = 36"));
+    }
+
+    #[test]
+    fn test_parse_plaintext() {
+        let text = "!Name: Glider\n!The smallest spaceship\n.O\n..O\nOOO\n";
+        let pattern = rle::parse_plaintext(text).unwrap();
+        assert_eq!(pattern.name, "Glider");
+        assert_eq!(pattern.comments, vec!["The smallest spaceship"]);
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        
+        assert!(rle::parse_plaintext("!Name: Bad\nO#O\n").is_err());
+    }
+}
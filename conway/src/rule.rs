@@ -0,0 +1,274 @@
+// Conway's Game of Life Rules
+// Life-like birth/survival rules in B/S notation, e.g. "B3/S23" for Conway's rule
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Error returned when a rule string cannot be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleError {
+    /// The text is not in B/S or survival/birth notation
+    InvalidFormat(String),
+    /// A neighbor count outside 0-8
+    InvalidCount(char),
+    /// The text is not a range rule in Larger than Life notation
+    InvalidRangeRule(String),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleError::InvalidFormat(text) => {
+                write!(f, "Invalid rule '{}': expected B/S notation such as B3/S23", text)
+            },
+            RuleError::InvalidCount(c) => write!(f, "Invalid neighbor count '{}' in rule: counts are 0-8", c),
+            RuleError::InvalidRangeRule(text) => write!(
+                f,
+                "Invalid range rule '{}': expected Larger than Life notation such as R5,C0,M0,S33..57,B34..45",
+                text
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// A life-like rule: which neighbor counts bring a dead cell to life and which keep a live cell alive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+impl Rule {
+    /// Conway's rule: born with exactly 3 neighbors, survives with 2 or 3
+    pub fn conway() -> Self {
+        Self::from_counts(&[3], &[2, 3])
+    }
+
+    /// Build a rule from the neighbor counts that cause birth and survival. Counts above 8 are ignored.
+    pub fn from_counts(birth: &[usize], survival: &[usize]) -> Self {
+        let mut rule = Self { birth: [false; 9], survival: [false; 9] };
+        for &count in birth.iter().filter(|&&count| count <= 8) {
+            rule.birth[count] = true;
+        }
+        for &count in survival.iter().filter(|&&count| count <= 8) {
+            rule.survival[count] = true;
+        }
+        rule
+    }
+
+    /// Parse a rule in B/S notation ("B36/S23", in either order and any case),
+    /// or the older survival/birth notation ("23/36")
+    pub fn parse(text: &str) -> Result<Self, RuleError> {
+        let invalid = || RuleError::InvalidFormat(text.to_string());
+        let (first, second) = text.trim().split_once('/').ok_or_else(invalid)?;
+
+        let tagged = |part: &str| {
+            let mut chars = part.chars();
+            let tag = chars.next()?.to_ascii_uppercase();
+            matches!(tag, 'B' | 'S').then(|| (tag, chars.as_str().to_string()))
+        };
+
+        let (birth, survival) = match (tagged(first), tagged(second)) {
+            (Some(('B', birth)), Some(('S', survival))) | (Some(('S', survival)), Some(('B', birth))) => (birth, survival),
+            (None, None) => (second.to_string(), first.to_string()),
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self::from_counts(&parse_counts(&birth)?, &parse_counts(&survival)?))
+    }
+
+    /// Whether a dead cell with this many live neighbors comes to life
+    pub fn births(&self, neighbors: u8) -> bool {
+        self.birth.get(neighbors as usize).copied().unwrap_or(false)
+    }
+
+    /// Whether a live cell with this many live neighbors stays alive
+    pub fn survives(&self, neighbors: u8) -> bool {
+        self.survival.get(neighbors as usize).copied().unwrap_or(false)
+    }
+
+    /// The state of a cell in the next generation
+    pub fn next_state(&self, alive: bool, neighbors: u8) -> bool {
+        if alive {
+            self.survives(neighbors)
+        } else {
+            self.births(neighbors)
+        }
+    }
+
+    /// Whether this is Conway's B3/S23, which the grid updates with a dedicated fast path
+    pub fn is_conway(&self) -> bool {
+        *self == Self::conway()
+    }
+
+    /// A table of the next state for every current state and neighbor count,
+    /// marking the entries where a cell is born or survives
+    pub fn truth_table(&self) -> String {
+        let mut table = format!("Rule {}\n", self);
+        table.push_str("current  neighbors  next\n");
+
+        for alive in [false, true] {
+            for neighbors in 0..=8 {
+                let next = self.next_state(alive, neighbors);
+                let note = match (alive, next) {
+                    (false, true) => "  (birth)",
+                    (true, true) => "  (survival)",
+                    _ => "",
+                };
+
+                let line = format!("{:<8} {:<10} {:<5}{}", state_name(alive), neighbors, state_name(next), note);
+                table.push_str(line.trim_end());
+                table.push('\n');
+            }
+        }
+
+        table
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = |table: &[bool; 9]| -> String {
+            (0..=8).filter(|&count| table[count]).map(|count| count.to_string()).collect()
+        };
+        write!(f, "B{}/S{}", counts(&self.birth), counts(&self.survival))
+    }
+}
+
+/// A Larger than Life rule: neighbors are counted over a square of the given radius around
+/// each cell, not counting the cell itself, and a cell is born or survives when the count
+/// falls in an inclusive range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeRule {
+    radius: usize,
+    birth: (usize, usize),
+    survival: (usize, usize),
+}
+
+impl RangeRule {
+    /// Build a rule counting neighbors within `radius` (at least 1) in both axes
+    pub fn new(radius: usize, birth: RangeInclusive<usize>, survival: RangeInclusive<usize>) -> Self {
+        Self {
+            radius: radius.max(1),
+            birth: (*birth.start(), *birth.end()),
+            survival: (*survival.start(), *survival.end()),
+        }
+    }
+
+    /// Parse Golly's Larger than Life notation, e.g. "R5,C0,M0,S33..57,B34..45". Only
+    /// two-state rules (C0 or C2) that leave the center cell out of the count (M0) are
+    /// supported, and the R, S and B fields are required.
+    pub fn parse(text: &str) -> Result<Self, RuleError> {
+        let invalid = || RuleError::InvalidRangeRule(text.to_string());
+        let (mut radius, mut birth, mut survival) = (None, None, None);
+
+        for field in text.trim().split(',') {
+            let field = field.trim();
+            let mut chars = field.chars();
+            let tag = chars.next().ok_or_else(invalid)?.to_ascii_uppercase();
+            let value = chars.as_str();
+
+            let range = || -> Result<(usize, usize), RuleError> {
+                let (low, high) = value.split_once("..").ok_or_else(invalid)?;
+                let low = low.parse().map_err(|_| invalid())?;
+                let high = high.parse().map_err(|_| invalid())?;
+                Ok((low, high))
+            };
+
+            match tag {
+                'R' => radius = Some(value.parse::<usize>().ok().filter(|&radius| radius >= 1).ok_or_else(invalid)?),
+                'C' if matches!(value, "0" | "2") => {},
+                'M' if value == "0" => {},
+                'S' => survival = Some(range()?),
+                'B' => birth = Some(range()?),
+                _ => return Err(invalid()),
+            }
+        }
+
+        match (radius, birth, survival) {
+            (Some(radius), Some(birth), Some(survival)) => Ok(Self { radius, birth, survival }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// How far neighbors are counted from a cell in each direction
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+
+    /// Number of cells counted as neighbors, the largest count the rule can see
+    pub fn neighborhood_size(&self) -> usize {
+        let side = 2 * self.radius + 1;
+        side * side - 1
+    }
+
+    /// Whether a dead cell with this many live neighbors comes to life
+    pub fn births(&self, neighbors: usize) -> bool {
+        (self.birth.0..=self.birth.1).contains(&neighbors)
+    }
+
+    /// Whether a live cell with this many live neighbors stays alive
+    pub fn survives(&self, neighbors: usize) -> bool {
+        (self.survival.0..=self.survival.1).contains(&neighbors)
+    }
+
+    /// The state of a cell in the next generation
+    pub fn next_state(&self, alive: bool, neighbors: usize) -> bool {
+        if alive {
+            self.survives(neighbors)
+        } else {
+            self.births(neighbors)
+        }
+    }
+
+    /// A short description of the neighborhood and ranges, the counterpart of `Rule::truth_table`
+    pub fn summary(&self) -> String {
+        format!(
+            "Rule {}\nradius {}, {} neighbors\nborn with {}-{} live neighbors\nsurvives with {}-{} live neighbors\n",
+            self, self.radius, self.neighborhood_size(), self.birth.0, self.birth.1, self.survival.0, self.survival.1
+        )
+    }
+}
+
+impl fmt::Display for RangeRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "R{},C0,M0,S{}..{},B{}..{}",
+            self.radius, self.survival.0, self.survival.1, self.birth.0, self.birth.1
+        )
+    }
+}
+
+/// Parse a rule in either notation: Larger than Life when it starts with its radius
+/// ("R5,C0,M0,S33..57,B34..45"), otherwise B/S. Returns the B/S rule, Conway's rule next to a
+/// range rule, and the range rule if there is one, ready for `Grid::set_rule` and `Grid::set_range_rule`.
+pub fn parse_any(text: &str) -> Result<(Rule, Option<RangeRule>), RuleError> {
+    if text.trim_start().starts_with(['R', 'r']) {
+        Ok((Rule::default(), Some(RangeRule::parse(text)?)))
+    } else {
+        Ok((Rule::parse(text)?, None))
+    }
+}
+
+// Read the digits of one half of a rule as neighbor counts
+fn parse_counts(digits: &str) -> Result<Vec<usize>, RuleError> {
+    digits.chars()
+        .map(|c| match c.to_digit(10) {
+            Some(count) if count <= 8 => Ok(count as usize),
+            _ => Err(RuleError::InvalidCount(c)),
+        })
+        .collect()
+}
+
+fn state_name(alive: bool) -> &'static str {
+    if alive { "alive" } else { "dead" }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crate::rule::{RangeRule, Rule, RuleError};
+
+    #[test]
+    fn test_parse_notations() {
+        let highlife = Rule::from_counts(&[3, 6], &[2, 3]);
+        
+        assert_eq!(Rule::parse("B36/S23"), Ok(highlife));
+        assert_eq!(Rule::parse(" s23/b36 "), Ok(highlife));
+        assert_eq!(Rule::parse("23/36"), Ok(highlife));
+        assert_eq!(Rule::parse("B3/S23").unwrap(), Rule::conway());
+        assert_eq!(Rule::parse("B2/S").unwrap().to_string(), "B2/S");
+        assert_eq!(highlife.to_string(), "B36/S23");
+        
+        assert_eq!(Rule::parse("B3S23"), Err(RuleError::InvalidFormat("B3S23".to_string())));
+        assert_eq!(Rule::parse("B3/23"), Err(RuleError::InvalidFormat("B3/23".to_string())));
+        assert_eq!(Rule::parse("B39/S23"), Err(RuleError::InvalidCount('9')));
+    }
+
+    #[test]
+    fn test_truth_table() {
+        let table = Rule::conway().truth_table();
+        let lines: Vec<&str> = table.lines().collect();
+        
+        // Header plus one line per state and neighbor count
+        assert_eq!(lines.len(), 2 + 18);
+        assert_eq!(lines[0], "Rule B3/S23");
+        
+        let marked: Vec<&str> = lines.iter().copied().filter(|line| line.contains('(')).collect();
+        assert_eq!(marked, [
+            "dead     3          alive  (birth)",
+            "alive    2          alive  (survival)",
+            "alive    3          alive  (survival)",
+        ]);
+        assert!(lines.contains(&"alive    4          dead"));
+    }
+
+    #[test]
+    fn test_range_rule_notation() {
+        let bosco = RangeRule::parse("R5,C0,M0,S33..57,B34..45").unwrap();
+        assert_eq!(bosco, RangeRule::new(5, 34..=45, 33..=57));
+        assert_eq!(bosco.to_string(), "R5,C0,M0,S33..57,B34..45");
+        assert_eq!(bosco.neighborhood_size(), 120);
+        assert!(bosco.births(34) && bosco.births(45) && !bosco.births(46));
+        assert!(bosco.survives(33) && !bosco.survives(32));
+        assert_eq!(RangeRule::parse(" r2, b3..4, s2..5 ").unwrap(), RangeRule::new(2, 3..=4, 2..=5));
+
+        // The center cell must be left out of the count, and every range is required
+        for text in ["R5,C0,M1,S33..57,B34..45", "R0,S1..2,B3..3", "R2,S2..5", "R2,S2-5,B3..4", "B3/S23"] {
+            assert_eq!(RangeRule::parse(text), Err(RuleError::InvalidRangeRule(text.to_string())));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Conway's Game of Life Session State
+// Saves a game to a file with everything needed to carry on where it was left
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::renderer::ViewState;
+
+/// Everything about a game worth resuming: the board, the state of the simulation and
+/// the view. Only whether cells are alive is kept, not the states of multi-state automata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    pub width: usize,
+    pub height: usize,
+    pub boundary: String,
+    pub rule: String,
+    pub generation: usize,
+    pub speed: usize,
+    pub running: bool,
+    pub cells: Vec<(usize, usize)>,
+    pub view: ViewState,
+}
+
+impl SessionState {
+    /// Save the session as a TOML file
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let text = toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+
+    /// Load a session saved with `save_to_file`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
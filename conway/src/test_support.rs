@@ -0,0 +1,29 @@
+// Conway's Game of Life Test Support
+// Assertions for comparing grids against ASCII art in tests
+
+// Assert that a grid matches rows of ASCII art ('#' alive, '.' dead), panicking with
+// both grids side by side and the differing rows marked when it does not
+macro_rules! assert_grid_eq {
+    ($grid:expr, $expected:expr $(,)?) => {{
+        let actual = $grid.to_ascii();
+        let expected: Vec<String> = $expected.iter().map(|row| row.to_string()).collect();
+        if actual != expected {
+            panic!("grids differ\n{}", $crate::test_support::grid_diff(&expected, &actual));
+        }
+    }};
+}
+
+// Lay out the expected and actual grids in two columns, with '<' after each row that differs
+pub fn grid_diff(expected: &[String], actual: &[String]) -> String {
+    let width = expected.iter().map(|row| row.chars().count()).max().unwrap_or(0).max("expected".len());
+    let mut diff = format!("{:<width$}   actual\n", "expected", width = width);
+    
+    for y in 0..expected.len().max(actual.len()) {
+        let left = expected.get(y).map(String::as_str).unwrap_or("");
+        let right = actual.get(y).map(String::as_str).unwrap_or("");
+        let marker = if left == right { "" } else { "  <" };
+        diff.push_str(&format!("{:<width$} | {}{}\n", left, right, marker, width = width));
+    }
+    
+    diff
+}
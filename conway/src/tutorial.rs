@@ -2,21 +2,39 @@
 // This module provides an interactive tutorial for learning about Conway's Game of Life
 
 use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Stdout};
+use std::path::Path;
 use std::time::Duration;
 use std::thread;
 
-use crate::grid::Grid;
-use crate::config::BoundaryType;
-use crate::patterns::Pattern;
+use serde::Deserialize;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::grid::{CycleDetector, Grid, StabilizationResult};
+use crate::config::{BoundaryType, CellTheme, ColorTheme};
+use crate::patterns::{Pattern, PatternLibrary};
+use crate::renderer::Renderer;
+
+// Width used to wrap tutorial text below the grid
+const TEXT_WIDTH: usize = 78;
+
+// Most generations a run_until_stable action advances when its step file gives no limit
+pub const DEFAULT_STABILIZATION_CAP: usize = 1000;
 
 // Tutorial step structure
 pub struct TutorialStep {
-    pub title: &'static str,
-    pub description: &'static str,
+    pub title: String,
+    pub description: String,
     pub grid_config: GridConfig,
     pub actions: Vec<Action>,
     pub expected_outcome: Option<Outcome>,
     pub next_steps: Vec<usize>,
+    pub highlight_changes: bool,  // Color births, survivors and cells about to die
 }
 
 // Grid configuration for a tutorial step
@@ -32,8 +50,8 @@ pub struct GridConfig {
 pub enum Action {
     Wait(usize),        // Wait for a number of generations
     SetCells(Vec<(usize, usize)>, bool), // Set cells at positions to a state
-    RunUntilStable,     // Run until the grid stabilizes
-    Observe(&'static str), // Observe a specific phenomenon
+    RunUntilStable(usize), // Run until the grid repeats a state, for at most this many generations
+    Observe(String),    // Observe a specific phenomenon
     UserInput(UserInputType), // Wait for user input
 }
 
@@ -47,7 +65,7 @@ pub enum UserInputType {
 
 // Expected outcomes
 pub struct Outcome {
-    pub description: &'static str,
+    pub description: String,
     pub grid_state: Option<HashMap<(usize, usize), bool>>, // Optional expected grid state
     pub stable_after: Option<usize>,     // Stable after n generations
     pub oscillator_period: Option<usize>, // Oscillates with period n
@@ -60,10 +78,37 @@ pub struct Tutorial {
     grid: Grid,
 }
 
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Tutorial {
     // Create a new tutorial
     pub fn new() -> Self {
-        let steps = Self::create_tutorial_steps();
+        Self::from_steps(Self::create_tutorial_steps())
+    }
+    
+    // Load a custom tutorial from a TOML file describing its steps
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let file: TutorialFile = toml::from_str(&contents)?;
+        
+        if file.steps.is_empty() {
+            return Err("Tutorial file contains no steps".into());
+        }
+        
+        let step_count = file.steps.len();
+        let steps = file.steps.into_iter()
+            .map(|step| step.into_step(step_count))
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(Self::from_steps(steps))
+    }
+    
+    // Create a tutorial from a list of steps, starting at the first one
+    fn from_steps(steps: Vec<TutorialStep>) -> Self {
         let first_step = &steps[0];
         let grid_config = &first_step.grid_config;
         
@@ -149,24 +194,8 @@ impl Tutorial {
                     self.grid.set(x, y, state);
                 }
             },
-            Action::RunUntilStable => {
-                let mut prev_state = self.grid.count_alive();
-                let mut generations = 0;
-                let max_generations = 1000; // Safety limit
-                
-                while generations < max_generations {
-                    self.grid.update();
-                    
-                    let current_state = self.grid.count_alive();
-                    if current_state == prev_state {
-                        break;
-                    }
-                    
-                    prev_state = current_state;
-                    generations += 1;
-                    
-                    thread::sleep(Duration::from_millis(50));
-                }
+            Action::RunUntilStable(max_generations) => {
+                self.grid.run_until_stable(max_generations);
             },
             Action::Observe(_) => {
                 // This is just a marker, no actual action
@@ -180,6 +209,217 @@ impl Tutorial {
         true
     }
     
+    // Run the tutorial interactively in the terminal until the user quits
+    pub fn run(&mut self) -> crossterm::Result<()> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        
+        let result = self.run_steps();
+        
+        execute!(io::stdout(), LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        
+        result
+    }
+    
+    // Play steps one after another, following the branch the user picks
+    fn run_steps(&mut self) -> crossterm::Result<()> {
+        loop {
+            let (width, height) = self.grid.dimensions();
+            let mut renderer = Renderer::new(io::stdout(), width, height, CellTheme::Block, ColorTheme::Green);
+            renderer.set_highlight_changes(self.current_step().highlight_changes);
+            renderer.set_mark_dying(self.current_step().highlight_changes);
+            renderer.init()?;
+            
+            let choice = self.run_current_step(&mut renderer)?;
+            renderer.cleanup()?;
+            
+            match choice {
+                Some(choice) if self.next_step(choice) => {},
+                _ => break,
+            }
+        }
+        
+        Ok(())
+    }
+    
+    // Play the actions of the current step, returning the chosen branch or None if the user quit
+    fn run_current_step(&mut self, renderer: &mut Renderer<Stdout>) -> crossterm::Result<Option<usize>> {
+        let mut choice = 0;
+        let mut message = String::new();
+        let mut action_index = 0;
+        
+        self.render_step(renderer, &message)?;
+        
+        while action_index < self.current_step().actions.len() {
+            let action = self.current_step().actions[action_index].clone();
+            
+            match action {
+                Action::Wait(generations) => {
+                    for _ in 0..generations {
+                        self.grid.update();
+                        self.render_step(renderer, &message)?;
+                        if Self::quit_requested(Duration::from_millis(100))? {
+                            return Ok(None);
+                        }
+                    }
+                },
+                Action::RunUntilStable(max_generations) => {
+                    let mut detector = CycleDetector::new(&self.grid);
+                    let mut result = StabilizationResult { generations: max_generations, period: None };
+                    
+                    for _ in 0..max_generations {
+                        self.grid.update();
+                        self.render_step(renderer, &message)?;
+                        
+                        if let Some(settled) = detector.observe(&self.grid) {
+                            result = settled;
+                            break;
+                        }
+                        
+                        if Self::quit_requested(Duration::from_millis(50))? {
+                            return Ok(None);
+                        }
+                    }
+                    
+                    message = describe_stabilization(&result);
+                    self.render_step(renderer, &message)?;
+                },
+                Action::SetCells(..) => {
+                    self.execute_actions(action_index);
+                    self.render_step(renderer, &message)?;
+                },
+                Action::Observe(text) => {
+                    message = text;
+                    self.render_step(renderer, &message)?;
+                    if Self::quit_requested(Duration::from_secs(2))? {
+                        return Ok(None);
+                    }
+                },
+                Action::UserInput(UserInputType::AnyKey) => {
+                    self.render_prompt(renderer, &message, "Press any key to continue (q to quit)")?;
+                    if Self::read_key()?.code == KeyCode::Char('q') {
+                        return Ok(None);
+                    }
+                },
+                Action::UserInput(UserInputType::SpecificKey(_)) => {
+                    // A run of consecutive key choices forms a menu; the key pressed picks the branch
+                    let keys: Vec<char> = self.current_step().actions[action_index..].iter()
+                        .map_while(|action| match action {
+                            Action::UserInput(UserInputType::SpecificKey(key)) => Some(*key),
+                            _ => None,
+                        })
+                        .collect();
+                    
+                    let options: Vec<String> = keys.iter().map(|key| format!("'{}'", key)).collect();
+                    self.render_prompt(renderer, &message, &format!("Press {} to choose (q to quit)", options.join(" or ")))?;
+                    
+                    loop {
+                        match Self::read_key()?.code {
+                            KeyCode::Char('q') => return Ok(None),
+                            KeyCode::Char(c) => {
+                                if let Some(index) = keys.iter().position(|&key| key == c) {
+                                    choice = index;
+                                    break;
+                                }
+                            },
+                            _ => {},
+                        }
+                    }
+                    
+                    action_index += keys.len();
+                    continue;
+                },
+                Action::UserInput(UserInputType::Position) => {
+                    // Let the user edit the grid before moving on
+                    loop {
+                        let (x, y) = renderer.get_cursor_pos();
+                        self.render_prompt(renderer, &message, &format!(
+                            "hjkl to move, Space to toggle ({}, {}), Enter to continue (q to quit)", x, y))?;
+                        
+                        match Self::read_key()?.code {
+                            KeyCode::Char('q') => return Ok(None),
+                            KeyCode::Char('h') => renderer.move_cursor(-1, 0),
+                            KeyCode::Char('l') => renderer.move_cursor(1, 0),
+                            KeyCode::Char('k') => renderer.move_cursor(0, -1),
+                            KeyCode::Char('j') => renderer.move_cursor(0, 1),
+                            KeyCode::Char(' ') => self.grid.toggle(x, y),
+                            KeyCode::Enter => break,
+                            _ => {},
+                        }
+                    }
+                },
+            }
+            
+            action_index += 1;
+        }
+        
+        // Report the outcome before moving on
+        if let Some(outcome) = &self.current_step().expected_outcome {
+            message = if self.verify_outcome() {
+                format!("Outcome reached: {}", outcome.description)
+            } else {
+                format!("Outcome not reached: {}", outcome.description)
+            };
+        }
+        
+        self.render_prompt(renderer, &message, "Press any key for the next step (q to quit)")?;
+        if Self::read_key()?.code == KeyCode::Char('q') {
+            return Ok(None);
+        }
+        
+        Ok(Some(choice))
+    }
+    
+    // Draw the grid with the step's title, description and current message
+    fn render_step(&self, renderer: &mut Renderer<Stdout>, message: &str) -> crossterm::Result<usize> {
+        let step = self.current_step();
+        let mut row = renderer.render_grid(&self.grid)? + 1;
+        
+        renderer.print_line(row, &format!("{} (step {} of {})", step.title, self.current_step + 1, self.steps.len()))?;
+        row += 2;
+        
+        for line in wrap_text(&step.description, TEXT_WIDTH) {
+            renderer.print_line(row, &line)?;
+            row += 1;
+        }
+        
+        if !message.is_empty() {
+            row += 1;
+            for line in wrap_text(message, TEXT_WIDTH) {
+                renderer.print_line(row, &line)?;
+                row += 1;
+            }
+        }
+        
+        Ok(row + 1)
+    }
+    
+    // Draw the step followed by a prompt line
+    fn render_prompt(&self, renderer: &mut Renderer<Stdout>, message: &str, prompt: &str) -> crossterm::Result<()> {
+        let row = self.render_step(renderer, message)?;
+        renderer.print_line(row, prompt)
+    }
+    
+    // Block until a key is pressed
+    fn read_key() -> crossterm::Result<KeyEvent> {
+        loop {
+            if let Event::Key(key_event) = event::read()? {
+                return Ok(key_event);
+            }
+        }
+    }
+    
+    // Wait up to the given time, returning true if the user pressed q
+    fn quit_requested(timeout: Duration) -> crossterm::Result<bool> {
+        if event::poll(timeout)? {
+            if let Event::Key(key_event) = event::read()? {
+                return Ok(key_event.code == KeyCode::Char('q'));
+            }
+        }
+        Ok(false)
+    }
+    
     // Verify if the expected outcome has been achieved
     pub fn verify_outcome(&self) -> bool {
         if let Some(outcome) = &self.current_step().expected_outcome {
@@ -206,8 +446,8 @@ impl Tutorial {
         vec![
             // Introduction
             TutorialStep {
-                title: "Introduction to Conway's Game of Life",
-                description: "Conway's Game of Life is a cellular automaton devised by mathematician John Conway in 1970. It consists of a grid of cells, each of which can be alive or dead. The grid evolves according to simple rules based on the state of neighboring cells.",
+                title: "Introduction to Conway's Game of Life".to_string(),
+                description: "Conway's Game of Life is a cellular automaton devised by mathematician John Conway in 1970. It consists of a grid of cells, each of which can be alive or dead. The grid evolves according to simple rules based on the state of neighboring cells.".to_string(),
                 grid_config: GridConfig {
                     width: 20,
                     height: 20,
@@ -219,12 +459,13 @@ impl Tutorial {
                 ],
                 expected_outcome: None,
                 next_steps: vec![1],
+                highlight_changes: false,
             },
             
             // Rules
             TutorialStep {
-                title: "The Rules",
-                description: "The rules of the Game of Life are simple:\n1. Any live cell with fewer than two live neighbors dies (underpopulation)\n2. Any live cell with two or three live neighbors lives on\n3. Any live cell with more than three live neighbors dies (overpopulation)\n4. Any dead cell with exactly three live neighbors becomes alive (reproduction)",
+                title: "The Rules".to_string(),
+                description: "The rules of the Game of Life are simple:\n1. Any live cell with fewer than two live neighbors dies (underpopulation)\n2. Any live cell with two or three live neighbors lives on\n3. Any live cell with more than three live neighbors dies (overpopulation)\n4. Any dead cell with exactly three live neighbors becomes alive (reproduction)".to_string(),
                 grid_config: GridConfig {
                     width: 20,
                     height: 20,
@@ -235,23 +476,24 @@ impl Tutorial {
                     Action::SetCells(vec![(9, 9), (10, 9), (11, 9)], true),
                     Action::UserInput(UserInputType::AnyKey),
                     Action::Wait(1),
-                    Action::Observe("Notice how the pattern changes from a horizontal line to a vertical line"),
+                    Action::Observe("Notice how the pattern changes from a horizontal line to a vertical line".to_string()),
                     Action::Wait(1),
-                    Action::Observe("The pattern oscillates between these two states - this is called a 'blinker'"),
+                    Action::Observe("The pattern oscillates between these two states - this is called a 'blinker'".to_string()),
                 ],
                 expected_outcome: Some(Outcome {
-                    description: "The blinker pattern oscillates between horizontal and vertical orientations.",
+                    description: "The blinker pattern oscillates between horizontal and vertical orientations.".to_string(),
                     grid_state: None,
                     stable_after: None,
                     oscillator_period: Some(2),
                 }),
                 next_steps: vec![2],
+                highlight_changes: true,
             },
             
             // Still Lifes
             TutorialStep {
-                title: "Still Lifes",
-                description: "Still lifes are patterns that do not change from one generation to the next. They are stable configurations where each live cell has exactly 2 or 3 live neighbors.",
+                title: "Still Lifes".to_string(),
+                description: "Still lifes are patterns that do not change from one generation to the next. They are stable configurations where each live cell has exactly 2 or 3 live neighbors.".to_string(),
                 grid_config: GridConfig {
                     width: 30,
                     height: 20,
@@ -261,32 +503,33 @@ impl Tutorial {
                 actions: vec![
                     // Block pattern
                     Action::SetCells(vec![(5, 5), (5, 6), (6, 5), (6, 6)], true),
-                    Action::Observe("This is a 'block', the simplest still life"),
+                    Action::Observe("This is a 'block', the simplest still life".to_string()),
                     
                     // Beehive pattern
                     Action::SetCells(vec![(15, 5), (16, 4), (17, 4), (18, 5), (17, 6), (16, 6)], true),
-                    Action::Observe("This is a 'beehive', another common still life"),
+                    Action::Observe("This is a 'beehive', another common still life".to_string()),
                     
                     // Loaf pattern
                     Action::SetCells(vec![(25, 5), (26, 4), (27, 4), (28, 5), (27, 6), (26, 6), (27, 7)], true),
-                    Action::Observe("This is a 'loaf', another stable pattern"),
+                    Action::Observe("This is a 'loaf', another stable pattern".to_string()),
                     
                     Action::Wait(5),
-                    Action::Observe("Notice that none of these patterns change over time"),
+                    Action::Observe("Notice that none of these patterns change over time".to_string()),
                 ],
                 expected_outcome: Some(Outcome {
-                    description: "The still life patterns remain unchanged.",
+                    description: "The still life patterns remain unchanged.".to_string(),
                     grid_state: None,
                     stable_after: Some(1),
                     oscillator_period: None,
                 }),
                 next_steps: vec![3],
+                highlight_changes: false,
             },
             
             // Oscillators
             TutorialStep {
-                title: "Oscillators",
-                description: "Oscillators are patterns that cycle through a fixed sequence of states, eventually returning to their initial configuration.",
+                title: "Oscillators".to_string(),
+                description: "Oscillators are patterns that cycle through a fixed sequence of states, eventually returning to their initial configuration.".to_string(),
                 grid_config: GridConfig {
                     width: 40,
                     height: 20,
@@ -296,15 +539,15 @@ impl Tutorial {
                 actions: vec![
                     // Blinker
                     Action::SetCells(vec![(5, 5), (6, 5), (7, 5)], true),
-                    Action::Observe("The 'blinker' oscillates with period 2"),
+                    Action::Observe("The 'blinker' oscillates with period 2".to_string()),
                     
                     // Toad
                     Action::SetCells(vec![(15, 5), (16, 5), (17, 5), (14, 6), (15, 6), (16, 6)], true),
-                    Action::Observe("The 'toad' also oscillates with period 2"),
+                    Action::Observe("The 'toad' also oscillates with period 2".to_string()),
                     
                     // Beacon
                     Action::SetCells(vec![(25, 5), (26, 5), (25, 6), (28, 7), (29, 8), (28, 8)], true),
-                    Action::Observe("The 'beacon' oscillates with period 2 as well"),
+                    Action::Observe("The 'beacon' oscillates with period 2 as well".to_string()),
                     
                     // Pulsar
                     Action::SetCells(vec![
@@ -319,19 +562,20 @@ impl Tutorial {
                         (30, 12), (35, 12), (37, 12), (42, 12),
                         (32, 14), (33, 14), (34, 14), (38, 14), (39, 14), (40, 14),
                     ], true),
-                    Action::Observe("The 'pulsar' is a larger oscillator with period 3"),
+                    Action::Observe("The 'pulsar' is a larger oscillator with period 3".to_string()),
                     
                     Action::Wait(10),
-                    Action::Observe("Watch as these patterns cycle through their states"),
+                    Action::Observe("Watch as these patterns cycle through their states".to_string()),
                 ],
                 expected_outcome: None,
                 next_steps: vec![4],
+                highlight_changes: false,
             },
             
             // Spaceships
             TutorialStep {
-                title: "Spaceships",
-                description: "Spaceships are patterns that translate across the grid, returning to their original shape but in a different location.",
+                title: "Spaceships".to_string(),
+                description: "Spaceships are patterns that translate across the grid, returning to their original shape but in a different location.".to_string(),
                 grid_config: GridConfig {
                     width: 40,
                     height: 20,
@@ -341,23 +585,24 @@ impl Tutorial {
                 actions: vec![
                     // Glider
                     Action::SetCells(vec![(5, 5), (6, 6), (7, 6), (5, 7), (6, 7)], true),
-                    Action::Observe("This is a 'glider', the smallest spaceship"),
+                    Action::Observe("This is a 'glider', the smallest spaceship".to_string()),
                     
                     // Lightweight spaceship
                     Action::SetCells(vec![(15, 5), (18, 5), (14, 6), (14, 7), (18, 7), (14, 8), (15, 8), (16, 8), (17, 8)], true),
-                    Action::Observe("This is a 'lightweight spaceship' (LWSS)"),
+                    Action::Observe("This is a 'lightweight spaceship' (LWSS)".to_string()),
                     
                     Action::Wait(20),
-                    Action::Observe("Watch as these patterns move across the grid"),
+                    Action::Observe("Watch as these patterns move across the grid".to_string()),
                 ],
                 expected_outcome: None,
                 next_steps: vec![5],
+                highlight_changes: false,
             },
             
             // Methuselahs
             TutorialStep {
-                title: "Methuselahs",
-                description: "Methuselahs are small patterns that take a long time to stabilize, often creating complex structures in the process.",
+                title: "Methuselahs".to_string(),
+                description: "Methuselahs are small patterns that take a long time to stabilize, often creating complex structures in the process.".to_string(),
                 grid_config: GridConfig {
                     width: 40,
                     height: 30,
@@ -367,19 +612,22 @@ impl Tutorial {
                 actions: vec![
                     // R-pentomino
                     Action::SetCells(vec![(20, 15), (21, 15), (19, 16), (20, 16), (20, 17)], true),
-                    Action::Observe("This is the 'R-pentomino', which takes 1103 generations to stabilize"),
+                    Action::Observe("This is the 'R-pentomino', which takes 1103 generations to stabilize".to_string()),
                     
                     Action::Wait(50),
-                    Action::Observe("The R-pentomino produces a complex pattern that evolves for many generations"),
+                    Action::Observe("The R-pentomino produces a complex pattern that evolves for many generations".to_string()),
+                    
+                    Action::RunUntilStable(1200),
                 ],
                 expected_outcome: None,
                 next_steps: vec![6],
+                highlight_changes: false,
             },
             
             // Guns and Puffers
             TutorialStep {
-                title: "Guns and Puffers",
-                description: "Guns are patterns that periodically emit spaceships. Puffers are moving patterns that leave debris behind them.",
+                title: "Guns and Puffers".to_string(),
+                description: "Guns are patterns that periodically emit spaceships. Puffers are moving patterns that leave debris behind them.".to_string(),
                 grid_config: GridConfig {
                     width: 50,
                     height: 30,
@@ -399,19 +647,20 @@ impl Tutorial {
                         (12, 12), (16, 12),
                         (13, 13), (14, 13),
                     ], true),
-                    Action::Observe("This is the 'Gosper Glider Gun', which emits a glider every 30 generations"),
+                    Action::Observe("This is the 'Gosper Glider Gun', which emits a glider every 30 generations".to_string()),
                     
                     Action::Wait(100),
-                    Action::Observe("Watch as the gun produces a stream of gliders"),
+                    Action::Observe("Watch as the gun produces a stream of gliders".to_string()),
                 ],
                 expected_outcome: None,
                 next_steps: vec![7],
+                highlight_changes: false,
             },
             
             // Conclusion
             TutorialStep {
-                title: "Conclusion",
-                description: "You've now seen the basic patterns and behaviors in Conway's Game of Life. Despite its simple rules, the Game of Life is Turing complete, meaning it can simulate any computer algorithm. Explore more by creating your own patterns or searching for famous discoveries!",
+                title: "Conclusion".to_string(),
+                description: "You've now seen the basic patterns and behaviors in Conway's Game of Life. Despite its simple rules, the Game of Life is Turing complete, meaning it can simulate any computer algorithm. Explore more by creating your own patterns or searching for famous discoveries!".to_string(),
                 grid_config: GridConfig {
                     width: 40,
                     height: 20,
@@ -423,7 +672,163 @@ impl Tutorial {
                 ],
                 expected_outcome: None,
                 next_steps: vec![0], // Loop back to start
+                highlight_changes: false,
             },
         ]
     }
-}
\ No newline at end of file
+}
+
+// Sentence telling the user how a run_until_stable action ended
+fn describe_stabilization(result: &StabilizationResult) -> String {
+    match result.period {
+        Some(1) => format!("The grid settled into still lifes after {} generations.", result.generations),
+        Some(period) => format!("The grid settled after {} generations, repeating every {} generations.", result.generations, period),
+        None => format!("The grid was still changing after {} generations.", result.generations),
+    }
+}
+
+// Word-wrap text to the given width, keeping explicit line breaks
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.len() + 1 + word.len() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    
+    lines
+}
+
+// On-disk description of a tutorial, as read from a TOML file
+#[derive(Deserialize)]
+struct TutorialFile {
+    steps: Vec<StepSpec>,
+}
+
+#[derive(Deserialize)]
+struct StepSpec {
+    title: String,
+    description: String,
+    width: usize,
+    height: usize,
+    #[serde(default = "default_boundary")]
+    boundary: String,
+    #[serde(default)]
+    patterns: Vec<PatternSpec>,
+    #[serde(default)]
+    actions: Vec<ActionSpec>,
+    expected_outcome: Option<OutcomeSpec>,
+    #[serde(default)]
+    next_steps: Vec<usize>,
+    #[serde(default)]
+    highlight_changes: bool,
+}
+
+#[derive(Deserialize)]
+struct PatternSpec {
+    name: String,
+    x: usize,
+    y: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ActionSpec {
+    Wait(usize),
+    SetCells { cells: Vec<(usize, usize)>, alive: bool },
+    RunUntilStable,
+    RunUntilStableWithin(usize),
+    Observe(String),
+    UserInput(UserInputSpec),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum UserInputSpec {
+    AnyKey,
+    SpecificKey(char),
+    Position,
+}
+
+#[derive(Deserialize)]
+struct OutcomeSpec {
+    description: String,
+    #[serde(default)]
+    alive: Vec<(usize, usize)>,
+    #[serde(default)]
+    dead: Vec<(usize, usize)>,
+    stable_after: Option<usize>,
+    oscillator_period: Option<usize>,
+}
+
+fn default_boundary() -> String {
+    "wrap".to_string()
+}
+
+impl StepSpec {
+    fn into_step(self, step_count: usize) -> Result<TutorialStep, Box<dyn std::error::Error>> {
+        if let Some(&next) = self.next_steps.iter().find(|&&next| next >= step_count) {
+            return Err(format!("Step '{}' points to missing step {}", self.title, next).into());
+        }
+        
+        let boundary = BoundaryType::parse(&self.boundary)
+            .ok_or_else(|| format!("Step '{}' has unknown boundary '{}'", self.title, self.boundary))?;
+        
+        let initial_patterns = self.patterns.into_iter()
+            .map(|spec| match PatternLibrary::get_by_name(&spec.name) {
+                Some(pattern) => Ok((pattern, spec.x, spec.y)),
+                None => Err(format!("Unknown pattern '{}'", spec.name)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        let actions = self.actions.into_iter()
+            .map(|action| match action {
+                ActionSpec::Wait(generations) => Action::Wait(generations),
+                ActionSpec::SetCells { cells, alive } => Action::SetCells(cells, alive),
+                ActionSpec::RunUntilStable => Action::RunUntilStable(DEFAULT_STABILIZATION_CAP),
+                ActionSpec::RunUntilStableWithin(max_generations) => Action::RunUntilStable(max_generations),
+                ActionSpec::Observe(text) => Action::Observe(text),
+                ActionSpec::UserInput(UserInputSpec::AnyKey) => Action::UserInput(UserInputType::AnyKey),
+                ActionSpec::UserInput(UserInputSpec::SpecificKey(key)) => Action::UserInput(UserInputType::SpecificKey(key)),
+                ActionSpec::UserInput(UserInputSpec::Position) => Action::UserInput(UserInputType::Position),
+            })
+            .collect();
+        
+        let expected_outcome = self.expected_outcome.map(|outcome| {
+            let grid_state: HashMap<(usize, usize), bool> = outcome.alive.into_iter().map(|cell| (cell, true))
+                .chain(outcome.dead.into_iter().map(|cell| (cell, false)))
+                .collect();
+            
+            Outcome {
+                description: outcome.description,
+                grid_state: if grid_state.is_empty() { None } else { Some(grid_state) },
+                stable_after: outcome.stable_after,
+                oscillator_period: outcome.oscillator_period,
+            }
+        });
+        
+        Ok(TutorialStep {
+            title: self.title,
+            description: self.description,
+            grid_config: GridConfig {
+                width: self.width,
+                height: self.height,
+                initial_patterns,
+                boundary,
+            },
+            actions,
+            expected_outcome,
+            next_steps: self.next_steps,
+            highlight_changes: self.highlight_changes,
+        })
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::tutorial::{Action, Tutorial, UserInputType, DEFAULT_STABILIZATION_CAP};
+
+    #[test]
+    fn test_from_file_loads_example_tutorial() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tutorials/spaceships.toml");
+        let tutorial = Tutorial::from_file(path).unwrap();
+        
+        let step = tutorial.current_step();
+        assert_eq!(step.title, "Spaceships");
+        assert_eq!(step.next_steps, vec![1, 2]);
+        assert!(matches!(step.actions[1], Action::UserInput(UserInputType::SpecificKey('g'))));
+        assert_eq!(tutorial.grid().dimensions(), (40, 20));
+    }
+
+    #[test]
+    fn test_from_file_places_patterns_by_name() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tutorials/spaceships.toml");
+        let mut tutorial = Tutorial::from_file(path).unwrap();
+        
+        assert!(tutorial.next_step(0));
+        assert_eq!(tutorial.current_step().title, "The Glider");
+        assert_eq!(tutorial.grid().count_alive(), 5);
+    }
+
+    #[test]
+    fn test_from_file_rejects_unknown_pattern() {
+        let path = std::env::temp_dir().join(format!("conway_tutorial_test_{}.toml", std::process::id()));
+        std::fs::write(&path, r#"
+            [[steps]]
+            title = "Broken"
+            description = "Uses a pattern that does not exist"
+            width = 10
+            height = 10
+            patterns = [{ name = "not-a-pattern", x = 0, y = 0 }]
+        "#).unwrap();
+        
+        let result = Tutorial::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_file_reads_stabilization_caps() {
+        let path = std::env::temp_dir().join(format!("conway_tutorial_cap_test_{}.toml", std::process::id()));
+        std::fs::write(&path, r#"
+            [[steps]]
+            title = "Settle"
+            description = "Runs until stable twice"
+            width = 10
+            height = 10
+            actions = ["run_until_stable", { run_until_stable_within = 1500 }]
+        "#).unwrap();
+        
+        let result = Tutorial::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        
+        let tutorial = result.unwrap();
+        let actions = &tutorial.current_step().actions;
+        assert!(matches!(actions[0], Action::RunUntilStable(DEFAULT_STABILIZATION_CAP)));
+        assert!(matches!(actions[1], Action::RunUntilStable(1500)));
+    }
+}
@@ -5,8 +5,10 @@ use std::fs::File;
 use std::path::Path;
 use std::io::BufWriter;
 
-use image::{Rgba, RgbaImage};
+use image::{DynamicImage, Rgba, RgbaImage};
 use gif::Encoder;
+use printpdf::{BuiltinFont, Image, ImageTransform, ImageXObject, Mm, PdfDocument};
+use rayon::prelude::*;
 
 // Custom gradient implementation since we're having issues with the palette crate
 struct CustomGradient {
@@ -48,10 +50,28 @@ impl CustomGradient {
     }
 }
 
-use crate::grid::Grid;
+use crate::grid::{Grid, GridError};
 use crate::patterns::Pattern;
 use crate::config::BoundaryType;
 
+// Copies of a wrapped grid drawn across and down when tiling the torus
+const TORUS_TILES: usize = 3;
+
+// Brightness kept by cells in the outer copies of a tiled grid
+const TORUS_DIM: f32 = 0.4;
+
+// PDF page layout in millimeters (A4 portrait)
+const PDF_PAGE_WIDTH: f32 = 210.0;
+const PDF_PAGE_HEIGHT: f32 = 297.0;
+const PDF_MARGIN: f32 = 15.0;
+const MM_PER_INCH: f32 = 25.4;
+
+// Pixels per dot of the generation label font
+const LABEL_SCALE: u32 = 2;
+
+// Shortest GIF frame delay in milliseconds; most viewers play shorter delays at their own speed
+const MIN_GIF_DELAY_MS: u16 = 20;
+
 // Color themes for different visualization styles
 pub enum VisualTheme {
     // Classic black and white
@@ -66,9 +86,22 @@ pub enum VisualTheme {
     Rainbow,
     // Custom gradient from start to end color
     Custom([f32; 4], [f32; 4]),
+    // Gradient through any number of color stops, e.g. loaded from a palette file
+    Multi(Vec<[f32; 4]>),
 }
 
 impl VisualTheme {
+    // Look up a built-in theme by name, falling back to Matrix
+    pub fn from_string(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "classic" => VisualTheme::Classic,
+            "ocean" => VisualTheme::Ocean,
+            "inferno" => VisualTheme::Inferno,
+            "rainbow" => VisualTheme::Rainbow,
+            _ => VisualTheme::Matrix,
+        }
+    }
+    
     // Get the gradient for this theme
     fn get_gradient(&self) -> CustomGradient {
         match self {
@@ -113,7 +146,46 @@ impl VisualTheme {
             VisualTheme::Custom(start, end) => {
                 CustomGradient::new(vec![*start, *end])
             },
+            VisualTheme::Multi(stops) => {
+                CustomGradient::new(stops.clone())
+            },
+        }
+    }
+    
+    // Load a multi-stop gradient from a palette file. Each line holds one RGBA stop as four
+    // numbers from 0.0 to 1.0, separated by spaces or commas; blank lines and lines starting
+    // with '#' are ignored. At least two stops are required.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<VisualTheme, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut stops = Vec::new();
+        
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            
+            let components = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|field| !field.is_empty())
+                .map(|field| field.parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Line {}: {}", index + 1, e))?;
+            
+            let stop: [f32; 4] = components.try_into()
+                .map_err(|_| format!("Line {}: expected 4 color components (r g b a)", index + 1))?;
+            if stop.iter().any(|component| !(0.0..=1.0).contains(component)) {
+                return Err(format!("Line {}: color components must be between 0.0 and 1.0", index + 1).into());
+            }
+            
+            stops.push(stop);
         }
+        
+        if stops.len() < 2 {
+            return Err(format!("A palette needs at least 2 color stops, found {}", stops.len()).into());
+        }
+        
+        Ok(VisualTheme::Multi(stops))
     }
 }
 
@@ -141,6 +213,16 @@ pub struct VisualizerSettings {
     pub border_size: u32,
     // Border color (RGBA)
     pub border_color: [u8; 4],
+    // Age in generations at which a cell reaches the end of the color gradient
+    pub max_age: usize,
+    // Draw a wrapped grid as a 3x3 tiling with the outer copies dimmed, so cells crossing
+    // an edge are seen leaving one side and entering the other. Ignored for fixed grids.
+    pub tile_torus: bool,
+    // Draw live cells as anti-aliased circles instead of filled squares. Slower, but smoother
+    // for presentations.
+    pub rounded_cells: bool,
+    // Write the generation and population into the top-left corner of every frame
+    pub show_generation_label: bool,
 }
 
 impl Default for VisualizerSettings {
@@ -157,6 +239,10 @@ impl Default for VisualizerSettings {
             grid_line_color: [50, 50, 50, 255],
             border_size: 1,
             border_color: [100, 100, 100, 255],
+            max_age: 100,
+            tile_torus: false,
+            rounded_cells: false,
+            show_generation_label: false,
         }
     }
 }
@@ -166,6 +252,8 @@ pub struct Visualizer {
     settings: VisualizerSettings,
     // Keep track of how long cells have been alive
     cell_age: Vec<Vec<usize>>,
+    // Whether the grid being drawn is tiled, set up with the grid
+    tiled: bool,
 }
 
 impl Visualizer {
@@ -174,6 +262,7 @@ impl Visualizer {
         Self {
             settings,
             cell_age: Vec::new(),
+            tiled: false,
         }
     }
     
@@ -184,25 +273,31 @@ impl Visualizer {
         output_path: P,
         grid_size: (usize, usize),
         boundary: BoundaryType,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let generations = self.settings.generations;
+        self.create_pattern_gif_with_generations(pattern, output_path, grid_size, boundary, generations)
+    }
+    
+    // Create a GIF of a pattern's evolution over the given number of generations instead of
+    // the configured one. Frames are encoded as soon as they are drawn and reuse the same
+    // buffers, so memory use does not grow with the length of the animation.
+    pub fn create_pattern_gif_with_generations<P: AsRef<Path>>(
+        &mut self,
+        pattern: &Pattern,
+        output_path: P,
+        grid_size: (usize, usize),
+        boundary: BoundaryType,
+        generations: usize,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Create grid and place pattern in center
-        let mut grid = Grid::new(grid_size.0, grid_size.1, boundary);
-        let x = grid_size.0 / 2 - pattern.width / 2;
-        let y = grid_size.1 / 2 - pattern.height / 2;
-        pattern.place(&mut grid, x, y);
-        
-        // Initialize cell age tracking
-        self.cell_age = vec![vec![0; grid_size.1]; grid_size.0];
+        let mut grid = self.setup_grid(pattern, grid_size, boundary)?;
         
         // Create output file
         let file = File::create(output_path)?;
         let writer = BufWriter::new(file);
         
         // Calculate image dimensions
-        let img_width = grid_size.0 as u32 * (self.settings.cell_size + self.settings.cell_padding) 
-                          + self.settings.border_size * 2;
-        let img_height = grid_size.1 as u32 * (self.settings.cell_size + self.settings.cell_padding)
-                          + self.settings.border_size * 2;
+        let (img_width, img_height) = self.frame_size(grid_size);
         
         // Set up GIF encoder
         let mut encoder = Encoder::new(
@@ -221,71 +316,34 @@ impl Visualizer {
         // Color gradient for the theme
         let gradient = self.settings.theme.get_gradient();
         
+        if self.settings.frame_delay < MIN_GIF_DELAY_MS {
+            eprintln!(
+                "Warning: a frame delay of {}ms is too short for most GIF viewers; using {}ms instead",
+                self.settings.frame_delay, MIN_GIF_DELAY_MS
+            );
+        }
+        
+        // One image drawn over for every generation, and a copy of its pixels for the
+        // encoder, which quantizes the colors in place
+        let mut frame = RgbaImage::new(img_width, img_height);
+        let mut pixels = Vec::with_capacity(frame.as_raw().len());
+        
         // Generate frames
-        for _ in 0..self.settings.generations {
-            // Create frame
-            let mut frame = RgbaImage::new(img_width, img_height);
-            
-            // Fill background
-            for pixel in frame.pixels_mut() {
-                *pixel = Rgba(self.settings.background_color);
-            }
-            
-            // Draw border if configured
-            if self.settings.border_size > 0 {
-                self.draw_border(&mut frame, img_width, img_height);
-            }
-            
-            // Draw grid lines if configured
-            if self.settings.show_grid_lines {
-                self.draw_grid_lines(&mut frame, grid_size);
-            }
-            
-            // Draw cells
-            for y in 0..grid_size.1 {
-                for x in 0..grid_size.0 {
-                    if grid.get(x, y) {
-                        // Increment age for living cells
-                        self.cell_age[x][y] += 1;
-                        
-                        // Calculate color based on cell age
-                        let rel_age = (self.cell_age[x][y] as f32).min(100.0) / 100.0;
-                        let color = gradient.get(rel_age);
-                        
-                        // Convert to RGBA
-                        let rgba = [
-                            (color[0] * 255.0) as u8,
-                            (color[1] * 255.0) as u8,
-                            (color[2] * 255.0) as u8,
-                            255,
-                        ];
-                        
-                        // Draw the cell
-                        self.draw_cell(&mut frame, x, y, rgba);
-                    } else {
-                        // Reset age for dead cells
-                        self.cell_age[x][y] = 0;
-                    }
-                }
-            }
-            
-            // Add the frame to the GIF
-            // Create a gif frame
-            let buffer = frame.into_raw();
+        for generation in 0..generations {
+            // Draw the current generation
+            self.draw_frame_into(&mut frame, &grid, grid_size, &gradient, generation);
             
-            // Create a new frame
-            let mut frame_data = vec![0; (img_width * img_height * 4) as usize];
-            frame_data.copy_from_slice(&buffer);
+            pixels.clear();
+            pixels.extend_from_slice(frame.as_raw());
             
             // Create a gif frame from RGBA data
             let mut gif_frame = gif::Frame::from_rgba(
                 img_width as u16, 
                 img_height as u16, 
-                &mut frame_data
+                &mut pixels
             );
             
-            // Set delay in centiseconds
-            gif_frame.delay = self.settings.frame_delay / 10; // Convert to centiseconds
+            gif_frame.delay = gif_frame_delay(self.settings.frame_delay, generation);
             encoder.write_frame(&gif_frame)?;
             
             // Update the grid for the next frame
@@ -295,27 +353,209 @@ impl Visualizer {
         Ok(())
     }
     
-    // Draw a single cell on the image
-    fn draw_cell(&self, frame: &mut RgbaImage, x: usize, y: usize, color: [u8; 4]) {
-        let cell_size = self.settings.cell_size;
-        let padding = self.settings.cell_padding;
-        let border = self.settings.border_size;
+    // Create a grid with the pattern placed in the center and reset cell age tracking.
+    // An empty grid size is an error rather than an empty image.
+    fn setup_grid(&mut self, pattern: &Pattern, grid_size: (usize, usize), boundary: BoundaryType) -> Result<Grid, GridError> {
+        self.tiled = self.settings.tile_torus && matches!(boundary, BoundaryType::Wrap);
+        
+        let mut grid = Grid::try_new(grid_size.0, grid_size.1, boundary)?;
+        let (x, y) = pattern.centered_position(grid_size.0, grid_size.1);
+        pattern.place(&mut grid, x, y);
+        
+        // Initialize cell age tracking
+        self.cell_age = vec![vec![0; grid_size.1]; grid_size.0];
+        
+        Ok(grid)
+    }
+    
+    // Calculate the pixel dimensions of a single frame
+    fn frame_size(&self, grid_size: (usize, usize)) -> (u32, u32) {
+        let grid_size = self.view_size(grid_size);
+        let width = grid_size.0 as u32 * (self.settings.cell_size + self.settings.cell_padding) 
+                    + self.settings.border_size * 2;
+        let height = grid_size.1 as u32 * (self.settings.cell_size + self.settings.cell_padding)
+                     + self.settings.border_size * 2;
+        (width, height)
+    }
+    
+    // Size in cells of what is drawn: the grid, or three copies each way when tiled
+    fn view_size(&self, grid_size: (usize, usize)) -> (usize, usize) {
+        let tiles = if self.tiled { TORUS_TILES } else { 1 };
+        (grid_size.0 * tiles, grid_size.1 * tiles)
+    }
+    
+    // Draw one generation into a new frame, aging the living cells
+    fn draw_frame(&mut self, grid: &Grid, grid_size: (usize, usize), gradient: &CustomGradient, generation: usize) -> RgbaImage {
+        let (width, height) = self.frame_size(grid_size);
+        let mut frame = RgbaImage::new(width, height);
+        self.draw_frame_into(&mut frame, grid, grid_size, gradient, generation);
+        frame
+    }
+    
+    // Draw one generation over an existing frame of the right size, aging the living cells
+    fn draw_frame_into(
+        &mut self,
+        frame: &mut RgbaImage,
+        grid: &Grid,
+        grid_size: (usize, usize),
+        gradient: &CustomGradient,
+        generation: usize,
+    ) {
+        let (width, height) = frame.dimensions();
+        
+        // Fill background
+        for pixel in frame.pixels_mut() {
+            *pixel = Rgba(self.settings.background_color);
+        }
+        
+        // Draw border if configured
+        if self.settings.border_size > 0 {
+            self.draw_border(frame, width, height);
+        }
+        
+        // Draw grid lines if configured
+        if self.settings.show_grid_lines {
+            self.draw_grid_lines(frame, self.view_size(grid_size));
+        }
         
-        let start_x = border + (x as u32) * (cell_size + padding);
-        let start_y = border + (y as u32) * (cell_size + padding);
+        // Age the cells and pick their colors, one grid column per task
+        let max_age = self.settings.max_age.max(1) as f32;
+        let colors: Vec<Vec<Option<[u8; 4]>>> = self.cell_age
+            .par_iter_mut()
+            .enumerate()
+            .map(|(x, column)| {
+                column.iter_mut().enumerate().map(|(y, age)| {
+                    if !grid.get(x, y) {
+                        // Reset age for dead cells
+                        *age = 0;
+                        return None;
+                    }
+                    
+                    // Increment age for living cells
+                    *age += 1;
+                    
+                    // Calculate color based on cell age, saturating the gradient at the configured age
+                    let rel_age = (*age as f32).min(max_age) / max_age;
+                    let color = gradient.get(rel_age);
+                    
+                    // Convert to RGBA
+                    Some([
+                        (color[0] * 255.0) as u8,
+                        (color[1] * 255.0) as u8,
+                        (color[2] * 255.0) as u8,
+                        255,
+                    ])
+                }).collect()
+            })
+            .collect();
+        
+        // Draw the cells, one pixel row per task
+        if self.tiled {
+            self.draw_cells(frame, &tile_colors(&colors));
+            self.outline_center_tile(frame, grid_size);
+        } else {
+            self.draw_cells(frame, &colors);
+        }
         
-        // Fill the cell with the given color
-        for cy in 0..cell_size {
-            for cx in 0..cell_size {
-                let px = start_x + cx;
-                let py = start_y + cy;
-                if px < frame.width() && py < frame.height() {
-                    frame.put_pixel(px, py, Rgba(color));
+        if self.settings.show_generation_label {
+            self.draw_label(frame, &format!("GEN {} POP {}", generation, grid.count_alive()));
+        }
+    }
+    
+    // Write text in the label font at the top-left corner, on a patch of background color so it
+    // stays readable over live cells, in black or white, whichever stands out from the background
+    fn draw_label(&self, frame: &mut RgbaImage, text: &str) {
+        let background = self.settings.background_color;
+        let luminance = 0.299 * background[0] as f32 + 0.587 * background[1] as f32 + 0.114 * background[2] as f32;
+        let ink = if luminance > 128.0 { Rgba([0, 0, 0, 255]) } else { Rgba([255, 255, 255, 255]) };
+        
+        // Glyphs are 3x5 dots with one dot between them, and one dot of margin all round
+        let origin = self.settings.border_size;
+        let width = (text.chars().count() as u32 * 4 + 1) * LABEL_SCALE;
+        let height = 7 * LABEL_SCALE;
+        for y in origin..(origin + height).min(frame.height()) {
+            for x in origin..(origin + width).min(frame.width()) {
+                frame.put_pixel(x, y, Rgba(background));
+            }
+        }
+        
+        for (index, c) in text.chars().enumerate() {
+            for (row, bits) in label_glyph(c).iter().enumerate() {
+                for column in 0..3 {
+                    if bits & (0b100 >> column) == 0 {
+                        continue;
+                    }
+                    
+                    let left = origin + (index as u32 * 4 + column + 1) * LABEL_SCALE;
+                    let top = origin + (row as u32 + 1) * LABEL_SCALE;
+                    for y in top..(top + LABEL_SCALE).min(frame.height()) {
+                        for x in left..(left + LABEL_SCALE).min(frame.width()) {
+                            frame.put_pixel(x, y, ink);
+                        }
+                    }
                 }
             }
         }
     }
     
+    // Outline the middle copy of a tiled grid, which holds the real cells, in the border color
+    fn outline_center_tile(&self, frame: &mut RgbaImage, grid_size: (usize, usize)) {
+        let pitch = self.settings.cell_size + self.settings.cell_padding;
+        let border = self.settings.border_size;
+        let (left, top) = (border + grid_size.0 as u32 * pitch, border + grid_size.1 as u32 * pitch);
+        let (right, bottom) = (left + grid_size.0 as u32 * pitch, top + grid_size.1 as u32 * pitch);
+        let color = Rgba(self.settings.border_color);
+        
+        for x in left..=right.min(frame.width() - 1) {
+            frame.put_pixel(x, top, color);
+            frame.put_pixel(x, bottom.min(frame.height() - 1), color);
+        }
+        for y in top..=bottom.min(frame.height() - 1) {
+            frame.put_pixel(left, y, color);
+            frame.put_pixel(right.min(frame.width() - 1), y, color);
+        }
+    }
+    
+    // Fill every live cell with its color. Cells never overlap, so pixel rows are drawn independently.
+    fn draw_cells(&self, frame: &mut RgbaImage, colors: &[Vec<Option<[u8; 4]>>]) {
+        let cell_size = self.settings.cell_size as usize;
+        let pitch = cell_size + self.settings.cell_padding as usize;
+        let border = self.settings.border_size as usize;
+        let width = frame.width() as usize;
+        let coverage = self.settings.rounded_cells.then(|| circle_coverage(cell_size));
+        
+        frame.par_chunks_mut(width * 4).enumerate().for_each(|(py, row)| {
+            // Find the grid row this pixel row crosses, skipping padding and border rows
+            let Some(offset) = py.checked_sub(border) else { return };
+            let y = offset / pitch;
+            let cell_row = offset % pitch;
+            if cell_row >= cell_size {
+                return;
+            }
+            
+            for (x, column) in colors.iter().enumerate() {
+                let Some(color) = column.get(y).copied().flatten() else { continue };
+                
+                let start_x = border + x * pitch;
+                let end_x = (start_x + cell_size).min(width);
+                let pixels = row[start_x.min(width) * 4..end_x * 4].chunks_exact_mut(4);
+                match &coverage {
+                    Some(coverage) => {
+                        let row_coverage = &coverage[cell_row * cell_size..(cell_row + 1) * cell_size];
+                        for (pixel, &amount) in pixels.zip(row_coverage) {
+                            blend_pixel(pixel, &color, amount);
+                        }
+                    },
+                    None => {
+                        for pixel in pixels {
+                            pixel.copy_from_slice(&color);
+                        }
+                    },
+                }
+            }
+        });
+    }
+    
     // Draw grid lines between cells
     fn draw_grid_lines(&self, frame: &mut RgbaImage, grid_size: (usize, usize)) {
         let color = self.settings.grid_line_color;
@@ -389,13 +629,7 @@ impl Visualizer {
         boundary: BoundaryType,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Create grid and place pattern in center
-        let mut grid = Grid::new(grid_size.0, grid_size.1, boundary);
-        let x = grid_size.0 / 2 - pattern.width / 2;
-        let y = grid_size.1 / 2 - pattern.height / 2;
-        pattern.place(&mut grid, x, y);
-        
-        // Initialize cell age tracking
-        self.cell_age = vec![vec![0; grid_size.1]; grid_size.0];
+        let mut grid = self.setup_grid(pattern, grid_size, boundary)?;
         
         // Create output directory if it doesn't exist
         let output_dir = output_dir.as_ref();
@@ -403,62 +637,13 @@ impl Visualizer {
             std::fs::create_dir_all(output_dir)?;
         }
         
-        // Calculate image dimensions
-        let img_width = grid_size.0 as u32 * (self.settings.cell_size + self.settings.cell_padding) 
-                          + self.settings.border_size * 2;
-        let img_height = grid_size.1 as u32 * (self.settings.cell_size + self.settings.cell_padding)
-                          + self.settings.border_size * 2;
-        
         // Color gradient for the theme
         let gradient = self.settings.theme.get_gradient();
         
         // Generate frames
         for generation in 0..self.settings.generations {
-            // Create frame
-            let mut frame = RgbaImage::new(img_width, img_height);
-            
-            // Fill background
-            for pixel in frame.pixels_mut() {
-                *pixel = Rgba(self.settings.background_color);
-            }
-            
-            // Draw border if configured
-            if self.settings.border_size > 0 {
-                self.draw_border(&mut frame, img_width, img_height);
-            }
-            
-            // Draw grid lines if configured
-            if self.settings.show_grid_lines {
-                self.draw_grid_lines(&mut frame, grid_size);
-            }
-            
-            // Draw cells
-            for y in 0..grid_size.1 {
-                for x in 0..grid_size.0 {
-                    if grid.get(x, y) {
-                        // Increment age for living cells
-                        self.cell_age[x][y] += 1;
-                        
-                        // Calculate color based on cell age
-                        let rel_age = (self.cell_age[x][y] as f32).min(100.0) / 100.0;
-                        let color = gradient.get(rel_age);
-                        
-                        // Convert to RGBA
-                        let rgba = [
-                            (color[0] * 255.0) as u8,
-                            (color[1] * 255.0) as u8,
-                            (color[2] * 255.0) as u8,
-                            255,
-                        ];
-                        
-                        // Draw the cell
-                        self.draw_cell(&mut frame, x, y, rgba);
-                    } else {
-                        // Reset age for dead cells
-                        self.cell_age[x][y] = 0;
-                    }
-                }
-            }
+            // Draw the current generation
+            let frame = self.draw_frame(&grid, grid_size, &gradient, generation);
             
             // Save the frame as an image
             let file_name = format!("{}_gen_{:04}.png", pattern.name.to_lowercase(), generation);
@@ -483,22 +668,13 @@ impl Visualizer {
         columns: usize,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Create grid and place pattern in center
-        let mut grid = Grid::new(grid_size.0, grid_size.1, boundary);
-        let x = grid_size.0 / 2 - pattern.width / 2;
-        let y = grid_size.1 / 2 - pattern.height / 2;
-        pattern.place(&mut grid, x, y);
-        
-        // Initialize cell age tracking
-        self.cell_age = vec![vec![0; grid_size.1]; grid_size.0];
+        let mut grid = self.setup_grid(pattern, grid_size, boundary)?;
         
         // Calculate frame dimensions
-        let frame_width = grid_size.0 as u32 * (self.settings.cell_size + self.settings.cell_padding) 
-                        + self.settings.border_size * 2;
-        let frame_height = grid_size.1 as u32 * (self.settings.cell_size + self.settings.cell_padding)
-                        + self.settings.border_size * 2;
+        let (frame_width, frame_height) = self.frame_size(grid_size);
         
         // Calculate composite image dimensions
-        let rows = (generations + columns - 1) / columns;
+        let rows = generations.div_ceil(columns);
         let img_width = frame_width * columns as u32;
         let img_height = frame_height * rows as u32;
         
@@ -518,63 +694,20 @@ impl Visualizer {
             let col = generation % columns;
             let row = generation / columns;
             
-            // Create frame
-            let mut frame = RgbaImage::new(frame_width, frame_height);
-            
-            // Fill background
-            for pixel in frame.pixels_mut() {
-                *pixel = Rgba(self.settings.background_color);
-            }
-            
-            // Draw border if configured
-            if self.settings.border_size > 0 {
-                self.draw_border(&mut frame, frame_width, frame_height);
-            }
-            
-            // Draw grid lines if configured
-            if self.settings.show_grid_lines {
-                self.draw_grid_lines(&mut frame, grid_size);
-            }
-            
-            // Draw cells
-            for y in 0..grid_size.1 {
-                for x in 0..grid_size.0 {
-                    if grid.get(x, y) {
-                        // Increment age for living cells
-                        self.cell_age[x][y] += 1;
-                        
-                        // Calculate color based on cell age
-                        let rel_age = (self.cell_age[x][y] as f32).min(100.0) / 100.0;
-                        let color = gradient.get(rel_age);
-                        
-                        // Convert to RGBA
-                        let rgba = [
-                            (color[0] * 255.0) as u8,
-                            (color[1] * 255.0) as u8,
-                            (color[2] * 255.0) as u8,
-                            255,
-                        ];
-                        
-                        // Draw the cell
-                        self.draw_cell(&mut frame, x, y, rgba);
-                    } else {
-                        // Reset age for dead cells
-                        self.cell_age[x][y] = 0;
-                    }
-                }
-            }
+            // Draw the current generation
+            let frame = self.draw_frame(&grid, grid_size, &gradient, generation);
             
             // Add frame to composite
             let start_x = col as u32 * frame_width;
             let start_y = row as u32 * frame_height;
             
-            for (x, y, pixel) in frame.enumerate_pixels() {
-                let comp_x = start_x + x;
-                let comp_y = start_y + y;
-                if comp_x < img_width && comp_y < img_height {
-                    composite.put_pixel(comp_x, comp_y, *pixel);
-                }
-            }
+            let frame_row = frame_width as usize * 4;
+            let offset = start_x as usize * 4;
+            composite
+                .par_chunks_mut(img_width as usize * 4)
+                .skip(start_y as usize)
+                .zip(frame.par_chunks(frame_row))
+                .for_each(|(dest, src)| dest[offset..offset + frame_row].copy_from_slice(src));
             
             // Update the grid for the next frame
             grid.update();
@@ -585,4 +718,152 @@ impl Visualizer {
         
         Ok(())
     }
-}
\ No newline at end of file
+    
+    // Create a PDF flipbook with one generation per page, captioned with its generation and population
+    pub fn create_evolution_pdf<P: AsRef<Path>>(
+        &mut self,
+        pattern: &Pattern,
+        output_path: P,
+        grid_size: (usize, usize),
+        boundary: BoundaryType,
+        generations: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create grid and place pattern in center
+        let mut grid = self.setup_grid(pattern, grid_size, boundary)?;
+        
+        // Scale frames to fill the page inside the margins, leaving room for the caption
+        let (frame_width, frame_height) = self.frame_size(grid_size);
+        let max_width = PDF_PAGE_WIDTH - 2.0 * PDF_MARGIN;
+        let max_height = PDF_PAGE_HEIGHT - 3.0 * PDF_MARGIN;
+        let dpi = (frame_width as f32 * MM_PER_INCH / max_width)
+            .max(frame_height as f32 * MM_PER_INCH / max_height);
+        let image_width = frame_width as f32 * MM_PER_INCH / dpi;
+        let image_height = frame_height as f32 * MM_PER_INCH / dpi;
+        
+        let (document, first_page, first_layer) = PdfDocument::new(
+            format!("{} evolution", pattern.name),
+            Mm(PDF_PAGE_WIDTH),
+            Mm(PDF_PAGE_HEIGHT),
+            "Generation 0",
+        );
+        let font = document.add_builtin_font(BuiltinFont::Helvetica)?;
+        
+        // Color gradient for the theme
+        let gradient = self.settings.theme.get_gradient();
+        
+        // Generate one page per generation
+        for generation in 0..generations {
+            let (page, layer) = if generation == 0 {
+                (first_page, first_layer)
+            } else {
+                document.add_page(Mm(PDF_PAGE_WIDTH), Mm(PDF_PAGE_HEIGHT), format!("Generation {}", generation))
+            };
+            let layer = document.get_page(page).get_layer(layer);
+            
+            // Caption above the frame
+            let caption = format!(
+                "{} - Generation {} - Population {}",
+                pattern.name, generation, grid.count_alive()
+            );
+            layer.use_text(caption, 14.0, Mm(PDF_MARGIN), Mm(PDF_PAGE_HEIGHT - PDF_MARGIN), &font);
+            
+            // Draw the current generation, dropping the alpha channel since frames are opaque
+            let frame = self.draw_frame(&grid, grid_size, &gradient, generation);
+            let mut image = ImageXObject::from_dynamic_image(&DynamicImage::ImageRgb8(
+                DynamicImage::ImageRgba8(frame).to_rgb8()
+            ));
+            
+            // Keep cell edges sharp when the viewer scales the page
+            image.interpolate = false;
+            
+            Image::from(image).add_to_layer(layer, ImageTransform {
+                translate_x: Some(Mm((PDF_PAGE_WIDTH - image_width) / 2.0)),
+                translate_y: Some(Mm(PDF_PAGE_HEIGHT - 2.0 * PDF_MARGIN - image_height)),
+                dpi: Some(dpi),
+                ..Default::default()
+            });
+            
+            // Update the grid for the next frame
+            grid.update();
+        }
+        
+        document.save(&mut BufWriter::new(File::create(output_path)?))?;
+        
+        Ok(())
+    }
+}
+
+// Repeat the per-column cell colors TORUS_TILES times in each direction, dimming every copy
+// but the center one
+fn tile_colors(colors: &[Vec<Option<[u8; 4]>>]) -> Vec<Vec<Option<[u8; 4]>>> {
+    let center = TORUS_TILES / 2;
+    let dim = |color: [u8; 4]| {
+        let channel = |value: u8| (value as f32 * TORUS_DIM) as u8;
+        [channel(color[0]), channel(color[1]), channel(color[2]), color[3]]
+    };
+    
+    (0..TORUS_TILES)
+        .flat_map(|tile_x| colors.iter().map(move |column| (tile_x, column)))
+        .map(|(tile_x, column)| {
+            (0..TORUS_TILES)
+                .flat_map(|tile_y| column.iter().map(move |&color| (tile_y, color)))
+                .map(|(tile_y, color)| {
+                    if tile_x == center && tile_y == center { color } else { color.map(dim) }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Delay in centiseconds, the unit GIFs use, of the given frame of an animation with the given
+// delay in milliseconds. Delays that are not whole centiseconds are rounded frame by frame so
+// the animation keeps the requested pace overall, e.g. 25ms alternates 2 and 3. Delays below
+// the GIF minimum are raised to it.
+fn gif_frame_delay(frame_delay_ms: u16, frame: usize) -> u16 {
+    let delay = frame_delay_ms.max(MIN_GIF_DELAY_MS) as usize;
+    let shown_by = |frames: usize| (frames * delay + 5) / 10;
+    (shown_by(frame + 1) - shown_by(frame)) as u16
+}
+
+// How much of each pixel in a square cell of the given size a centered circle covers, from 0.0
+// to 1.0 in row order. Pixels the edge passes through get partial coverage for anti-aliasing.
+fn circle_coverage(cell_size: usize) -> Vec<f32> {
+    let radius = cell_size as f32 / 2.0;
+    (0..cell_size * cell_size)
+        .map(|index| {
+            let dx = (index % cell_size) as f32 + 0.5 - radius;
+            let dy = (index / cell_size) as f32 + 0.5 - radius;
+            (radius + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+// Mix a color into a pixel by the given amount, keeping the pixel's alpha
+fn blend_pixel(pixel: &mut [u8], color: &[u8; 4], amount: f32) {
+    for (channel, &target) in pixel.iter_mut().zip(color).take(3) {
+        *channel = (*channel as f32 + (target as f32 - *channel as f32) * amount).round() as u8;
+    }
+}
+
+// Rows of a 3x5 dot glyph for the generation label, top to bottom, with the leftmost dot in the
+// highest bit. Covers the digits and the letters of the label; anything else is blank.
+fn label_glyph(c: char) -> [u8; 5] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'E' => [0b111, 0b100, 0b111, 0b100, 0b111],
+        'G' => [0b111, 0b100, 0b101, 0b101, 0b111],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b111, 0b101, 0b111, 0b100, 0b100],
+        _ => [0; 5],
+    }
+}
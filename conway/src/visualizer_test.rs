@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::BoundaryType;
+    use crate::patterns::PatternLibrary;
+    use crate::visualizer::{VisualTheme, Visualizer, VisualizerSettings};
+
+    #[test]
+    fn test_from_file_loads_palette() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/palettes/sunset.palette");
+        
+        match VisualTheme::from_file(path).unwrap() {
+            VisualTheme::Multi(stops) => {
+                assert_eq!(stops.len(), 4);
+                assert_eq!(stops[0], [0.15, 0.05, 0.30, 1.0]);
+            },
+            _ => panic!("Expected a multi-stop theme"),
+        }
+    }
+
+    #[test]
+    fn test_from_file_rejects_invalid_palettes() {
+        let path = std::env::temp_dir().join(format!("conway_palette_test_{}.palette", std::process::id()));
+        
+        // A single stop is not a gradient
+        std::fs::write(&path, "# one stop\n0.0, 0.5, 1.0, 1.0\n").unwrap();
+        let error = VisualTheme::from_file(&path).err().unwrap();
+        assert!(error.to_string().contains("at least 2"));
+        
+        // Stops need all four components
+        std::fs::write(&path, "0.0 0.0 0.0 1.0\n1.0 1.0 1.0\n").unwrap();
+        let error = VisualTheme::from_file(&path).err().unwrap();
+        assert!(error.to_string().contains("Line 2"));
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+    
+    #[test]
+    fn test_tiled_torus_repeats_grid_with_dimmed_copies() {
+        let dir = std::env::temp_dir().join(format!("conway_tiles_test_{}", std::process::id()));
+        let settings = VisualizerSettings {
+            cell_size: 2,
+            cell_padding: 0,
+            border_size: 0,
+            theme: VisualTheme::Classic,
+            generations: 1,
+            max_age: 1,
+            tile_torus: true,
+            ..Default::default()
+        };
+        let mut visualizer = Visualizer::new(settings);
+        let blinker = PatternLibrary::blinker();
+        
+        visualizer.create_pattern_images(&blinker, &dir, (5, 5), BoundaryType::Wrap).unwrap();
+        let frame = image::open(dir.join("blinker_gen_0000.png")).unwrap().to_rgba8();
+        assert_eq!(frame.dimensions(), (30, 30));
+        
+        // Every live cell in the center tile is repeated, dimmed, in the top left tile
+        let live: Vec<(u32, u32)> = (10..20)
+            .flat_map(|y| (10..20).map(move |x| (x, y)))
+            .filter(|&(x, y)| frame.get_pixel(x, y)[0] == 255)
+            .collect();
+        assert_eq!(live.len(), blinker.population() * 4);
+        for (x, y) in live {
+            assert_eq!(frame.get_pixel(x - 10, y - 10)[0], 102);
+        }
+        
+        // A fixed grid is drawn once
+        visualizer.create_pattern_images(&blinker, &dir, (5, 5), BoundaryType::Fixed).unwrap();
+        let frame = image::open(dir.join("blinker_gen_0000.png")).unwrap();
+        assert_eq!((frame.width(), frame.height()), (10, 10));
+        
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn test_zero_sized_grid_is_an_error() {
+        let path = std::env::temp_dir().join(format!("conway_empty_test_{}.gif", std::process::id()));
+        let mut visualizer = Visualizer::new(VisualizerSettings::default());
+        
+        let result = visualizer.create_pattern_gif(&PatternLibrary::glider(), &path, (0, 0), BoundaryType::Wrap);
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+    
+    #[test]
+    fn test_rounded_cells_are_antialiased_circles() {
+        let dir = std::env::temp_dir().join(format!("conway_rounded_test_{}", std::process::id()));
+        let settings = VisualizerSettings {
+            cell_size: 8,
+            cell_padding: 0,
+            border_size: 0,
+            theme: VisualTheme::Classic,
+            generations: 1,
+            max_age: 1,
+            rounded_cells: true,
+            ..Default::default()
+        };
+        let mut visualizer = Visualizer::new(settings);
+        
+        visualizer.create_pattern_images(&PatternLibrary::blinker(), &dir, (5, 5), BoundaryType::Fixed).unwrap();
+        let frame = image::open(dir.join("blinker_gen_0000.png")).unwrap().to_rgba8();
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        // The middle cell of the blinker spans pixels 16 to 23: solid in the center, empty in
+        // the corners and blended along the edge of the circle
+        let cell: Vec<u8> = (16..24).flat_map(|y| (16..24).map(move |x| (x, y)))
+            .map(|(x, y)| frame.get_pixel(x, y)[0])
+            .collect();
+        assert_eq!(frame.get_pixel(20, 20)[0], 255);
+        assert_eq!(frame.get_pixel(16, 16)[0], 0);
+        assert!(cell.iter().any(|&value| value > 0 && value < 255));
+    }
+    
+    #[test]
+    fn test_generation_label_is_drawn_in_the_corner() {
+        let dir = std::env::temp_dir().join(format!("conway_label_test_{}", std::process::id()));
+        let settings = VisualizerSettings {
+            cell_size: 10,
+            cell_padding: 0,
+            border_size: 0,
+            generations: 1,
+            show_generation_label: true,
+            ..Default::default()
+        };
+        let mut visualizer = Visualizer::new(settings);
+        
+        visualizer.create_pattern_images(&PatternLibrary::blinker(), &dir, (10, 10), BoundaryType::Fixed).unwrap();
+        let frame = image::open(dir.join("blinker_gen_0000.png")).unwrap().to_rgba8();
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        // White text on the black background, starting with the top bar of the G after a margin
+        assert_eq!(frame.get_pixel(0, 0).0, [0, 0, 0, 255]);
+        assert_eq!(frame.get_pixel(2, 2).0, [255, 255, 255, 255]);
+        assert_eq!(frame.get_pixel(7, 2).0, [255, 255, 255, 255]);
+        assert_eq!(frame.get_pixel(4, 4).0, [0, 0, 0, 255]);
+    }
+    
+    #[test]
+    fn test_gif_frame_delays_keep_the_requested_pace() {
+        let path = std::env::temp_dir().join(format!("conway_delay_test_{}.gif", std::process::id()));
+        let delays = |frame_delay: u16| {
+            let settings = VisualizerSettings { frame_delay, generations: 4, ..Default::default() };
+            Visualizer::new(settings)
+                .create_pattern_gif(&PatternLibrary::blinker(), &path, (5, 5), BoundaryType::Fixed)
+                .unwrap();
+            
+            let mut decoder = gif::DecodeOptions::new().read_info(std::fs::File::open(&path).unwrap()).unwrap();
+            let mut delays = Vec::new();
+            while let Some(frame) = decoder.read_next_frame().unwrap() {
+                delays.push(frame.delay);
+            }
+            delays
+        };
+        
+        assert_eq!(delays(100), vec![10, 10, 10, 10]);
+        // Half centiseconds are made up on alternate frames
+        assert_eq!(delays(25), vec![3, 2, 3, 2]);
+        // Delays too short for viewers are raised to the minimum
+        assert_eq!(delays(5), vec![2, 2, 2, 2]);
+        
+        std::fs::remove_file(&path).unwrap();
+    }    
+    #[test]
+    fn test_evolution_pdf_has_a_page_per_generation() {
+        let path = std::env::temp_dir().join(format!("conway_flipbook_test_{}.pdf", std::process::id()));
+        let mut visualizer = Visualizer::new(VisualizerSettings { cell_size: 4, ..Default::default() });
+        visualizer.create_evolution_pdf(&PatternLibrary::glider(), &path, (8, 8), BoundaryType::Wrap, 5).unwrap();
+        
+        // Page objects are "/Type/Page"; the page tree is "/Type/Pages"
+        let pdf = String::from_utf8_lossy(&std::fs::read(&path).unwrap()).into_owned();
+        let pages = pdf.matches("/Type/Page").count() - pdf.matches("/Type/Pages").count();
+        assert_eq!(pages, 5);
+        assert!(pdf.contains("/Type/Pages/Count 5"));
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+}
\ No newline at end of file
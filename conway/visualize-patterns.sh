@@ -54,11 +54,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("  gif       - Create animated GIF");
         println!("  images    - Create sequence of images");
         println!("  evolution - Create evolution composite image");
+        println!("  pdf       - Create flipbook PDF with one generation per page");
         println!();
         println!("Examples:");
         println!("  {} gif glider glider.gif", args[0]);
         println!("  {} images blinker ./blinker_frames/", args[0]);
-        println!("  {} evolution r-pentomino r-pentomino_evolution.png 20 4", args[0]);
+        println!("  {} evolution r-pentomino r-pentomino_evolution.png matrix 20 4", args[0]);
+        println!("  {} pdf glider glider.pdf matrix 12", args[0]);
         println!();
         
         println!("Available patterns:");
@@ -73,6 +75,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("  - ocean (Blue gradient)");
         println!("  - inferno (Fire colors)");
         println!("  - rainbow (Multiple colors)");
+        println!("  - path to a palette file (see palettes/sunset.palette)");
         
         return Ok(());
     }
@@ -98,6 +101,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "ocean" => VisualTheme::Ocean,
             "inferno" => VisualTheme::Inferno,
             "rainbow" => VisualTheme::Rainbow,
+            _ if Path::new(theme_name).is_file() => VisualTheme::from_file(theme_name)?,
             _ => VisualTheme::Matrix,
         };
     }
@@ -108,13 +112,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
     
-    if args.len() > 6 {
-        if let Ok(cell_size) = args[6].parse() {
+    // Evolution images take a number of columns before the cell size
+    let cell_size_index = if mode.eq_ignore_ascii_case("evolution") { 7 } else { 6 };
+    if args.len() > cell_size_index {
+        if let Ok(cell_size) = args[cell_size_index].parse() {
             settings.cell_size = cell_size;
         }
     }
     
-    if args.len() > 7 {
+    if args.len() > 7 && cell_size_index == 6 {
         if let Ok(delay) = args[7].parse() {
             settings.frame_delay = delay;
         }
@@ -134,7 +140,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "gif" => {
             println!("Creating GIF for pattern '{}' at '{}'", pattern_name, output_path);
             visualizer.create_pattern_gif(
-                pattern,
+                &pattern,
                 output_path,
                 grid_size,
                 BoundaryType::Wrap,
@@ -144,7 +150,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "images" => {
             println!("Creating image sequence for pattern '{}' at '{}'", pattern_name, output_path);
             visualizer.create_pattern_images(
-                pattern,
+                &pattern,
                 output_path,
                 grid_size,
                 BoundaryType::Wrap,
@@ -154,21 +160,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "evolution" => {
             println!("Creating evolution image for pattern '{}' at '{}'", pattern_name, output_path);
             
-            // Parse additional parameters for evolution image
-            let generations = if args.len() > 4 {
-                args[4].parse().unwrap_or(20)
+            // Parse additional parameters for evolution image, after the theme
+            let generations = if args.len() > 5 {
+                args[5].parse().unwrap_or(20)
             } else {
                 20
             };
             
-            let columns = if args.len() > 5 {
-                args[5].parse().unwrap_or(4)
+            let columns = if args.len() > 6 {
+                args[6].parse().unwrap_or(4)
             } else {
                 4
             };
             
             visualizer.create_pattern_evolution_image(
-                pattern,
+                &pattern,
                 output_path,
                 grid_size,
                 BoundaryType::Wrap,
@@ -177,6 +183,23 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             )?;
             println!("Evolution image created successfully!");
         },
+        "pdf" => {
+            println!("Creating flipbook PDF for pattern '{}' at '{}'", pattern_name, output_path);
+            let generations = if args.len() > 5 {
+                args[5].parse().unwrap_or(20)
+            } else {
+                20
+            };
+            
+            visualizer.create_evolution_pdf(
+                &pattern,
+                output_path,
+                grid_size,
+                BoundaryType::Wrap,
+                generations,
+            )?;
+            println!("Flipbook PDF created successfully!");
+        },
         _ => {
             return Err(format!("Unknown mode: {}", mode).into());
         }
@@ -185,7 +208,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 EOF
-        }
+        fi
         
         cd "$SCRIPT_DIR" && cargo build --release --example visualizer
         if [ $? -ne 0 ]; then
@@ -284,6 +307,34 @@ visualize_evolution() {
     fi
 }
 
+visualize_pdf() {
+    local pattern=$1
+    local output_file=$2
+    local theme=${3:-matrix}
+    local generations=${4:-20}
+    local cell_size=${5:-15}
+    
+    echo -e "${CYAN}Creating flipbook PDF for pattern: ${YELLOW}$pattern${NC}"
+    echo -e "${CYAN}Output file: ${YELLOW}$output_file${NC}"
+    echo -e "${CYAN}Theme: ${YELLOW}$theme${NC}"
+    echo -e "${CYAN}Generations: ${YELLOW}$generations${NC}"
+    echo -e "${CYAN}Cell size: ${YELLOW}${cell_size}px${NC}"
+    echo
+    
+    echo -e "${GREEN}Generating flipbook PDF...${NC}"
+    
+    # Make sure output directory exists
+    mkdir -p "$(dirname "$output_file")"
+    
+    "$SCRIPT_DIR/target/release/examples/visualizer" pdf "$pattern" "$output_file" "$theme" "$generations" "$cell_size"
+    
+    if [ $? -eq 0 ]; then
+        echo -e "${GREEN}Flipbook PDF created successfully: ${YELLOW}$output_file${NC}"
+    else
+        echo -e "${RED}Failed to create flipbook PDF!${NC}"
+    fi
+}
+
 batch_process() {
     local mode=$1
     local output_dir=$2
@@ -587,6 +638,13 @@ if [[ $# -gt 0 ]]; then
             fi
             visualize_evolution "$2" "$3" "${4:-20}" "${5:-4}" "${6:-matrix}" "${7:-15}"
             ;;
+        "pdf")
+            if [[ $# -lt 3 ]]; then
+                echo -e "${RED}Missing parameters. Usage: $0 pdf <pattern_name> <output_file> [theme] [generations] [cell_size]${NC}"
+                exit 1
+            fi
+            visualize_pdf "$2" "$3" "${4:-matrix}" "${5:-20}" "${6:-15}"
+            ;;
         "batch")
             if [[ $# -lt 3 ]]; then
                 echo -e "${RED}Missing parameters. Usage: $0 batch <mode> <output_dir> [theme]${NC}"
@@ -597,7 +655,7 @@ if [[ $# -gt 0 ]]; then
             ;;
         *)
             echo -e "${RED}Unknown command: $1${NC}"
-            echo -e "${YELLOW}Available commands: gif, images, evolution, batch${NC}"
+            echo -e "${YELLOW}Available commands: gif, images, evolution, pdf, batch${NC}"
             exit 1
             ;;
     esac